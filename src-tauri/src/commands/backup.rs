@@ -0,0 +1,35 @@
+use tauri::State;
+
+use crate::commands::project::AppState;
+use crate::database::backup::{
+    create_backup, list_backups, restore_backup, BackupInfo, BackupKind,
+};
+use crate::database::migrations::rollback_migrations;
+
+#[tauri::command]
+pub fn backup_state(state: State<AppState>) -> Result<BackupInfo, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    create_backup(db.get_connection(), BackupKind::Manual).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_backups() -> Result<Vec<BackupInfo>, String> {
+    Ok(list_backups())
+}
+
+#[tauri::command]
+pub fn restore_state(state: State<AppState>, path: String) -> Result<(), String> {
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    restore_backup(db.get_connection_mut(), std::path::Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Revert the schema to `target_version` while working on a migration
+#[tauri::command]
+pub fn rollback_database(state: State<AppState>, target_version: i32) -> Result<(), String> {
+    if !cfg!(debug_assertions) {
+        return Err("Rolling back migrations is only available in development builds".to_string());
+    }
+
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    rollback_migrations(db.get_connection_mut(), target_version)
+}
@@ -0,0 +1,256 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::project::AppState;
+use crate::config_store::config::load_settings;
+use crate::models::project::Project;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::utils::disk_usage::{
+    find_artifacts, remove_artifact, Artifact, ArtifactKind, CleanMode,
+};
+use crate::utils::scan_ignore::ScanIgnore;
+use serde::Serialize;
+
+/// Event every disk usage job reports on
+pub const DISK_USAGE_EVENT: &str = "disk-usage";
+
+/// Cancellation flags of running disk usage jobs, by job id
+#[derive(Default)]
+pub struct DiskUsageJobs {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum DiskUsageEvent {
+    Project {
+        project_id: String,
+        total_size: u64,
+        artifacts: Vec<Artifact>,
+    },
+    Finished {
+        cancelled: bool,
+    },
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskUsageJobEvent {
+    job_id: String,
+    #[serde(flatten)]
+    event: DiskUsageEvent,
+}
+
+/// What `clean_project` removed, or would remove in a dry run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanReport {
+    pub dry_run: bool,
+    pub removed: Vec<Artifact>,
+    pub freed: u64,
+    pub errors: Vec<CleanError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanError {
+    pub path: String,
+    pub message: String,
+}
+
+fn find_project(state: &AppState, project_id: &str) -> Result<Project, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    ProjectRepository::new(db.get_connection())
+        .find_by_id(project_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Project not found".to_string())
+}
+
+/// Measure the artifact directories of the given projects, or of every
+/// registered project, in the background and return the job id. Each
+/// project is reported as a `disk-usage` event once measured; projects
+/// still being measured when the job is cancelled are not reported.
+#[tauri::command]
+pub fn start_disk_usage_scan(
+    app: AppHandle,
+    state: State<AppState>,
+    jobs: State<DiskUsageJobs>,
+    project_ids: Option<Vec<String>>,
+) -> Result<String, String> {
+    let projects = match project_ids {
+        Some(ids) => ids
+            .iter()
+            .map(|id| find_project(&state, id))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => {
+            let db = state.db.lock().map_err(|e| e.to_string())?;
+            ProjectRepository::new(db.get_connection())
+                .find_all()
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    let ignore_patterns = load_settings().ignore_patterns;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    jobs.jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(job_id.clone(), cancelled.clone());
+
+    let id = job_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let emit = |event| {
+            app.emit(
+                DISK_USAGE_EVENT,
+                DiskUsageJobEvent {
+                    job_id: id.clone(),
+                    event,
+                },
+            )
+            .ok();
+        };
+
+        projects.par_iter().for_each(|project| {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let folder = Path::new(&project.folder);
+            let ignore = ScanIgnore::new(folder, &ignore_patterns);
+            let Some(artifacts) = find_artifacts(folder, &ignore, &cancelled) else {
+                return;
+            };
+            emit(DiskUsageEvent::Project {
+                project_id: project.id.clone(),
+                total_size: artifacts.iter().map(|a| a.size).sum(),
+                artifacts,
+            });
+        });
+
+        emit(DiskUsageEvent::Finished {
+            cancelled: cancelled.load(Ordering::Relaxed),
+        });
+
+        if let Ok(mut jobs) = app.state::<DiskUsageJobs>().jobs.lock() {
+            jobs.remove(&id);
+        }
+    });
+
+    Ok(job_id)
+}
+
+/// Returns false when the job is unknown or already done
+#[tauri::command]
+pub fn cancel_disk_usage_scan(jobs: State<DiskUsageJobs>, job_id: String) -> Result<bool, String> {
+    let jobs = jobs.jobs.lock().map_err(|e| e.to_string())?;
+
+    match jobs.get(&job_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Remove the artifact directories of the given kinds from a project,
+/// moving them to the trash unless `mode` is `delete`. A dry run only lists
+/// what would be removed.
+#[tauri::command]
+pub async fn clean_project(
+    state: State<'_, AppState>,
+    project_id: String,
+    kinds: Vec<ArtifactKind>,
+    mode: Option<CleanMode>,
+    dry_run: Option<bool>,
+) -> Result<CleanReport, String> {
+    if kinds.is_empty() {
+        return Err("No artifact kinds selected".to_string());
+    }
+
+    let project = find_project(&state, &project_id)?;
+    let mode = mode.unwrap_or_default();
+    let dry_run = dry_run.unwrap_or(false);
+    let ignore_patterns = load_settings().ignore_patterns;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let ignore = ScanIgnore::new(Path::new(&project.folder), &ignore_patterns);
+        clean_folder(Path::new(&project.folder), &kinds, mode, dry_run, &ignore)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn clean_folder(
+    folder: &Path,
+    kinds: &[ArtifactKind],
+    mode: CleanMode,
+    dry_run: bool,
+    ignore: &ScanIgnore,
+) -> CleanReport {
+    let artifacts: Vec<Artifact> = find_artifacts(folder, ignore, &AtomicBool::new(false))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|artifact| kinds.contains(&artifact.kind))
+        .collect();
+
+    let mut report = CleanReport {
+        dry_run,
+        removed: Vec::new(),
+        freed: 0,
+        errors: Vec::new(),
+    };
+
+    for artifact in artifacts {
+        if !dry_run {
+            if let Err(message) = remove_artifact(&artifact, mode) {
+                report.errors.push(CleanError {
+                    path: artifact.path,
+                    message,
+                });
+                continue;
+            }
+        }
+        report.freed += artifact.size;
+        report.removed.push(artifact);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_clean_folder_dry_run() {
+        let fixture = Fixture::new(&[
+            ("package.json", "{}"),
+            ("node_modules/vite/index.js", &"x".repeat(200)),
+            ("dist/index.html", &"x".repeat(50)),
+        ]);
+        let ignore = ScanIgnore::new(fixture.path(), &[]);
+        let kinds = [ArtifactKind::NodeModules];
+
+        let report = clean_folder(fixture.path(), &kinds, CleanMode::Delete, true, &ignore);
+        assert!(report.dry_run);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.freed, 200);
+        assert!(fixture.join("node_modules").exists());
+
+        let report = clean_folder(fixture.path(), &kinds, CleanMode::Delete, false, &ignore);
+        assert_eq!(report.freed, 200);
+        assert!(report.errors.is_empty());
+        assert!(!fixture.join("node_modules").exists());
+        assert!(fixture.join("dist").exists());
+    }
+}
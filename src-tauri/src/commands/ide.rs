@@ -1,3 +1,5 @@
+#[cfg(not(target_os = "windows"))]
+use crate::utils::launch_env::shell_escape;
 use std::process::Stdio;
 use tokio::process::Command;
 
@@ -82,10 +84,3 @@ async fn open_in_ide_unix(path: &str, ide_command: &str) -> Result<(), String> {
 
     Ok(())
 }
-
-/// Wraps a string in single quotes and escapes any contained single quotes,
-/// producing a safe POSIX shell word even for paths with spaces.
-#[cfg(not(target_os = "windows"))]
-fn shell_escape(s: &str) -> String {
-    format!("'{}'", s.replace('\'', "'\\''"))
-}
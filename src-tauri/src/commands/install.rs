@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::project::AppState;
+use crate::config_store::install_state::record_lockfile_hash;
+use crate::models::detected_service::InstallStatus;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::repositories::service_repository::ServiceRepository;
+use crate::utils::detectors::{detect_install_status, detect_package_manager, find_lockfile};
+use crate::utils::launch_env::{read_lines, resolve_launch_command, LaunchCommand};
+use crate::utils::parsers::DetectionRules;
+use crate::utils::project_scanner::detect_service;
+use serde::Serialize;
+
+/// Event every dependency install reports on
+pub const INSTALL_EVENT: &str = "dependency-install";
+
+/// Ids of the services with an install running
+#[derive(Default)]
+pub struct InstallJobs {
+    running: Mutex<HashSet<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum InstallEvent {
+    /// The install command was found and is starting
+    Started {
+        command: String,
+    },
+    Output {
+        line: String,
+        stderr: bool,
+    },
+    Finished {
+        success: bool,
+        exit_code: Option<i32>,
+        /// Status once the install is over
+        status: Option<InstallStatus>,
+    },
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallJobEvent {
+    service_id: String,
+    #[serde(flatten)]
+    event: InstallEvent,
+}
+
+/// Marks a service as installing until dropped, also when the install
+/// panics, so it can be installed again
+struct RunningInstall {
+    app: AppHandle,
+    service_id: String,
+}
+
+impl Drop for RunningInstall {
+    fn drop(&mut self) {
+        let jobs = self.app.state::<InstallJobs>();
+        let mut running = jobs
+            .running
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        running.remove(&self.service_id);
+    }
+}
+
+/// Directory of a registered service and of its project
+fn service_dirs(state: &AppState, service_id: &str) -> Result<(PathBuf, PathBuf), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let conn = db.get_connection();
+
+    let service = ServiceRepository::new(conn)
+        .find_by_id(service_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Service not found".to_string())?;
+    let project = ProjectRepository::new(conn)
+        .find_by_id(&service.project_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    Ok((PathBuf::from(service.path), PathBuf::from(project.folder)))
+}
+
+#[tauri::command]
+pub fn get_install_status(
+    state: State<AppState>,
+    service_id: String,
+) -> Result<Option<InstallStatus>, String> {
+    let (path, _) = service_dirs(&state, &service_id)?;
+    Ok(detect_install_status(&path, &detect_package_manager(&path)))
+}
+
+/// Run the service's install command in the background. The command, its
+/// output lines and the outcome are emitted as `dependency-install` events.
+#[tauri::command]
+pub async fn install_dependencies(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    jobs: State<'_, InstallJobs>,
+    service_id: String,
+) -> Result<(), String> {
+    let (path, root) = service_dirs(&state, &service_id)?;
+
+    if !jobs
+        .running
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(service_id.clone())
+    {
+        return Err("Dependencies are already being installed".to_string());
+    }
+    let running = RunningInstall {
+        app: app.clone(),
+        service_id: service_id.clone(),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let _running = running;
+        let emit = |event| {
+            app.emit(
+                INSTALL_EVENT,
+                InstallJobEvent {
+                    service_id: service_id.clone(),
+                    event,
+                },
+            )
+            .ok();
+        };
+
+        let service = detect_service(&path, &root, &DetectionRules::load(&root));
+        let package_manager = service
+            .as_ref()
+            .map(|service| service.package_manager.clone())
+            .unwrap_or_else(|| detect_package_manager(&path));
+        let Some(command) = service
+            .and_then(|service| service.install_command)
+            .filter(|command| !command.trim().is_empty())
+        else {
+            emit(InstallEvent::Output {
+                line: "No install command for this service".to_string(),
+                stderr: true,
+            });
+            emit(InstallEvent::Finished {
+                success: false,
+                exit_code: None,
+                status: detect_install_status(&path, &package_manager),
+            });
+            return;
+        };
+        emit(InstallEvent::Started {
+            command: command.clone(),
+        });
+
+        let launch = resolve_launch_command(&path, &root, &command);
+        let exit = run_streaming(&launch, &path, &|line, stderr| {
+            emit(InstallEvent::Output { line, stderr })
+        });
+
+        let exit_code = match exit {
+            Ok(status) => status.code(),
+            Err(e) => {
+                emit(InstallEvent::Output {
+                    line: format!("Failed to run '{}': {}", launch.command, e),
+                    stderr: true,
+                });
+                None
+            }
+        };
+        let success = exit_code == Some(0);
+        if success {
+            if let Some(lockfile) = find_lockfile(&path, &package_manager) {
+                record_lockfile_hash(&lockfile).ok();
+            }
+        }
+
+        emit(InstallEvent::Finished {
+            success,
+            exit_code,
+            status: detect_install_status(&path, &package_manager),
+        });
+    });
+
+    Ok(())
+}
+
+/// Run a command to completion, passing each output line on as it comes
+pub fn run_streaming(
+    launch: &LaunchCommand,
+    dir: &Path,
+    on_line: &(dyn Fn(String, bool) + Sync),
+) -> std::io::Result<ExitStatus> {
+    let mut child = launch
+        .to_command(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    std::thread::scope(|scope| {
+        if let Some(stdout) = stdout {
+            scope.spawn(|| read_lines(stdout, &mut |line| on_line(line, false)));
+        }
+        if let Some(stderr) = stderr {
+            scope.spawn(|| read_lines(stderr, &mut |line| on_line(line, true)));
+        }
+    });
+
+    child.wait()
+}
@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use crate::utils::launch_env::{resolve_launch_command, LaunchCommand};
+
+/// Resolve how to run a service command: through the version manager its
+/// version files pin, with the user's login-shell environment.
+///
+/// `project_path` bounds the search for version files; it defaults to the
+/// service directory alone.
+#[tauri::command]
+pub async fn get_launch_command(
+    service_path: String,
+    command: String,
+    project_path: Option<String>,
+) -> Result<LaunchCommand, String> {
+    if command.trim().is_empty() {
+        return Err("Empty command".to_string());
+    }
+
+    // Capturing the shell environment the first time can take a few seconds
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = project_path.as_deref().unwrap_or(&service_path);
+        resolve_launch_command(Path::new(&service_path), Path::new(root), &command)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
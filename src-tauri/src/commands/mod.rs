@@ -1,5 +1,10 @@
+pub mod backup;
+pub mod disk;
 pub mod git;
 pub mod ide;
+pub mod install;
+pub mod launch;
 pub mod project;
 pub mod scaffold;
+pub mod scan;
 pub mod settings;
@@ -2,14 +2,26 @@ use rusqlite::Result;
 use std::sync::Mutex;
 use tauri::State;
 
+use crate::commands::scan::resolve_workspace_roots;
+use crate::config_store::config::load_settings;
 use crate::database::connection::Database;
-use crate::models::detected_service::{DetectedProject, DetectedService};
+use crate::models::detected_service::{
+    DetectedProject, DetectedService, DetectionEvidence, Framework,
+};
 use crate::models::project::{CreateProjectRequest, Project, UpdateProjectRequest};
 use crate::models::service::{CreateServiceRequest, Service, UpdateServiceRequest};
 use crate::repositories::project_repository::ProjectRepository;
 use crate::repositories::service_repository::ServiceRepository;
-use crate::utils::project_scanner::{scan_project_deep, scan_workspace_deep};
+use crate::utils::detectors::{detect_framework_explained, DEV_ENV_FILES};
+use crate::utils::parsers::{
+    CargoToml, DetectionRules, ManifestService, PackageJson, WardenManifest,
+};
+use crate::utils::project_scanner::{
+    declared_service, detect_service, scan_project_deep, scan_workspace_roots,
+};
+use rusqlite::Connection;
 use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 pub struct AppState {
     pub db: Mutex<Database>,
@@ -188,25 +200,23 @@ pub fn scan_project_services(path: String, max_depth: u8) -> Result<DetectedProj
         return Err("Path is not a directory".to_string());
     }
 
-    Ok(scan_project_deep(path, max_depth))
+    Ok(scan_project_deep(
+        path,
+        max_depth,
+        &load_settings().ignore_patterns,
+    ))
 }
 
+/// Scan one workspace directory, or every configured workspace root when no
+/// path is given
 #[tauri::command]
 pub fn scan_workspace_services(
-    workspace_path: String,
-    max_depth: u8,
+    workspace_path: Option<String>,
+    max_depth: Option<u8>,
 ) -> Result<Vec<DetectedProject>, String> {
-    let path = std::path::Path::new(&workspace_path);
-
-    if !path.exists() {
-        return Err("Workspace path does not exist".to_string());
-    }
-
-    if !path.is_dir() {
-        return Err("Workspace path is not a directory".to_string());
-    }
-
-    Ok(scan_workspace_deep(&workspace_path, max_depth))
+    let settings = load_settings();
+    let roots = resolve_workspace_roots(&settings, workspace_path, max_depth)?;
+    Ok(scan_workspace_roots(&roots, &settings.ignore_patterns))
 }
 
 #[tauri::command]
@@ -217,10 +227,28 @@ pub fn get_detected_services(path: String) -> Result<Vec<DetectedService>, Strin
         return Err("Path does not exist".to_string());
     }
 
-    let project = scan_project_deep(path, 1);
+    let project = scan_project_deep(path, 1, &load_settings().ignore_patterns);
     Ok(project.services)
 }
 
+/// Every framework considered for a directory, with the evidence behind it
+#[tauri::command]
+pub fn explain_framework_detection(path: String) -> Result<DetectionEvidence, String> {
+    let path = std::path::Path::new(&path);
+
+    if !path.is_dir() {
+        return Err("Path is not a directory".to_string());
+    }
+
+    let (evidence, _) = detect_framework_explained(
+        path,
+        PackageJson::parse(path).as_ref(),
+        CargoToml::parse(path).as_ref(),
+        &DetectionRules::load(path),
+    );
+    Ok(evidence)
+}
+
 #[tauri::command]
 pub fn update_project(
     state: State<AppState>,
@@ -253,3 +281,328 @@ pub fn update_service(
         .map(ServiceResponse::from)
         .ok_or_else(|| "Service not found".to_string())
 }
+
+/// Serialize a registered project as a manifest (`toml` or `json`) with
+/// service paths relative to the project folder
+#[tauri::command]
+pub fn export_project(
+    state: State<AppState>,
+    project_id: String,
+    format: Option<String>,
+) -> Result<String, String> {
+    let (project, services) = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        let conn = db.get_connection();
+
+        let project = ProjectRepository::new(conn)
+            .find_by_id(&project_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Project not found".to_string())?;
+        let services = ServiceRepository::new(conn)
+            .find_by_project_id(&project_id)
+            .map_err(|e| e.to_string())?;
+        (project, services)
+    };
+
+    // Detection reads the service directories, so it runs unlocked
+    let manifest = export_manifest(&project, &services)?;
+
+    match format.as_deref().unwrap_or("toml") {
+        "toml" => toml::to_string_pretty(&manifest).map_err(|e| e.to_string()),
+        "json" => serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string()),
+        other => Err(format!("Unsupported export format: {}", other)),
+    }
+}
+
+/// The registered services, completed with what detection finds in their
+/// directories. A service outside the project folder cannot be written as a
+/// relative path and fails the export.
+fn export_manifest(project: &Project, services: &[Service]) -> Result<WardenManifest, String> {
+    let folder = Path::new(&project.folder);
+    let rules = DetectionRules::load(folder);
+
+    let services = services
+        .iter()
+        .map(|service| export_service(folder, service, &rules))
+        .collect::<Result<_, _>>()?;
+
+    Ok(WardenManifest {
+        name: Some(project.name.clone()),
+        services,
+        ..WardenManifest::default()
+    })
+}
+
+fn export_service(
+    folder: &Path,
+    service: &Service,
+    rules: &DetectionRules,
+) -> Result<ManifestService, String> {
+    let dir = Path::new(&service.path);
+    let path = dir
+        .strip_prefix(folder)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|_| {
+            format!(
+                "Service `{}` at {} is outside the project folder",
+                service.name, service.path
+            )
+        })?;
+
+    let detected = detect_service(dir, folder, rules);
+    let detected = detected.as_ref();
+    let env_files = match detected.map(|d| &d.env_files) {
+        Some(declared) if !declared.is_empty() => declared.clone(),
+        _ => DEV_ENV_FILES
+            .iter()
+            .filter(|file| dir.join(file).is_file())
+            .map(|file| file.to_string())
+            .collect(),
+    };
+
+    Ok(ManifestService {
+        name: service.name.clone(),
+        path: Some(if path.is_empty() {
+            ".".to_string()
+        } else {
+            path
+        }),
+        framework: detected
+            .map(|d| d.framework.clone())
+            .filter(|framework| *framework != Framework::Unknown),
+        stack: Some(service.stack.clone()),
+        category: serde_json::from_value(serde_json::Value::String(service.service_type.clone()))
+            .ok(),
+        port: u16::try_from(service.port).ok().filter(|port| *port > 0),
+        url: (!service.url.is_empty()).then(|| service.url.clone()),
+        env_files,
+        dev: (!service.command.is_empty()).then(|| service.command.clone()),
+        build: detected.and_then(|d| d.build_command.clone()),
+        start: detected.and_then(|d| d.start_command.clone()),
+        install: detected.and_then(|d| d.install_command.clone()),
+        depends_on: detected.map(|d| d.depends_on.clone()).unwrap_or_default(),
+        health_check: detected.and_then(|d| d.health_check.clone()),
+        line: 0,
+    })
+}
+
+/// Row for a service found by the scanner or declared in a manifest
+pub fn service_request(
+    project_id: &str,
+    service: DetectedService,
+    url: String,
+) -> CreateServiceRequest {
+    CreateServiceRequest {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: project_id.to_string(),
+        name: service.name,
+        service_type: serde_json::to_value(&service.category)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_else(|| "backend".to_string()),
+        stack: service.stack,
+        path: service.path,
+        url,
+        port: service.port.map(i32::from).unwrap_or(0),
+        command: service
+            .dev_command
+            .or(service.start_command)
+            .unwrap_or_default(),
+    }
+}
+
+/// Register a project from a manifest file, or from a folder holding one.
+/// The project and its services are created together or not at all.
+#[tauri::command]
+pub fn import_project(state: State<AppState>, path: String) -> Result<Project, String> {
+    let (folder, manifest) = read_import_manifest(Path::new(&path))?;
+
+    // Detection reads every service directory; it must not hold up other
+    // commands waiting on the database
+    let rules = DetectionRules::load(&folder);
+    let services: Vec<(DetectedService, String)> = manifest
+        .services
+        .iter()
+        .map(|declaration| {
+            let service = declared_service(&folder, declaration, &rules);
+            (service, declaration.url.clone().unwrap_or_default())
+        })
+        .collect();
+
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    register_import(db.get_connection_mut(), &folder, manifest.name, services)
+}
+
+/// The manifest to import and the folder it describes
+fn read_import_manifest(path: &Path) -> Result<(PathBuf, WardenManifest), String> {
+    let manifest = if path.is_dir() {
+        WardenManifest::parse(path)
+    } else {
+        WardenManifest::parse_file(path)
+    }
+    .ok_or_else(|| "No Warden manifest found".to_string())?;
+
+    if !manifest.errors.is_empty() {
+        let errors: Vec<String> = manifest
+            .errors
+            .iter()
+            .map(|e| format!("{} line {}: {}", manifest.file_name, e.line, e.message))
+            .collect();
+        return Err(errors.join("\n"));
+    }
+
+    let folder = if path.is_dir() {
+        path
+    } else {
+        path.parent()
+            .ok_or_else(|| "Invalid manifest path".to_string())?
+    };
+    let folder = folder.canonicalize().map_err(|e| e.to_string())?;
+    Ok((folder, manifest))
+}
+
+/// Create the project and its services (with their URLs) in one transaction
+fn register_import(
+    conn: &mut Connection,
+    folder: &Path,
+    name: Option<String>,
+    services: Vec<(DetectedService, String)>,
+) -> Result<Project, String> {
+    let folder_str = folder.to_string_lossy().to_string();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let project_repo = ProjectRepository::new(&tx);
+    if project_repo
+        .folder_exists(&folder_str)
+        .map_err(|e| e.to_string())?
+    {
+        return Err(format!(
+            "A project is already registered for {}",
+            folder_str
+        ));
+    }
+
+    let project_name = name.unwrap_or_else(|| {
+        folder
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    });
+    let project = project_repo
+        .create(&CreateProjectRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: project_name,
+            folder: folder_str,
+        })
+        .map_err(|e| e.to_string())?;
+
+    let service_repo = ServiceRepository::new(&tx);
+    for (service, url) in services {
+        service_repo
+            .create(&service_request(&project.id, service, url))
+            .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations::run_migrations;
+    use crate::utils::test_fixtures::Fixture;
+
+    fn database() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn
+    }
+
+    fn add_service(conn: &Connection, project: &Project, name: &str, path: &Path, port: i32) {
+        ServiceRepository::new(conn)
+            .create(&CreateServiceRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                project_id: project.id.clone(),
+                name: name.to_string(),
+                service_type: "frontend".to_string(),
+                stack: "node".to_string(),
+                path: path.to_string_lossy().to_string(),
+                url: format!("http://{}.test", name),
+                port,
+                command: "npm run dev".to_string(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let fixture = Fixture::new(&[
+            (
+                "web/package.json",
+                r#"{"scripts": {"dev": "vite", "build": "vite build"}, "devDependencies": {"vite": "^5"}}"#,
+            ),
+            ("web/.env", "VITE_PORT=5174\n"),
+        ]);
+        let folder = fixture.path().canonicalize().unwrap();
+
+        let exporting = database();
+        let project = ProjectRepository::new(&exporting)
+            .create(&CreateProjectRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: "shop".to_string(),
+                folder: folder.to_string_lossy().to_string(),
+            })
+            .unwrap();
+        add_service(&exporting, &project, "web", &folder.join("web"), 5174);
+        let services = ServiceRepository::new(&exporting)
+            .find_by_project_id(&project.id)
+            .unwrap();
+
+        let manifest = export_manifest(&project, &services).unwrap();
+        let web = &manifest.services[0];
+        assert_eq!(web.get_path(), "web");
+        assert_eq!(web.framework, Some(Framework::Vite));
+        assert!(web.build.is_some());
+        assert!(web.install.is_some());
+        assert_eq!(web.env_files, vec![".env"]);
+
+        fixture.write(".warden.toml", &toml::to_string_pretty(&manifest).unwrap());
+        let (imported_folder, imported) = read_import_manifest(&folder).unwrap();
+        assert_eq!(imported_folder, folder);
+        let rules = DetectionRules::default();
+        let declared = imported
+            .services
+            .iter()
+            .map(|d| {
+                let service = declared_service(&folder, d, &rules);
+                (service, d.url.clone().unwrap_or_default())
+            })
+            .collect();
+
+        let mut importing = database();
+        let imported = register_import(&mut importing, &folder, imported.name, declared).unwrap();
+        assert_eq!(imported.name, "shop");
+        let round_tripped = ServiceRepository::new(&importing)
+            .find_by_project_id(&imported.id)
+            .unwrap();
+        assert_eq!(round_tripped.len(), 1);
+        let (before, after) = (&services[0], &round_tripped[0]);
+        assert_eq!(
+            (&after.name, &after.path, &after.url, after.port),
+            (&before.name, &before.path, &before.url, before.port)
+        );
+        assert_eq!(
+            (&after.service_type, &after.stack, &after.command),
+            (&before.service_type, &before.stack, &before.command)
+        );
+
+        // A service outside the folder has no relative path to export
+        add_service(&exporting, &project, "stray", Path::new("/srv/stray"), 0);
+        let services = ServiceRepository::new(&exporting)
+            .find_by_project_id(&project.id)
+            .unwrap();
+        let error = export_manifest(&project, &services).unwrap_err();
+        assert!(error.contains("`stray`"));
+    }
+}
@@ -1,39 +1,256 @@
-use std::process::Stdio;
-use tokio::process::Command;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
 
-/// Execute a scaffold command in the specified working directory.
+use crate::commands::project::{service_request, AppState};
+use crate::config_store::config::load_settings;
+use crate::models::detected_service::{DetectedService, PackageManager};
+use crate::models::project::{CreateProjectRequest, Project};
+use crate::models::scaffold::{ScaffoldParams, ScaffoldTemplate};
+use crate::repositories::project_repository::ProjectRepository;
+use crate::repositories::service_repository::ServiceRepository;
+use crate::utils::parsers::ScaffoldTemplates;
+use crate::utils::project_scanner::scan_project_deep;
+use crate::utils::scaffold::{plan_scaffold, run_scaffold, ScaffoldPlan, ScaffoldStatus};
+
+/// Event every scaffold job reports on
+pub const SCAFFOLD_EVENT: &str = "scaffold";
+
+/// Cancellation flags of running scaffolds, by job id
+#[derive(Default)]
+pub struct ScaffoldJobs {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ScaffoldEvent {
+    /// A step started
+    Step {
+        index: usize,
+        command: Vec<String>,
+    },
+    Output {
+        line: String,
+        stderr: bool,
+    },
+    Finished {
+        status: ScaffoldStatus,
+        exit_code: Option<i32>,
+        duration_ms: u64,
+        error: Option<String>,
+        /// The registered project, when the scaffold succeeded
+        project: Option<Project>,
+    },
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScaffoldJobEvent {
+    job_id: String,
+    #[serde(flatten)]
+    event: ScaffoldEvent,
+}
+
+/// Built-in templates and the user's `scaffold-templates.*` from the config
+/// dir, with the problems that kept user templates out
+#[tauri::command]
+pub fn get_scaffold_templates() -> Result<ScaffoldTemplates, String> {
+    Ok(ScaffoldTemplates::load())
+}
+
+fn find_template(template_id: &str) -> Result<ScaffoldTemplate, String> {
+    ScaffoldTemplates::load()
+        .find(template_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown scaffold template: {}", template_id))
+}
+
+/// What `execute_scaffold` would run, without running it
+#[tauri::command]
+pub fn preview_scaffold(
+    template_id: String,
+    folder: String,
+    params: ScaffoldParams,
+) -> Result<ScaffoldPlan, String> {
+    plan_scaffold(&find_template(&template_id)?, Path::new(&folder), &params)
+}
+
+/// Create `<folder>/<name>` from a template in the background and return
+/// the job id. Step output is emitted line by line as `scaffold` events;
+/// the last one carries the outcome and, on success, the project, which is
+/// registered with the services detected in what the template generated.
 ///
-/// The command string is passed verbatim to the shell (`sh -c`) so that
-/// quoted arguments (e.g. `--import-alias "@/*"`) are handled correctly.
+/// Steps run directly, without a shell, so parameters can't inject
+/// commands. `timeout_secs` overrides the timeout from the settings.
 #[tauri::command]
-pub async fn execute_scaffold(working_dir: String, command: String) -> Result<String, String> {
-    if command.trim().is_empty() {
-        return Err("Empty command".to_string());
+pub fn execute_scaffold(
+    app: AppHandle,
+    jobs: State<ScaffoldJobs>,
+    template_id: String,
+    folder: String,
+    params: ScaffoldParams,
+    url: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<String, String> {
+    let template = find_template(&template_id)?;
+    let plan = plan_scaffold(&template, Path::new(&folder), &params)?;
+    let timeout = Some(timeout_secs.unwrap_or_else(|| load_settings().scaffold_timeout_secs))
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
+
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    jobs.jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(job_id.clone(), cancelled.clone());
+
+    let id = job_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let emit = |event| {
+            app.emit(
+                SCAFFOLD_EVENT,
+                ScaffoldJobEvent {
+                    job_id: id.clone(),
+                    event,
+                },
+            )
+            .ok();
+        };
+
+        let run = run_scaffold(
+            &plan,
+            timeout,
+            &cancelled,
+            &|index, command| {
+                emit(ScaffoldEvent::Step {
+                    index,
+                    command: command.to_vec(),
+                })
+            },
+            &|line, stderr| emit(ScaffoldEvent::Output { line, stderr }),
+        );
+
+        let (project, error) = match run.status {
+            ScaffoldStatus::Succeeded => {
+                let state = app.state::<AppState>();
+                match register_project(&state, &template, &plan, &params, url.unwrap_or_default()) {
+                    Ok(project) => (Some(project), None),
+                    Err(e) => (None, Some(e)),
+                }
+            }
+            _ => (None, run.error),
+        };
+
+        emit(ScaffoldEvent::Finished {
+            status: if error.is_some() {
+                ScaffoldStatus::Failed
+            } else {
+                run.status
+            },
+            exit_code: run.exit_code,
+            duration_ms: run.duration_ms,
+            error,
+            project,
+        });
+
+        if let Ok(mut jobs) = app.state::<ScaffoldJobs>().jobs.lock() {
+            jobs.remove(&id);
+        }
+    });
+
+    Ok(job_id)
+}
+
+/// Kill the running step of a scaffold. Returns false when the job is
+/// unknown or already done.
+#[tauri::command]
+pub fn cancel_scaffold(jobs: State<ScaffoldJobs>, job_id: String) -> Result<bool, String> {
+    let jobs = jobs.jobs.lock().map_err(|e| e.to_string())?;
+
+    match jobs.get(&job_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
     }
+}
+
+fn register_project(
+    state: &AppState,
+    template: &ScaffoldTemplate,
+    plan: &ScaffoldPlan,
+    params: &ScaffoldParams,
+    url: String,
+) -> Result<Project, String> {
+    let project_dir = Path::new(&plan.project_dir);
+    let mut services = scan_project_deep(project_dir, 2, &load_settings().ignore_patterns).services;
+    if services.is_empty() {
+        let mut service = DetectedService::new(
+            template.name.clone(),
+            plan.project_dir.clone(),
+            String::new(),
+        );
+        service.category = template.category.clone();
+        service.package_manager = params
+            .package_manager
+            .clone()
+            .unwrap_or_else(|| template.package_manager.clone());
+        services.push(service);
+    }
+    // The chosen port and url belong to the service at the project root,
+    // or the first one when the template generated a workspace
+    let main = services
+        .iter()
+        .position(|service| Path::new(&service.path) == project_dir)
+        .unwrap_or(0);
+    services[main].port = Some(plan.port);
+
+    let mut db = state.db.lock().map_err(|e| e.to_string())?;
+    let tx = db
+        .get_connection_mut()
+        .transaction()
+        .map_err(|e| e.to_string())?;
 
-    let output = Command::new("sh")
-        .args(["-c", &command])
-        .current_dir(&working_dir)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute command: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-
-    if !output.status.success() {
-        return Err(format!(
-            "Command failed with exit code {}\nSTDOUT: {}\nSTDERR: {}",
-            output
-                .status
-                .code()
-                .map_or_else(|| "unknown".to_string(), |c| c.to_string()),
-            stdout,
-            stderr
-        ));
+    let project = ProjectRepository::new(&tx)
+        .create(&CreateProjectRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: params.name.clone(),
+            folder: plan.project_dir.clone(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    let service_repo = ServiceRepository::new(&tx);
+    for (index, service) in services.into_iter().enumerate() {
+        let url = if index == main {
+            url.clone()
+        } else {
+            String::new()
+        };
+        service_repo
+            .create(&service_request(&project.id, service, url))
+            .map_err(|e| e.to_string())?;
     }
 
-    Ok(stdout.to_string())
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(project)
+}
+
+/// Prefix a package command with the manager's download-and-run runner
+/// (`npx`, `pnpm dlx`, `yarn dlx`, `bunx`, `deno run -A npm:`)
+#[tauri::command]
+pub fn get_dlx_command(package_manager: PackageManager, command: String) -> Result<String, String> {
+    package_manager
+        .dlx_command(&command)
+        .ok_or_else(|| format!("{:?} cannot run npm packages", package_manager))
 }
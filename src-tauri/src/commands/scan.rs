@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::config_store::config::{load_settings, Settings, WorkspaceRoot};
+use crate::utils::workspace_scan::{scan_workspace_streaming, ScanEvent, PROJECT_SCAN_BUDGET};
+use serde::Serialize;
+
+/// Event every scan job reports on
+pub const SCAN_EVENT: &str = "workspace-scan";
+
+/// Cancellation flags of running scan jobs, by job id
+#[derive(Default)]
+pub struct ScanJobs {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanJobEvent {
+    job_id: String,
+    #[serde(flatten)]
+    event: ScanEvent,
+}
+
+/// The given workspace directory, or every configured root when none is given
+pub fn resolve_workspace_roots(
+    settings: &Settings,
+    workspace_path: Option<String>,
+    max_depth: Option<u8>,
+) -> Result<Vec<WorkspaceRoot>, String> {
+    let Some(workspace_path) = workspace_path else {
+        return Ok(settings.workspace_roots.clone());
+    };
+
+    let path = std::path::Path::new(&workspace_path);
+
+    if !path.exists() {
+        return Err("Workspace path does not exist".to_string());
+    }
+
+    if !path.is_dir() {
+        return Err("Workspace path is not a directory".to_string());
+    }
+
+    Ok(vec![WorkspaceRoot {
+        path: workspace_path.clone(),
+        scan_depth: max_depth.unwrap_or(settings.scan_depth),
+        ..WorkspaceRoot::default()
+    }])
+}
+
+/// Start scanning in the background and return the job id. Progress and
+/// each detected project are emitted as `workspace-scan` events.
+#[tauri::command]
+pub fn start_workspace_scan(
+    app: AppHandle,
+    jobs: State<ScanJobs>,
+    workspace_path: Option<String>,
+    max_depth: Option<u8>,
+) -> Result<String, String> {
+    let settings = load_settings();
+    let roots = resolve_workspace_roots(&settings, workspace_path, max_depth)?;
+
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    jobs.jobs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(job_id.clone(), cancelled.clone());
+
+    let id = job_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        scan_workspace_streaming(
+            &roots,
+            &settings.ignore_patterns,
+            &cancelled,
+            PROJECT_SCAN_BUDGET,
+            &|event| {
+                app.emit(
+                    SCAN_EVENT,
+                    ScanJobEvent {
+                        job_id: id.clone(),
+                        event,
+                    },
+                )
+                .ok();
+            },
+        );
+
+        if let Ok(mut jobs) = app.state::<ScanJobs>().jobs.lock() {
+            jobs.remove(&id);
+        }
+    });
+
+    Ok(job_id)
+}
+
+/// Ask a scan job to stop; projects already being scanned still finish.
+/// Returns false when the job is unknown or already done.
+#[tauri::command]
+pub fn cancel_workspace_scan(jobs: State<ScanJobs>, job_id: String) -> Result<bool, String> {
+    let jobs = jobs.jobs.lock().map_err(|e| e.to_string())?;
+
+    match jobs.get(&job_id) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
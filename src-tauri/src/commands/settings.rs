@@ -1,4 +1,6 @@
-use crate::config_store::config::{load_settings, save_settings};
+use crate::config_store::config::{
+    load_settings, save_settings, validate_ignore_patterns, validate_scan_depth, WorkspaceRoot,
+};
 
 #[tauri::command]
 pub fn save_default_ide(ide: String) -> Result<(), String> {
@@ -28,10 +30,35 @@ pub fn get_ide_command() -> Result<String, String> {
     Ok(settings.ide_command)
 }
 
+/// Set the path of the first workspace root; an empty path removes it
 #[tauri::command]
 pub fn save_root_path(path: String) -> Result<(), String> {
     let mut settings = load_settings();
-    settings.workspace_path = path;
+
+    if path.is_empty() {
+        if !settings.workspace_roots.is_empty() {
+            settings.workspace_roots.remove(0);
+        }
+    } else {
+        let root = match settings.workspace_roots.first() {
+            Some(first) => WorkspaceRoot {
+                path,
+                ..first.clone()
+            },
+            None => WorkspaceRoot {
+                path,
+                scan_depth: settings.scan_depth,
+                ..WorkspaceRoot::default()
+            },
+        };
+        root.validate()?;
+
+        match settings.workspace_roots.first_mut() {
+            Some(first) => *first = root,
+            None => settings.workspace_roots.push(root),
+        }
+    }
+
     save_settings(&settings)?;
     Ok(())
 }
@@ -39,7 +66,29 @@ pub fn save_root_path(path: String) -> Result<(), String> {
 #[tauri::command]
 pub fn get_root_path() -> Result<String, String> {
     let settings = load_settings();
-    Ok(settings.workspace_path)
+    Ok(settings
+        .workspace_roots
+        .first()
+        .map(|root| root.path.clone())
+        .unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn save_workspace_roots(roots: Vec<WorkspaceRoot>) -> Result<(), String> {
+    for root in &roots {
+        root.validate()?;
+    }
+
+    let mut settings = load_settings();
+    settings.workspace_roots = roots;
+    save_settings(&settings)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_workspace_roots() -> Result<Vec<WorkspaceRoot>, String> {
+    let settings = load_settings();
+    Ok(settings.workspace_roots)
 }
 
 #[tauri::command]
@@ -58,6 +107,7 @@ pub fn get_default_suffix() -> Result<String, String> {
 
 #[tauri::command]
 pub fn save_scan_depth(depth: u8) -> Result<(), String> {
+    validate_scan_depth(depth)?;
     let mut settings = load_settings();
     settings.scan_depth = depth;
     save_settings(&settings)?;
@@ -69,3 +119,32 @@ pub fn get_scan_depth() -> Result<u8, String> {
     let settings = load_settings();
     Ok(settings.scan_depth)
 }
+
+#[tauri::command]
+pub fn save_ignore_patterns(patterns: Vec<String>) -> Result<(), String> {
+    validate_ignore_patterns(&patterns)?;
+    let mut settings = load_settings();
+    settings.ignore_patterns = patterns;
+    save_settings(&settings)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_ignore_patterns() -> Result<Vec<String>, String> {
+    let settings = load_settings();
+    Ok(settings.ignore_patterns)
+}
+
+#[tauri::command]
+pub fn save_scaffold_timeout(seconds: u64) -> Result<(), String> {
+    let mut settings = load_settings();
+    settings.scaffold_timeout_secs = seconds;
+    save_settings(&settings)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_scaffold_timeout() -> Result<u64, String> {
+    let settings = load_settings();
+    Ok(settings.scaffold_timeout_secs)
+}
@@ -1,58 +1,404 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version written by this build. Files without a `version` are version 1.
+pub const SETTINGS_VERSION: u32 = 2;
+
+pub const MIN_SCAN_DEPTH: u8 = 1;
+pub const MAX_SCAN_DEPTH: u8 = 10;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct Settings {
+    pub version: u32,
     pub default_ide: String,
     pub ide_command: String,
-    pub workspace_path: String,
+    pub workspace_roots: Vec<WorkspaceRoot>,
     pub theme: String,
     pub default_suffix: String,
+    /// Depth given to newly added workspace roots
+    pub scan_depth: u8,
+    /// Folder name patterns the scanner never enters, on top of hidden
+    /// folders and `.gitignore`
+    pub ignore_patterns: Vec<String>,
+    /// Seconds a scaffold may run before it is killed; 0 for no limit
+    pub scaffold_timeout_secs: u64,
+}
+
+/// A directory whose sub-folders are scanned for projects. `path` may start
+/// with `~` and end with a glob (`~/clients/*`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WorkspaceRoot {
+    pub path: String,
     pub scan_depth: u8,
+    /// Glob patterns of project folder names to skip
+    pub ignore: Vec<String>,
+    /// Register projects found here without asking
+    pub auto_import: bool,
+}
+
+impl Default for WorkspaceRoot {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            scan_depth: 2,
+            ignore: Vec::new(),
+            auto_import: true,
+        }
+    }
+}
+
+impl WorkspaceRoot {
+    /// Directories the root stands for
+    pub fn expand(&self) -> Vec<PathBuf> {
+        let path = match self.path.strip_prefix("~") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| format!("{}{}", home.to_string_lossy(), rest))
+                .unwrap_or_else(|| self.path.clone()),
+            None => self.path.clone(),
+        };
+
+        if !path.contains(['*', '?', '[']) {
+            let path = PathBuf::from(path);
+            return if path.is_dir() {
+                vec![path]
+            } else {
+                Vec::new()
+            };
+        }
+
+        glob::glob(&path)
+            .map(|paths| paths.flatten().filter(|p| p.is_dir()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        validate_scan_depth(self.scan_depth)?;
+        self.validate_syntax()?;
+
+        if !self.path.contains(['*', '?', '[']) && self.expand().is_empty() {
+            return Err(format!("Workspace path {} is not a directory", self.path));
+        }
+        Ok(())
+    }
+
+    /// Checks that don't touch the file system, so a root on an unmounted
+    /// drive survives loading
+    fn validate_syntax(&self) -> Result<(), String> {
+        if !self.path.starts_with('~') && !Path::new(&self.path).is_absolute() {
+            return Err(format!("Workspace path {} must be absolute", self.path));
+        }
+        if self.path.contains(['*', '?', '[']) {
+            glob::Pattern::new(&self.path).map_err(|e| format!("{}: {}", self.path, e))?;
+        }
+        validate_ignore_patterns(&self.ignore)
+    }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             default_ide: "zed".to_string(),
             ide_command: "zed".to_string(),
-            workspace_path: String::new(),
+            workspace_roots: Vec::new(),
             theme: "system".to_string(),
             default_suffix: "test".to_string(),
             scan_depth: 2,
+            ignore_patterns: [
+                "node_modules",
+                "target",
+                "dist",
+                "build",
+                "__pycache__",
+                "vendor",
+                "coverage",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            scaffold_timeout_secs: 600,
         }
     }
 }
 
-pub fn get_config_path() -> PathBuf {
+/// Rewrites raw settings from one version to the next
+type Upgrade = fn(&mut Value);
+
+/// Upgrade steps, each taking the settings from `version` to `version + 1`
+const UPGRADES: &[(u32, Upgrade)] = &[(1, upgrade_v1_to_v2)];
+
+/// The single `workspace_path` becomes the first workspace root
+fn upgrade_v1_to_v2(settings: &mut Value) {
+    let Some(object) = settings.as_object_mut() else {
+        return;
+    };
+
+    let path = object
+        .remove("workspace_path")
+        .and_then(|p| p.as_str().map(String::from))
+        .unwrap_or_default();
+    if path.is_empty() {
+        return;
+    }
+
+    let root = WorkspaceRoot {
+        path,
+        scan_depth: object
+            .get("scan_depth")
+            .and_then(Value::as_u64)
+            .map(|d| d.min(u8::MAX as u64) as u8)
+            .unwrap_or(2),
+        ..WorkspaceRoot::default()
+    };
+    object.insert(
+        "workspace_roots".to_string(),
+        serde_json::to_value(vec![root]).unwrap_or_default(),
+    );
+}
+
+impl Settings {
+    /// Parse settings of any known version, upgrading older ones
+    pub fn parse_str(content: &str) -> Result<Self, String> {
+        let mut value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if !value.is_object() {
+            return Err("settings must be a JSON object".to_string());
+        }
+
+        let mut version = file_version(&value);
+        if version > SETTINGS_VERSION {
+            return Err(format!(
+                "settings version {} is newer than this version of Warden",
+                version
+            ));
+        }
+
+        for (from, upgrade) in UPGRADES {
+            if version == *from {
+                upgrade(&mut value);
+                version += 1;
+            }
+        }
+        value["version"] = Value::from(SETTINGS_VERSION);
+
+        let mut settings: Settings = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        settings.scan_depth = settings.scan_depth.clamp(MIN_SCAN_DEPTH, MAX_SCAN_DEPTH);
+        // Includes the root upgraded from a v1 `workspace_path`
+        settings
+            .workspace_roots
+            .retain(|root| root.validate_syntax().is_ok());
+        for root in &mut settings.workspace_roots {
+            root.scan_depth = root.scan_depth.clamp(MIN_SCAN_DEPTH, MAX_SCAN_DEPTH);
+        }
+        Ok(settings)
+    }
+}
+
+pub fn validate_scan_depth(depth: u8) -> Result<(), String> {
+    if !(MIN_SCAN_DEPTH..=MAX_SCAN_DEPTH).contains(&depth) {
+        return Err(format!(
+            "Scan depth must be between {} and {}",
+            MIN_SCAN_DEPTH, MAX_SCAN_DEPTH
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_ignore_patterns(patterns: &[String]) -> Result<(), String> {
+    for pattern in patterns {
+        glob::Pattern::new(pattern).map_err(|e| format!("{}: {}", pattern, e))?;
+    }
+    Ok(())
+}
+
+/// Warden's directory under the user config dir
+pub fn get_config_dir() -> PathBuf {
     let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("~/.config"));
     path.push("warden");
     fs::create_dir_all(&path).ok();
-    path.push("settings.json");
     path
 }
 
+pub fn get_config_path() -> PathBuf {
+    get_config_dir().join("settings.json")
+}
+
+/// Version of raw settings; files without one are version 1
+fn file_version(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map(|v| v.min(u32::MAX as u64) as u32)
+        .unwrap_or(1)
+}
+
+/// Whether a settings file was written by a newer Warden, which this one
+/// must neither replace nor rename
+fn is_newer_file(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .is_some_and(|value| file_version(&value) > SETTINGS_VERSION)
+}
+
 pub fn load_settings() -> Settings {
-    let path = get_config_path();
+    load_settings_from(&get_config_path())
+}
 
-    if let Ok(content) = fs::read_to_string(&path) {
-        if let Ok(settings) = serde_json::from_str(&content) {
-            return settings;
+fn load_settings_from(path: &Path) -> Settings {
+    let Ok(content) = fs::read_to_string(path) else {
+        let default = Settings::default();
+        save_settings_to(path, &default).ok();
+        return default;
+    };
+
+    match Settings::parse_str(&content) {
+        Ok(settings) => settings,
+        // Run with defaults but leave the newer file as it is
+        Err(_) if is_newer_file(path) => Settings::default(),
+        Err(_) => {
+            // Keep the user's file around instead of overwriting it
+            let stamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            fs::rename(path, path.with_extension(format!("json.invalid-{}", stamp))).ok();
+
+            let default = Settings::default();
+            save_settings_to(path, &default).ok();
+            default
         }
     }
-
-    let default = Settings::default();
-    save_settings(&default).ok();
-    default
 }
 
 pub fn save_settings(settings: &Settings) -> Result<(), String> {
-    let path = get_config_path();
+    save_settings_to(&get_config_path(), settings)
+}
+
+fn save_settings_to(path: &Path, settings: &Settings) -> Result<(), String> {
+    if is_newer_file(path) {
+        return Err(format!(
+            "{} was written by a newer version of Warden and is left unchanged",
+            path.display()
+        ));
+    }
 
     let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    write_atomic(path, &json)
+}
 
-    fs::write(path, json).map_err(|e| e.to_string())?;
+/// Write through a uniquely named temp file and rename, so a crash never
+/// leaves a half-written file and concurrent writers never share a temp file
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name,
+        uuid::Uuid::new_v4().simple()
+    ));
 
-    Ok(())
+    let written = fs::write(&tmp_path, content).and_then(|_| fs::rename(&tmp_path, path));
+    if written.is_err() {
+        fs::remove_file(&tmp_path).ok();
+    }
+    written.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_upgrade_v1_settings() {
+        let settings = Settings::parse_str(
+            r#"{ "default_ide": "code", "ide_command": "", "workspace_path": "/code", "scan_depth": 40 }"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        // An empty command leaves the choice to the IDE's defaults
+        assert_eq!(settings.ide_command, "");
+        assert_eq!(settings.theme, "system");
+        assert_eq!(settings.scan_depth, MAX_SCAN_DEPTH);
+        assert_eq!(settings.workspace_roots.len(), 1);
+        assert_eq!(settings.workspace_roots[0].path, "/code");
+        assert_eq!(settings.workspace_roots[0].scan_depth, MAX_SCAN_DEPTH);
+
+        assert!(Settings::parse_str(r#"{ "version": 99 }"#).is_err());
+
+        // Not a path Warden can scan
+        let settings = Settings::parse_str(
+            r#"{ "version": 2, "workspace_roots": [{ "path": "code/clients" }, { "path": "~/code" }] }"#,
+        )
+        .unwrap();
+        let paths: Vec<&str> = settings
+            .workspace_roots
+            .iter()
+            .map(|root| root.path.as_str())
+            .collect();
+        assert_eq!(paths, ["~/code"]);
+    }
+
+    #[test]
+    fn test_unreadable_and_newer_settings_files() {
+        let fixture = Fixture::new(&[("settings.json", "{ not json")]);
+        let path = fixture.join("settings.json");
+
+        // An unparseable file is set aside and replaced with defaults
+        let settings = load_settings_from(&path);
+        assert_eq!(settings.scan_depth, Settings::default().scan_depth);
+        let files = file_names(fixture.path());
+        assert_eq!(files.len(), 2);
+        assert!(files[1].starts_with("settings.json.invalid-"));
+        assert!(Settings::parse_str(&fs::read_to_string(&path).unwrap()).is_ok());
+
+        // A newer file is used as defaults and never overwritten
+        let newer = r#"{ "version": 99, "theme": "dark" }"#;
+        fs::write(&path, newer).unwrap();
+        assert_eq!(load_settings_from(&path).theme, "system");
+        assert!(save_settings_to(&path, &Settings::default()).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), newer);
+    }
+
+    #[test]
+    fn test_concurrent_saves() {
+        let fixture = Fixture::new(&[]);
+        let path = fixture.join("settings.json");
+
+        std::thread::scope(|scope| {
+            for depth in MIN_SCAN_DEPTH..=MAX_SCAN_DEPTH {
+                let path = &path;
+                scope.spawn(move || {
+                    let settings = Settings {
+                        scan_depth: depth,
+                        ..Settings::default()
+                    };
+                    save_settings_to(path, &settings).unwrap();
+                });
+            }
+        });
+
+        // One of the writes won whole, and no temp file is left behind
+        let saved = Settings::parse_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!((MIN_SCAN_DEPTH..=MAX_SCAN_DEPTH).contains(&saved.scan_depth));
+        assert_eq!(file_names(fixture.path()), ["settings.json"]);
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
 }
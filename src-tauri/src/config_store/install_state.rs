@@ -0,0 +1,54 @@
+use crate::config_store::config::{get_config_dir, write_atomic};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Serializes read-modify-write of the state file between installs
+/// finishing at the same time
+static INSTALL_STATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Lockfile hashes recorded after each successful install, by lockfile path.
+/// A lockfile rewritten with the same content (a checkout, a rebase) is then
+/// not reported as stale.
+pub fn get_install_state_path() -> PathBuf {
+    get_config_dir().join("install-state.json")
+}
+
+fn load_install_state() -> HashMap<String, String> {
+    fs::read_to_string(get_install_state_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Hash of the lockfile when dependencies were last installed through Warden
+pub fn recorded_lockfile_hash(lockfile: &Path) -> Option<String> {
+    load_install_state().remove(&lockfile.to_string_lossy().to_string())
+}
+
+pub fn record_lockfile_hash(lockfile: &Path) -> Result<(), String> {
+    let Some(hash) = hash_file(lockfile) else {
+        return Ok(());
+    };
+
+    // Only guards the file, so a panicked holder left nothing inconsistent
+    let _lock = INSTALL_STATE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let mut state = load_install_state();
+    state.insert(lockfile.to_string_lossy().to_string(), hash);
+
+    let json = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
+    write_atomic(&get_install_state_path(), &json)
+}
+
+/// FNV-1a hash of a file's content, stable across Rust versions
+pub fn hash_file(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    Some(format!("{:016x}", hash))
+}
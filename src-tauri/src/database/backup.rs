@@ -0,0 +1,334 @@
+use crate::config_store::config::{get_config_dir, write_atomic, Settings};
+use crate::database::migrations::run_migrations;
+use rusqlite::{backup::Progress, Connection, OpenFlags, Result, MAIN_DB};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Pre-migration backups kept before the oldest is dropped
+const MAX_MIGRATION_BACKUPS: usize = 5;
+
+const DATABASE_FILE: &str = "warden.db";
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Why a backup was taken; part of its directory name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupKind {
+    Manual,
+    PreMigration,
+    PreRestore,
+}
+
+impl BackupKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BackupKind::Manual => "manual",
+            BackupKind::PreMigration => "pre-migration",
+            BackupKind::PreRestore => "pre-restore",
+        }
+    }
+}
+
+/// A backup on disk: `backups/<timestamp>-<kind>/{warden.db,settings.json}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub name: String,
+    pub path: String,
+    pub has_settings: bool,
+}
+
+impl BackupInfo {
+    fn from_dir(dir: &Path) -> Option<Self> {
+        if !dir.join(DATABASE_FILE).is_file() {
+            return None;
+        }
+
+        Some(Self {
+            name: dir.file_name()?.to_string_lossy().to_string(),
+            path: dir.to_string_lossy().to_string(),
+            has_settings: dir.join(SETTINGS_FILE).is_file(),
+        })
+    }
+}
+
+pub fn get_backups_dir() -> PathBuf {
+    get_config_dir().join("backups")
+}
+
+/// Snapshot the live database with SQLite's online backup API, plus the
+/// settings file.
+pub fn create_backup(conn: &Connection, kind: BackupKind) -> Result<BackupInfo> {
+    create_backup_in(conn, kind, &get_config_dir())
+}
+
+fn create_backup_in(conn: &Connection, kind: BackupKind, config_dir: &Path) -> Result<BackupInfo> {
+    // SQLite is the only clock around; no need for a date crate
+    let timestamp: String =
+        conn.query_row("SELECT strftime('%Y%m%d-%H%M%f', 'now')", [], |row| {
+            row.get(0)
+        })?;
+
+    let backups_dir = config_dir.join("backups");
+    let mut dir = backups_dir.join(format!("{}-{}", timestamp, kind.as_str()));
+    let mut attempt = 1;
+    while dir.exists() {
+        attempt += 1;
+        dir = backups_dir.join(format!("{}-{}-{}", timestamp, kind.as_str(), attempt));
+    }
+    fs::create_dir_all(&dir).map_err(io_error)?;
+
+    conn.backup(MAIN_DB, dir.join(DATABASE_FILE), None)?;
+
+    let settings_path = config_dir.join(SETTINGS_FILE);
+    if settings_path.is_file() {
+        fs::copy(&settings_path, dir.join(SETTINGS_FILE)).map_err(io_error)?;
+    }
+
+    if kind == BackupKind::PreMigration {
+        prune_backups(&backups_dir, kind, MAX_MIGRATION_BACKUPS)?;
+    }
+
+    BackupInfo::from_dir(&dir).ok_or_else(|| io_error("backup was not written"))
+}
+
+/// Backups on disk, newest first
+pub fn list_backups() -> Vec<BackupInfo> {
+    list_backups_in(&get_backups_dir())
+}
+
+fn list_backups_in(backups_dir: &Path) -> Vec<BackupInfo> {
+    let mut backups: Vec<BackupInfo> = fs::read_dir(backups_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| BackupInfo::from_dir(&entry.path()))
+        .collect();
+
+    // Names start with the timestamp
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    backups
+}
+
+/// Replace the live database and settings with a backup. The backup is
+/// checked first and the current state is backed up before it is replaced,
+/// then put back if the restored database can't be brought up to date.
+pub fn restore_backup(conn: &mut Connection, backup_dir: &Path) -> Result<()> {
+    restore_backup_in(conn, backup_dir, &get_config_dir())
+}
+
+fn restore_backup_in(conn: &mut Connection, backup_dir: &Path, config_dir: &Path) -> Result<()> {
+    let database = backup_dir.join(DATABASE_FILE);
+    if !database.is_file() {
+        return Err(io_error(format!(
+            "{} holds no {}",
+            backup_dir.display(),
+            DATABASE_FILE
+        )));
+    }
+
+    check_integrity(&Connection::open_with_flags(
+        &database,
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )?)?;
+
+    let settings_file = backup_dir.join(SETTINGS_FILE);
+    let settings = match fs::read_to_string(&settings_file) {
+        Ok(content) => {
+            Settings::parse_str(&content)
+                .map_err(|e| io_error(format!("{}: {}", settings_file.display(), e)))?;
+            Some(content)
+        }
+        Err(_) => None,
+    };
+
+    let previous = create_backup_in(conn, BackupKind::PreRestore, config_dir)?;
+
+    let restored = (|| {
+        conn.restore(MAIN_DB, &database, None::<fn(Progress)>)?;
+        check_integrity(conn)?;
+        // Backups of an older Warden have an older schema
+        run_migrations(conn).map_err(io_error)?;
+
+        // Last, so the settings are only replaced once the database is
+        if let Some(settings) = &settings {
+            write_atomic(&config_dir.join(SETTINGS_FILE), settings).map_err(io_error)?;
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = restored {
+        let previous_database = Path::new(&previous.path).join(DATABASE_FILE);
+        conn.restore(MAIN_DB, &previous_database, None::<fn(Progress)>)
+            .map_err(|rollback| {
+                io_error(format!(
+                    "{}; putting back the previous state from {} failed too: {}",
+                    e, previous.path, rollback
+                ))
+            })?;
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// `PRAGMA integrity_check`, plus the tables Warden cannot run without
+fn check_integrity(conn: &Connection) -> Result<()> {
+    let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if result != "ok" {
+        return Err(io_error(format!("integrity check failed: {}", result)));
+    }
+
+    // Tables every schema version has; migrations create the rest
+    let tables: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master
+         WHERE type = 'table' AND name IN ('schema_migrations', 'projects')",
+        [],
+        |row| row.get(0),
+    )?;
+    if tables < 2 {
+        return Err(io_error("not a Warden database"));
+    }
+
+    Ok(())
+}
+
+/// Drop the oldest backups of a kind beyond `keep`
+fn prune_backups(backups_dir: &Path, kind: BackupKind, keep: usize) -> Result<()> {
+    let suffix = format!("-{}", kind.as_str());
+    let stale = list_backups_in(backups_dir)
+        .into_iter()
+        .filter(|backup| backup.name.contains(&suffix))
+        .skip(keep);
+
+    for backup in stale {
+        fs::remove_dir_all(&backup.path).map_err(io_error)?;
+    }
+
+    Ok(())
+}
+
+/// Same mapping as `Database::new` uses for filesystem errors
+fn io_error(e: impl ToString) -> rusqlite::Error {
+    rusqlite::Error::InvalidPath(PathBuf::from(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    fn live_database() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn
+    }
+
+    fn project_names(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM projects ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap()
+    }
+
+    fn add_project(conn: &Connection, name: &str) {
+        conn.execute(
+            "INSERT INTO projects (id, name, folder) VALUES (?1, ?1, '/code')",
+            [name],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let config = Fixture::new(&[(SETTINGS_FILE, r#"{ "version": 2, "theme": "dark" }"#)]);
+        let mut conn = live_database();
+        add_project(&conn, "shop");
+
+        let backup = create_backup_in(&conn, BackupKind::Manual, config.path()).unwrap();
+        assert!(backup.has_settings);
+
+        add_project(&conn, "blog");
+        config.write(SETTINGS_FILE, r#"{ "version": 2, "theme": "light" }"#);
+
+        restore_backup_in(&mut conn, Path::new(&backup.path), config.path()).unwrap();
+        assert_eq!(project_names(&conn), ["shop"]);
+        let settings = fs::read_to_string(config.join(SETTINGS_FILE)).unwrap();
+        assert_eq!(Settings::parse_str(&settings).unwrap().theme, "dark");
+
+        // The state it replaced was kept
+        let backups = list_backups_in(&config.join("backups"));
+        assert_eq!(backups.len(), 2);
+        assert!(backups.iter().any(|b| b.name.ends_with("-pre-restore")));
+    }
+
+    #[test]
+    fn test_restore_rejects_broken_backups() {
+        let config = Fixture::new(&[]);
+        let mut conn = live_database();
+        add_project(&conn, "shop");
+
+        let corrupt = config.write("corrupt/warden.db", "not a database");
+        let corrupt = corrupt.parent().unwrap();
+        assert!(restore_backup_in(&mut conn, corrupt, config.path()).is_err());
+
+        // A database from a newer Warden passes the checks, fails to
+        // migrate, and the live state is put back
+        let newer = config.mkdir("newer");
+        {
+            let other = Connection::open(newer.join(DATABASE_FILE)).unwrap();
+            other.execute_batch(
+                "CREATE TABLE schema_migrations (version INTEGER PRIMARY KEY, checksum TEXT);
+                 CREATE TABLE projects (id TEXT PRIMARY KEY, name TEXT NOT NULL, folder TEXT NOT NULL);
+                 INSERT INTO schema_migrations (version) VALUES (99);
+                 INSERT INTO projects (id, name, folder) VALUES ('x', 'future', '/code');",
+            )
+            .unwrap();
+        }
+        assert!(restore_backup_in(&mut conn, &newer, config.path()).is_err());
+        assert_eq!(project_names(&conn), ["shop"]);
+    }
+
+    #[test]
+    fn test_restore_migrates_older_schema() {
+        let config = Fixture::new(&[]);
+        let older = config.mkdir("older");
+        {
+            // Layout written by the original hand-coded migrations
+            let other = Connection::open(older.join(DATABASE_FILE)).unwrap();
+            other
+                .execute_batch(
+                    "CREATE TABLE schema_migrations (
+                        version INTEGER PRIMARY KEY,
+                        applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                    );
+                    CREATE TABLE projects (
+                        id TEXT PRIMARY KEY,
+                        name TEXT NOT NULL,
+                        folder TEXT NOT NULL,
+                        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                    );
+                    INSERT INTO schema_migrations (version) VALUES (1);
+                    INSERT INTO projects (id, name, folder) VALUES ('p1', 'legacy', '/code');",
+                )
+                .unwrap();
+        }
+
+        let mut conn = live_database();
+        restore_backup_in(&mut conn, &older, config.path()).unwrap();
+        assert_eq!(project_names(&conn), ["legacy"]);
+        check_integrity(&conn).unwrap();
+        let services: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'services'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(services, 1);
+    }
+}
@@ -1,92 +1,317 @@
-use rusqlite::{Connection, Result};
+use crate::database::backup::{create_backup, BackupKind};
+use rusqlite::{params, Connection, OptionalExtension};
 
-pub fn run_migrations(conn: &Connection) -> Result<()> {
-    // Criar tabela de controle de migrations
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS schema_migrations (
-            version INTEGER PRIMARY KEY,
-            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )",
-        [],
-    )?;
+/// A schema change. Once released, `up` must never be edited: its checksum
+/// is stored with the version and checked on every start.
+struct Migration {
+    version: i32,
+    name: &'static str,
+    up: &'static str,
+    /// Reverts `up`; only used during development
+    down: Option<&'static str>,
+}
 
-    // Verificar versão atual
-    let current_version: i32 = conn
-        .query_row(
-            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_projects",
+        up: "CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                folder TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE INDEX IF NOT EXISTS idx_projects_name ON projects(name);",
+        down: Some("DROP TABLE IF EXISTS projects;"),
+    },
+    Migration {
+        version: 2,
+        name: "create_services",
+        up: "CREATE TABLE IF NOT EXISTS services (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                service_type TEXT NOT NULL,
+                stack TEXT NOT NULL,
+                path TEXT NOT NULL,
+                url TEXT NOT NULL,
+                port INTEGER NOT NULL,
+                command TEXT NOT NULL,
+                status TEXT DEFAULT 'stopped',
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_services_project_id ON services(project_id);
+            CREATE INDEX IF NOT EXISTS idx_services_status ON services(status);",
+        down: Some("DROP TABLE IF EXISTS services;"),
+    },
+];
+
+fn latest_version() -> i32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Bring the schema up to date. Each migration runs in its own transaction
+/// together with its `schema_migrations` row.
+pub fn run_migrations(conn: &mut Connection) -> Result<(), String> {
+    migrate(conn, |conn| {
+        // In-memory databases have nothing on disk to protect
+        if conn.path().is_some_and(|p| !p.is_empty()) {
+            create_backup(conn, BackupKind::PreMigration)?;
+        }
+        Ok(())
+    })
+}
+
+/// `backup` runs before the first write to an existing database, the
+/// `schema_migrations` upgrade included
+fn migrate(
+    conn: &mut Connection,
+    backup: impl FnOnce(&Connection) -> rusqlite::Result<()>,
+) -> Result<(), String> {
+    ensure_migrations_table(conn).map_err(|e| e.to_string())?;
+
+    let current_version = current_version(conn)?;
+    if current_version > latest_version() {
+        return Err(format!(
+            "Database schema is at version {} but this version of Warden only knows up to {}; \
+             update Warden or restore a backup",
+            current_version,
+            latest_version()
+        ));
+    }
+
+    let has_checksums = has_checksum_column(conn).map_err(|e| e.to_string())?;
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current_version)
+        .collect();
 
-    if current_version < 1 {
-        migration_001_create_projects(conn)?;
+    // Keep a copy of existing data before touching the schema
+    if current_version > 0 && (!has_checksums || !pending.is_empty()) {
+        backup(conn).map_err(|e| e.to_string())?;
     }
 
-    if current_version < 2 {
-        migration_002_create_services(conn)?;
+    // Databases created before checksums were recorded
+    if !has_checksums {
+        conn.execute("ALTER TABLE schema_migrations ADD COLUMN checksum TEXT", [])
+            .map_err(|e| e.to_string())?;
+    }
+
+    verify_checksums(conn)?;
+
+    for migration in pending {
+        apply(conn, migration).map_err(|e| {
+            format!(
+                "Migration {} ({}) failed: {}",
+                migration.version, migration.name, e
+            )
+        })?;
     }
 
     Ok(())
 }
 
-fn migration_001_create_projects(conn: &Connection) -> Result<()> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS projects (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            folder TEXT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )",
-        [],
-    )?;
+/// Revert migrations above `target_version`, newest first. Development only:
+/// fails on migrations without a `down` script.
+pub fn rollback_migrations(conn: &mut Connection, target_version: i32) -> Result<(), String> {
+    let current_version = current_version(conn)?;
 
-    // Criar índice para busca por nome
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_projects_name ON projects(name)",
-        [],
-    )?;
+    for migration in MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|m| m.version > target_version && m.version <= current_version)
+    {
+        let down = migration.down.ok_or_else(|| {
+            format!(
+                "Migration {} ({}) cannot be reverted",
+                migration.version, migration.name
+            )
+        })?;
 
-    // Registrar migration
-    conn.execute("INSERT INTO schema_migrations (version) VALUES (1)", [])?;
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute_batch(down).map_err(|e| e.to_string())?;
+        tx.execute(
+            "DELETE FROM schema_migrations WHERE version = ?1",
+            params![migration.version],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
+    }
 
     Ok(())
 }
 
-fn migration_002_create_services(conn: &Connection) -> Result<()> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS services (
-            id TEXT PRIMARY KEY,
-            project_id TEXT NOT NULL,
-            name TEXT NOT NULL,
-            service_type TEXT NOT NULL,
-            stack TEXT NOT NULL,
-            path TEXT NOT NULL,
-            url TEXT NOT NULL,
-            port INTEGER NOT NULL,
-            command TEXT NOT NULL,
-            status TEXT DEFAULT 'stopped',
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
-        )",
-        [],
+fn apply(conn: &mut Connection, migration: &Migration) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(migration.up)?;
+    tx.execute(
+        "INSERT INTO schema_migrations (version, checksum) VALUES (?1, ?2)",
+        params![migration.version, checksum(migration.up)],
     )?;
+    tx.commit()
+}
 
-    // Criar índices
+fn ensure_migrations_table(conn: &Connection) -> rusqlite::Result<()> {
     conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_services_project_id ON services(project_id)",
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            checksum TEXT
+        )",
         [],
     )?;
+    Ok(())
+}
 
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_services_status ON services(status)",
+fn has_checksum_column(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.prepare("SELECT 1 FROM pragma_table_info('schema_migrations') WHERE name = 'checksum'")?
+        .exists([])
+}
+
+fn current_version(conn: &Connection) -> Result<i32, String> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
         [],
-    )?;
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Fail when an applied migration differs from the one shipped. Versions
+/// recorded before checksums existed are trusted and get theirs filled in.
+fn verify_checksums(conn: &Connection) -> Result<(), String> {
+    for migration in MIGRATIONS {
+        let stored: Option<Option<String>> = conn
+            .query_row(
+                "SELECT checksum FROM schema_migrations WHERE version = ?1",
+                params![migration.version],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
 
-    // Registrar migration
-    conn.execute("INSERT INTO schema_migrations (version) VALUES (2)", [])?;
+        let expected = checksum(migration.up);
+        match stored {
+            None => {}
+            Some(None) => {
+                conn.execute(
+                    "UPDATE schema_migrations SET checksum = ?1 WHERE version = ?2",
+                    params![expected, migration.version],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            Some(Some(stored)) if stored != expected => {
+                return Err(format!(
+                    "Migration {} ({}) was modified after being applied",
+                    migration.version, migration.name
+                ));
+            }
+            Some(Some(_)) => {}
+        }
+    }
 
     Ok(())
 }
+
+/// FNV-1a of the SQL, ignoring whitespace so reformatting is not a change
+fn checksum(sql: &str) -> String {
+    let hash = sql
+        .split_whitespace()
+        .flat_map(|word| word.bytes().chain(std::iter::once(b' ')))
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_migrate_empty_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert_eq!(
+            tables(&conn),
+            vec!["projects", "schema_migrations", "services"]
+        );
+
+        // Running again is a no-op
+        run_migrations(&mut conn).unwrap();
+
+        rollback_migrations(&mut conn, 1).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), 1);
+        assert_eq!(tables(&conn), vec!["projects", "schema_migrations"]);
+    }
+
+    #[test]
+    fn test_migrate_v1_database() {
+        // Layout written by the original hand-coded migrations
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schema_migrations (
+                version INTEGER PRIMARY KEY,
+                applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                folder TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO schema_migrations (version) VALUES (1);
+            INSERT INTO projects (id, name, folder) VALUES ('p1', 'shop', '/code/shop');",
+        )
+        .unwrap();
+
+        // The backup sees the database exactly as it was found
+        let mut backed_up = false;
+        migrate(&mut conn, |conn| {
+            assert!(!has_checksum_column(conn)?);
+            assert_eq!(current_version(conn).unwrap(), 1);
+            backed_up = true;
+            Ok(())
+        })
+        .unwrap();
+        assert!(backed_up);
+
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        let projects: i32 = conn
+            .query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(projects, 1);
+        let missing_checksums: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM schema_migrations WHERE checksum IS NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(missing_checksums, 0);
+
+        // Nothing left to do, nothing to back up
+        migrate(&mut conn, |_| panic!("backup of an up to date database")).unwrap();
+
+        // A database from a newer Warden is left alone
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (99)", [])
+            .unwrap();
+        assert!(run_migrations(&mut conn).is_err());
+    }
+}
@@ -5,7 +5,11 @@ mod models;
 mod repositories;
 mod utils;
 
+use crate::commands::disk::DiskUsageJobs;
+use crate::commands::install::InstallJobs;
 use crate::commands::project::AppState;
+use crate::commands::scaffold::ScaffoldJobs;
+use crate::commands::scan::ScanJobs;
 use crate::database::connection::init_database;
 use crate::database::migrations::run_migrations;
 use std::sync::Mutex;
@@ -20,6 +24,10 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(AppState { db: Mutex::new(db) })
+        .manage(ScanJobs::default())
+        .manage(DiskUsageJobs::default())
+        .manage(InstallJobs::default())
+        .manage(ScaffoldJobs::default())
         .invoke_handler(tauri::generate_handler![
             commands::settings::save_default_ide,
             commands::settings::get_default_ide,
@@ -27,12 +35,28 @@ pub fn run() {
             commands::settings::get_ide_command,
             commands::settings::save_root_path,
             commands::settings::get_root_path,
+            commands::settings::save_workspace_roots,
+            commands::settings::get_workspace_roots,
             commands::settings::save_default_suffix,
             commands::settings::get_default_suffix,
             commands::settings::save_scan_depth,
             commands::settings::get_scan_depth,
+            commands::settings::save_ignore_patterns,
+            commands::settings::get_ignore_patterns,
+            commands::settings::save_scaffold_timeout,
+            commands::settings::get_scaffold_timeout,
+            commands::backup::backup_state,
+            commands::backup::get_backups,
+            commands::backup::restore_state,
+            commands::backup::rollback_database,
+            commands::disk::start_disk_usage_scan,
+            commands::disk::cancel_disk_usage_scan,
+            commands::disk::clean_project,
             commands::git::clone,
             commands::ide::open_in_ide,
+            commands::install::get_install_status,
+            commands::install::install_dependencies,
+            commands::launch::get_launch_command,
             commands::project::create_project,
             commands::project::get_projects,
             commands::project::get_project,
@@ -43,9 +67,18 @@ pub fn run() {
             commands::project::scan_project_services,
             commands::project::scan_workspace_services,
             commands::project::get_detected_services,
+            commands::project::explain_framework_detection,
+            commands::project::export_project,
+            commands::project::import_project,
             commands::project::update_project,
             commands::project::update_service,
+            commands::scaffold::get_scaffold_templates,
+            commands::scaffold::preview_scaffold,
             commands::scaffold::execute_scaffold,
+            commands::scaffold::cancel_scaffold,
+            commands::scaffold::get_dlx_command,
+            commands::scan::start_workspace_scan,
+            commands::scan::cancel_workspace_scan,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -111,6 +111,9 @@ pub enum Framework {
     CSharp,
     Elixir,
 
+    /// Matched by a user-defined detection rule
+    Custom,
+
     Unknown,
 }
 
@@ -206,7 +209,7 @@ impl Framework {
             Framework::CSharp => "other",
             Framework::Elixir => "other",
 
-            Framework::Unknown => "other",
+            Framework::Custom | Framework::Unknown => "other",
         }
     }
 
@@ -297,7 +300,7 @@ impl Framework {
             Framework::CSharp => Some(5000),
             Framework::Elixir => Some(4000),
 
-            Framework::Unknown => None,
+            Framework::Custom | Framework::Unknown => None,
         }
     }
 }
@@ -323,6 +326,8 @@ pub enum PackageManager {
     Pipenv,
     Conda,
     Uv,
+    Pdm,
+    Hatch,
 
     // PHP
     Composer,
@@ -357,11 +362,12 @@ impl PackageManager {
             PackageManager::Bun => "bun run",
             PackageManager::Deno => "deno task",
             PackageManager::Cargo => "cargo",
-            PackageManager::Pip
-            | PackageManager::Poetry
-            | PackageManager::Pipenv
-            | PackageManager::Conda
-            | PackageManager::Uv => "python",
+            PackageManager::Pip | PackageManager::Conda => "python",
+            PackageManager::Poetry => "poetry run",
+            PackageManager::Pipenv => "pipenv run",
+            PackageManager::Uv => "uv run",
+            PackageManager::Pdm => "pdm run",
+            PackageManager::Hatch => "hatch run",
             PackageManager::Composer => "composer",
             PackageManager::Bundler => "bundle exec",
             PackageManager::GoMod => "go",
@@ -373,6 +379,33 @@ impl PackageManager {
         }
     }
 
+    /// Name of the package manager's executable
+    pub fn cli_name(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn | PackageManager::YarnBerry => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+            PackageManager::Deno => "deno",
+            PackageManager::Cargo => "cargo",
+            PackageManager::Pip => "pip",
+            PackageManager::Poetry => "poetry",
+            PackageManager::Pipenv => "pipenv",
+            PackageManager::Conda => "conda",
+            PackageManager::Uv => "uv",
+            PackageManager::Pdm => "pdm",
+            PackageManager::Hatch => "hatch",
+            PackageManager::Composer => "composer",
+            PackageManager::Bundler => "bundle",
+            PackageManager::GoMod => "go",
+            PackageManager::Maven => "mvn",
+            PackageManager::Gradle => "gradle",
+            PackageManager::Nuget | PackageManager::Dotnet => "dotnet",
+            PackageManager::Mix => "mix",
+            PackageManager::Unknown => "",
+        }
+    }
+
     /// Get the install command for this package manager
     pub fn install_command(&self) -> &'static str {
         match self {
@@ -380,13 +413,15 @@ impl PackageManager {
             PackageManager::Yarn | PackageManager::YarnBerry => "yarn install",
             PackageManager::Pnpm => "pnpm install",
             PackageManager::Bun => "bun install",
-            PackageManager::Deno => "deno cache",
+            PackageManager::Deno => "deno install",
             PackageManager::Cargo => "cargo build",
             PackageManager::Pip => "pip install -r requirements.txt",
             PackageManager::Poetry => "poetry install",
             PackageManager::Pipenv => "pipenv install",
             PackageManager::Conda => "conda install",
             PackageManager::Uv => "uv sync",
+            PackageManager::Pdm => "pdm install",
+            PackageManager::Hatch => "hatch env create",
             PackageManager::Composer => "composer install",
             PackageManager::Bundler => "bundle install",
             PackageManager::GoMod => "go mod download",
@@ -397,6 +432,32 @@ impl PackageManager {
             PackageManager::Unknown => "",
         }
     }
+
+    /// Run a binary installed in the project (`npx nx ...`); JavaScript only
+    pub fn exec_command(&self, command: &str) -> Option<String> {
+        match self {
+            PackageManager::Npm => Some(format!("npx {}", command)),
+            PackageManager::Yarn | PackageManager::YarnBerry => Some(format!("yarn {}", command)),
+            PackageManager::Pnpm => Some(format!("pnpm exec {}", command)),
+            PackageManager::Bun => Some(format!("bunx {}", command)),
+            PackageManager::Deno => Some(format!("deno run -A npm:{}", command)),
+            _ => None,
+        }
+    }
+
+    /// Download and run a package without installing it
+    /// (`pnpm dlx create-vite ...`); JavaScript only
+    pub fn dlx_command(&self, command: &str) -> Option<String> {
+        match self {
+            // Yarn 1 has no dlx
+            PackageManager::Npm | PackageManager::Yarn => Some(format!("npx {}", command)),
+            PackageManager::YarnBerry => Some(format!("yarn dlx {}", command)),
+            PackageManager::Pnpm => Some(format!("pnpm dlx {}", command)),
+            PackageManager::Bun => Some(format!("bunx {}", command)),
+            PackageManager::Deno => Some(format!("deno run -A npm:{}", command)),
+            _ => None,
+        }
+    }
 }
 
 /// Service category/type
@@ -442,7 +503,160 @@ pub enum MonorepoTool {
     Lerna,
     Rush,
     CargoWorkspace,
-    None,
+    Maven,
+    Gradle,
+    GoWorkspace,
+}
+
+/// A workspace system found in a repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSystem {
+    pub tool: MonorepoTool,
+    /// Directory holding its definition, relative to the repository (`.` for the root)
+    pub root: String,
+}
+
+/// Where a detected task was declared
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskSource {
+    Makefile,
+    Justfile,
+    Taskfile,
+    PyProject,
+    Pipfile,
+    LaunchSettings,
+    Cargo,
+    Nx,
+    Turborepo,
+    Deno,
+}
+
+impl TaskSource {
+    /// Get the command used to invoke a task from this source
+    pub fn run_command(&self, task: &str) -> String {
+        match self {
+            TaskSource::Makefile => format!("make {}", task),
+            TaskSource::Justfile => format!("just {}", task),
+            TaskSource::Taskfile => format!("task {}", task),
+            // Console scripts are installed on PATH; the Python runner prefix
+            // is added by the caller.
+            TaskSource::PyProject => task.to_string(),
+            TaskSource::Pipfile => format!("pipenv run {}", task),
+            // `project file:profile`, or the profile alone in the project's directory
+            TaskSource::LaunchSettings => match task.split_once(':') {
+                Some((project, profile)) => format!(
+                    "dotnet run --project \"{}\" --launch-profile \"{}\"",
+                    project, profile
+                ),
+                None => format!("dotnet run --launch-profile \"{}\"", task),
+            },
+            // `package:example`, or the example alone in a single-crate project
+            TaskSource::Cargo => match task.split_once(':') {
+                Some((package, example)) => {
+                    format!("cargo run -p {} --example {}", package, example)
+                }
+                None => format!("cargo run --example {}", task),
+            },
+            // `project:target`
+            TaskSource::Nx => format!("nx run {}", task),
+            // `task --filter=package`
+            TaskSource::Turborepo => format!("turbo run {}", task),
+            TaskSource::Deno => format!("deno task {}", task),
+        }
+    }
+}
+
+/// A runnable task declared by a task runner (Makefile target, just recipe...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedTask {
+    pub name: String,
+    pub description: Option<String>,
+    pub source: TaskSource,
+    /// Command that invokes the task (e.g. `make dev`)
+    pub command: String,
+    /// Raw commands from the task body
+    pub steps: Vec<String>,
+}
+
+/// A framework considered during detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkCandidate {
+    pub framework: Framework,
+    /// Label of the user-defined rule behind the candidate
+    pub rule: Option<String>,
+    pub score: u32,
+    /// Files and dependencies that contributed to the score
+    pub evidence: Vec<String>,
+}
+
+/// Why a framework was chosen: every candidate, best first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionEvidence {
+    pub framework: Framework,
+    /// 0.0 - 1.0, low when another framework scored close to the winner
+    pub confidence: f32,
+    pub candidates: Vec<FrameworkCandidate>,
+}
+
+/// How to tell a running service is healthy
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct HealthCheck {
+    /// URL answering 2xx when the service is up
+    pub url: Option<String>,
+    /// Command exiting 0 when the service is up
+    pub command: Option<String>,
+    /// Seconds between checks
+    pub interval: Option<u32>,
+}
+
+/// A runtime whose version a repository pins
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Toolchain {
+    Node,
+    Python,
+    Rust,
+    Go,
+}
+
+/// The version of a toolchain a service asks for next to the one on PATH
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolchainRequirement {
+    pub toolchain: Toolchain,
+    /// Version or range as written (`20`, `>=18 <21`, `1.77.2`, `stable`)
+    pub required: String,
+    /// File the requirement comes from, relative to the project root
+    pub source: String,
+    /// `None` when the toolchain is not on PATH
+    pub installed: Option<String>,
+    /// Missing, or installed in a version the requirement rules out
+    pub mismatch: bool,
+}
+
+/// Whether a service's dependencies are installed and match its lockfile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallStatus {
+    Installed,
+    /// The lockfile changed since the last install
+    Stale,
+    Missing,
+}
+
+/// A problem found in a `.warden.toml` / `warden.yaml` manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestError {
+    /// 1-based line the error points at
+    pub line: usize,
+    pub message: String,
 }
 
 /// A detected service within a project
@@ -454,6 +668,9 @@ pub struct DetectedService {
     pub relative_path: String,
     pub category: ServiceCategory,
     pub framework: Framework,
+    /// Display name set by a user-defined detection rule
+    #[serde(default)]
+    pub framework_label: Option<String>,
     pub stack: String,
     pub package_manager: PackageManager,
     pub port: Option<u16>,
@@ -463,6 +680,23 @@ pub struct DetectedService {
     pub install_command: Option<String>,
     pub is_docker_service: bool,
     pub docker_service_name: Option<String>,
+    pub tasks: Vec<DetectedTask>,
+    #[serde(default)]
+    pub detection_evidence: Option<DetectionEvidence>,
+    /// Env files loaded before the service starts, relative to its path
+    #[serde(default)]
+    pub env_files: Vec<String>,
+    /// Names of services that must be running first
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub health_check: Option<HealthCheck>,
+    /// Pinned toolchain versions found in the service's version files
+    #[serde(default)]
+    pub toolchains: Vec<ToolchainRequirement>,
+    /// `None` when the service has nothing Warden knows how to check
+    #[serde(default)]
+    pub install_status: Option<InstallStatus>,
 }
 
 impl DetectedService {
@@ -473,6 +707,7 @@ impl DetectedService {
             relative_path,
             category: ServiceCategory::Unknown,
             framework: Framework::Unknown,
+            framework_label: None,
             stack: "other".to_string(),
             package_manager: PackageManager::Unknown,
             port: None,
@@ -482,6 +717,13 @@ impl DetectedService {
             install_command: None,
             is_docker_service: false,
             docker_service_name: None,
+            tasks: Vec::new(),
+            detection_evidence: None,
+            env_files: Vec::new(),
+            depends_on: Vec::new(),
+            health_check: None,
+            toolchains: Vec::new(),
+            install_status: None,
         }
     }
 
@@ -512,13 +754,23 @@ pub struct DetectedProject {
     pub name: String,
     pub path: String,
     pub is_monorepo: bool,
-    pub monorepo_tool: MonorepoTool,
+    /// Every workspace system, including nested ones
+    pub workspace_systems: Vec<WorkspaceSystem>,
     pub is_tauri: bool,
     pub has_docker: bool,
     pub has_docker_compose: bool,
     pub root_package_manager: PackageManager,
     pub services: Vec<DetectedService>,
     pub workspaces: Vec<String>,
+    /// `.warden.toml` / `warden.yaml` the services were declared in
+    pub manifest: Option<String>,
+    pub manifest_errors: Vec<ManifestError>,
+    /// Detection rule files that failed to load and the rules dropped from them
+    pub rule_errors: Vec<String>,
+    /// Workspace root the project was found under
+    pub workspace_root: Option<String>,
+    /// Whether the root allows registering the project without asking
+    pub auto_import: bool,
 }
 
 impl DetectedProject {
@@ -527,13 +779,18 @@ impl DetectedProject {
             name,
             path,
             is_monorepo: false,
-            monorepo_tool: MonorepoTool::None,
+            workspace_systems: Vec::new(),
             is_tauri: false,
             has_docker: false,
             has_docker_compose: false,
             root_package_manager: PackageManager::Unknown,
             services: Vec::new(),
             workspaces: Vec::new(),
+            manifest: None,
+            manifest_errors: Vec::new(),
+            rule_errors: Vec::new(),
+            workspace_root: None,
+            auto_import: true,
         }
     }
 }
@@ -1,3 +1,4 @@
 pub mod detected_service;
 pub mod project;
+pub mod scaffold;
 pub mod service;
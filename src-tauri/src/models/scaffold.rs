@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::detected_service::{PackageManager, ServiceCategory};
+
+/// Language a scaffold template generates
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScaffoldLanguage {
+    Typescript,
+    Javascript,
+    Python,
+    Php,
+    Rust,
+    Go,
+    Java,
+    Kotlin,
+    Ruby,
+    Elixir,
+    Csharp,
+}
+
+impl ScaffoldLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScaffoldLanguage::Typescript => "typescript",
+            ScaffoldLanguage::Javascript => "javascript",
+            ScaffoldLanguage::Python => "python",
+            ScaffoldLanguage::Php => "php",
+            ScaffoldLanguage::Rust => "rust",
+            ScaffoldLanguage::Go => "go",
+            ScaffoldLanguage::Java => "java",
+            ScaffoldLanguage::Kotlin => "kotlin",
+            ScaffoldLanguage::Ruby => "ruby",
+            ScaffoldLanguage::Elixir => "elixir",
+            ScaffoldLanguage::Csharp => "csharp",
+        }
+    }
+}
+
+/// Where a template was loaded from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateSource {
+    #[default]
+    Builtin,
+    /// `scaffold-templates.*` in the Warden config dir
+    User,
+}
+
+/// A project generator. Steps are run without a shell: each is a program
+/// and its arguments, in which `{name}`, `{port}`, `{packageManager}` and
+/// `{language}` are replaced by the scaffold parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldTemplate {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub language: ScaffoldLanguage,
+    pub package_manager: PackageManager,
+    pub category: ServiceCategory,
+    pub steps: Vec<Vec<String>>,
+    /// Create the project folder and run the steps inside it, for
+    /// generators that work in the current directory
+    #[serde(default)]
+    pub create_folder: bool,
+    /// Files written into the project after the steps, by relative path
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    pub default_port: u16,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_deserializing)]
+    pub source: TemplateSource,
+}
+
+/// Values a template is instantiated with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldParams {
+    /// Project folder name, created inside the target folder
+    pub name: String,
+    /// Defaults to the template's
+    pub package_manager: Option<PackageManager>,
+    /// Defaults to the template's
+    pub language: Option<ScaffoldLanguage>,
+    /// Defaults to the template's
+    pub port: Option<u16>,
+}
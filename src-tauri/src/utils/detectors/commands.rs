@@ -1,5 +1,8 @@
-use crate::models::detected_service::{Framework, PackageManager};
-use crate::utils::parsers::{CargoToml, PackageJson, TauriConf};
+use crate::models::detected_service::{DetectedTask, Framework, PackageManager};
+use crate::utils::detectors::jvm::{is_runnable_jvm_module, locate_jvm_build, JvmBuild};
+use crate::utils::detectors::python::{find_manage_py, find_python_app, python_command};
+use crate::utils::detectors::task_runner::{find_build_task, find_dev_task};
+use crate::utils::parsers::{AirToml, CargoToml, DenoJson, PackageJson, PyProject, TauriConf};
 use std::env::consts::OS;
 use std::path::Path;
 
@@ -7,24 +10,6 @@ use std::path::Path;
 // OS helpers
 // ---------------------------------------------------------------------------
 
-/// Returns `"mvnw.cmd"` on Windows, `"./mvnw"` on Unix.
-fn mvnw() -> &'static str {
-    if OS == "windows" {
-        "mvnw.cmd"
-    } else {
-        "./mvnw"
-    }
-}
-
-/// Returns `"gradlew.bat"` on Windows, `"./gradlew"` on Unix.
-fn gradlew() -> &'static str {
-    if OS == "windows" {
-        "gradlew.bat"
-    } else {
-        "./gradlew"
-    }
-}
-
 /// Returns `"app.exe"` on Windows, `"./app"` on Unix.
 fn go_binary() -> &'static str {
     if OS == "windows" {
@@ -48,6 +33,39 @@ pub fn detect_commands(
     package_manager: &PackageManager,
     package_json: Option<&PackageJson>,
     cargo_toml: Option<&CargoToml>,
+    tasks: &[DetectedTask],
+) -> DetectedCommands {
+    let mut commands =
+        detect_framework_commands(path, framework, package_manager, package_json, cargo_toml);
+
+    // Tauri's dev command is dictated by tauri.conf.json
+    if *framework == Framework::Tauri {
+        return commands;
+    }
+
+    // A `dev`/`run`/`serve` task is what the team actually runs, so it wins
+    // over framework heuristics. An explicit package.json dev script is just
+    // as intentional and is kept.
+    let has_dev_script = package_json.is_some_and(|pkg| pkg.get_dev_command().is_some());
+    if !has_dev_script {
+        if let Some(task) = find_dev_task(tasks) {
+            commands.dev = Some(task.command.clone());
+        }
+    }
+
+    if commands.build.is_none() {
+        commands.build = find_build_task(tasks).map(|task| task.command.clone());
+    }
+
+    commands
+}
+
+fn detect_framework_commands(
+    path: &Path,
+    framework: &Framework,
+    package_manager: &PackageManager,
+    package_json: Option<&PackageJson>,
+    cargo_toml: Option<&CargoToml>,
 ) -> DetectedCommands {
     let mut commands = DetectedCommands {
         install: Some(package_manager.install_command().to_string()),
@@ -86,6 +104,20 @@ pub fn detect_commands(
             return commands;
         }
 
+        // `deno task` runs package.json scripts too, so tasks come first
+        Framework::Deno => {
+            if let Some(deno) = DenoJson::parse(path) {
+                commands.dev = deno
+                    .task_command("dev")
+                    .or_else(|| deno.task_command("start"));
+                commands.build = deno.task_command("build");
+                commands.start = deno.task_command("start");
+            }
+            if commands.dev.is_some() || package_json.is_none() {
+                return commands;
+            }
+        }
+
         _ => {}
     }
 
@@ -123,21 +155,49 @@ pub fn detect_commands(
 
     match framework {
         Framework::Django => {
-            commands.dev = Some("python manage.py runserver".to_string());
+            let manage_py = find_manage_py(path).unwrap_or_else(|| "manage.py".to_string());
+            let runserver =
+                python_command(package_manager, &format!("python {} runserver", manage_py));
+            commands.dev = Some(runserver.clone());
             commands.build = None;
-            commands.start = Some("python manage.py runserver".to_string());
+            commands.start = Some(runserver);
         }
         Framework::Flask => {
-            commands.dev = Some("flask run".to_string());
-            commands.start = Some("flask run".to_string());
+            let flask_run = match find_python_app(path, framework) {
+                // src layouts are not importable from the project root, so
+                // point Flask at the file instead of the module
+                Some(app) if app.app_dir.is_some() => {
+                    format!("flask --app {}:{} run", app.file, app.variable)
+                }
+                Some(app) => format!("flask --app {} run", app.target()),
+                None => "flask run".to_string(),
+            };
+            commands.dev = Some(python_command(package_manager, &flask_run));
+            commands.start = Some(python_command(package_manager, &flask_run));
         }
         Framework::FastApi => {
-            commands.dev = Some("uvicorn main:app --reload".to_string());
-            commands.start = Some("uvicorn main:app".to_string());
+            let (target, app_dir) = match find_python_app(path, framework) {
+                Some(app) => (
+                    app.target(),
+                    app.app_dir
+                        .map(|dir| format!(" --app-dir {}", dir))
+                        .unwrap_or_default(),
+                ),
+                None => ("main:app".to_string(), String::new()),
+            };
+            commands.dev = Some(python_command(
+                package_manager,
+                &format!("uvicorn {} --reload{}", target, app_dir),
+            ));
+            commands.start = Some(python_command(
+                package_manager,
+                &format!("uvicorn {}{}", target, app_dir),
+            ));
         }
         Framework::Python => {
-            commands.dev = Some("python main.py".to_string());
-            commands.start = Some("python main.py".to_string());
+            let entry = python_entry_command(path);
+            commands.dev = Some(python_command(package_manager, &entry));
+            commands.start = Some(python_command(package_manager, &entry));
         }
         _ => {}
     }
@@ -179,7 +239,12 @@ pub fn detect_commands(
         | Framework::Chi
         | Framework::Beego
         | Framework::Go => {
-            commands.dev = Some("go run .".to_string());
+            commands.dev = Some(
+                AirToml::parse(path)
+                    .and_then(Result::ok)
+                    .map(|air| air.get_dev_command())
+                    .unwrap_or_else(|| "go run .".to_string()),
+            );
             commands.build = Some(format!(
                 "go build -o {} .",
                 go_binary().trim_start_matches("./")
@@ -189,25 +254,30 @@ pub fn detect_commands(
         _ => {}
     }
 
-    match framework {
-        Framework::Spring => {
-            if path.join("mvnw").exists() || path.join("mvnw.cmd").exists() {
-                commands.dev = Some(format!("{} spring-boot:run", mvnw()));
-                commands.build = Some(format!("{} package", mvnw()));
-            } else if path.join("gradlew").exists() || path.join("gradlew.bat").exists() {
-                commands.dev = Some(format!("{} bootRun", gradlew()));
-                commands.build = Some(format!("{} build", gradlew()));
-            }
-        }
-        Framework::Quarkus => {
-            commands.dev = Some(format!("{} quarkus:dev", mvnw()));
-            commands.build = Some(format!("{} package", mvnw()));
-        }
-        Framework::Ktor => {
-            commands.dev = Some(format!("{} run", gradlew()));
-            commands.build = Some(format!("{} build", gradlew()));
-        }
-        _ => {}
+    if let Some(build) = jvm_build_for(path, framework) {
+        let (dev_goal, run_goal) = match build.tool {
+            PackageManager::Gradle => ("bootRun", "run"),
+            _ => ("spring-boot:run", "exec:java"),
+        };
+
+        let dev = match (framework, &build.tool) {
+            (Framework::Spring, _) => Some(dev_goal),
+            (Framework::Quarkus, PackageManager::Gradle) => Some("quarkusDev"),
+            (Framework::Quarkus, _) => Some("quarkus:dev"),
+            (Framework::Micronaut, PackageManager::Maven) => Some("mn:run"),
+            (Framework::Micronaut | Framework::Ktor, _) => Some(run_goal),
+            _ if is_runnable_jvm_module(path) => Some(run_goal),
+            _ => None,
+        };
+
+        let (build_goal, install_goal) = match build.tool {
+            PackageManager::Gradle => ("build", "build"),
+            _ => ("package", "install"),
+        };
+
+        commands.dev = dev.map(|goal| build.command(goal));
+        commands.build = Some(build.command(build_goal));
+        commands.install = Some(build.command(install_goal));
     }
 
     match framework {
@@ -227,6 +297,38 @@ pub fn detect_commands(
     commands
 }
 
+/// The Maven/Gradle build of a JVM service
+fn jvm_build_for(path: &Path, framework: &Framework) -> Option<JvmBuild> {
+    match framework {
+        Framework::Spring
+        | Framework::Quarkus
+        | Framework::Micronaut
+        | Framework::Ktor
+        | Framework::Java
+        | Framework::Kotlin => locate_jvm_build(path),
+        _ => None,
+    }
+}
+
+/// Entry point for a generic Python project: a conventional script file, or
+/// the single console script declared in pyproject.toml.
+fn python_entry_command(path: &Path) -> String {
+    for file in ["main.py", "app.py", "run.py"] {
+        if path.join(file).exists() {
+            return format!("python {}", file);
+        }
+    }
+
+    if let Some(pyproject) = PyProject::parse(path) {
+        let scripts = pyproject.get_console_scripts();
+        if scripts.len() == 1 {
+            return scripts[0].clone();
+        }
+    }
+
+    "python main.py".to_string()
+}
+
 fn format_npm_command(
     run_prefix: &str,
     script_name: &str,
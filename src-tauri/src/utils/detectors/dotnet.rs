@@ -0,0 +1,256 @@
+use crate::models::detected_service::{
+    DetectedService, DetectedTask, Framework, ServiceCategory, TaskSource,
+};
+use crate::utils::parsers::{
+    find_project_file, is_project_file, project_name, CsProj, DotnetProjectKind, LaunchSettings,
+    Solution,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A .NET project found through a solution or next to it
+#[derive(Debug, Clone)]
+pub struct DotnetProject {
+    pub name: String,
+    /// Project file relative to the scanned directory (`src/Api/Api.csproj`)
+    pub project_file: String,
+    /// Directory holding the project file
+    pub dir: PathBuf,
+    pub csproj: CsProj,
+}
+
+impl DotnetProject {
+    pub fn kind(&self) -> DotnetProjectKind {
+        let kind = self.csproj.kind();
+
+        // Blazor Web Apps (.NET 8+) use the Web SDK with Razor components
+        if kind == DotnetProjectKind::Web
+            && (self.dir.join("Components").join("App.razor").exists()
+                || self.dir.join("App.razor").exists())
+        {
+            return DotnetProjectKind::Blazor;
+        }
+
+        kind
+    }
+
+    pub fn is_runnable(&self) -> bool {
+        !matches!(
+            self.kind(),
+            DotnetProjectKind::Library | DotnetProjectKind::Test
+        )
+    }
+}
+
+/// Projects listed in the directory's solution, or the project files found
+/// directly in it when there is no solution.
+pub fn find_dotnet_projects(path: &Path) -> Vec<DotnetProject> {
+    let project_files: Vec<String> = match Solution::parse(path) {
+        Some(solution) => solution.projects.into_iter().map(|p| p.path).collect(),
+        None => fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| is_project_file(name))
+            .collect(),
+    };
+
+    let mut projects: Vec<DotnetProject> = project_files
+        .into_iter()
+        .filter_map(|project_file| {
+            let file_path = path.join(&project_file);
+            let csproj = CsProj::parse(&file_path)?;
+            Some(DotnetProject {
+                name: project_name(&project_file),
+                dir: file_path.parent()?.to_path_buf(),
+                project_file,
+                csproj,
+            })
+        })
+        .collect();
+
+    projects.sort_by(|a, b| a.project_file.cmp(&b.project_file));
+    projects
+}
+
+/// Framework of the main project in a directory: the project file itself, or
+/// the first runnable project of the solution.
+pub fn detect_dotnet_framework(path: &Path) -> Option<Framework> {
+    if let Some(project_file) = find_project_file(path) {
+        let csproj = CsProj::parse(&project_file)?;
+        let project = DotnetProject {
+            name: String::new(),
+            project_file: String::new(),
+            dir: path.to_path_buf(),
+            csproj,
+        };
+        return Some(framework_for_kind(&project.kind()));
+    }
+
+    let projects = find_dotnet_projects(path);
+    if projects.is_empty() {
+        return Solution::parse(path).map(|_| Framework::CSharp);
+    }
+
+    Some(
+        projects
+            .iter()
+            .find(|p| p.is_runnable())
+            .map(|p| framework_for_kind(&p.kind()))
+            .unwrap_or(Framework::CSharp),
+    )
+}
+
+/// Split a .NET solution into one service per runnable project, each run
+/// from the solution directory with `--project`.
+pub fn detect_dotnet_services(path: &Path, base: &DetectedService) -> Vec<DetectedService> {
+    if !matches!(
+        base.framework,
+        Framework::AspNetCore | Framework::Blazor | Framework::CSharp
+    ) {
+        return Vec::new();
+    }
+
+    let mut services = Vec::new();
+
+    for project in find_dotnet_projects(path)
+        .into_iter()
+        .filter(|p| p.is_runnable())
+    {
+        let kind = project.kind();
+        let target = project_target(&project);
+        let launch_settings = LaunchSettings::parse(&project.dir);
+
+        let mut service = base.clone();
+        service.name = project.name.clone();
+        service.framework = framework_for_kind(&kind);
+        service.category = category_for_kind(&kind, &project.dir);
+        service.port = launch_settings
+            .as_ref()
+            .and_then(|s| s.get_port())
+            .or(match kind {
+                DotnetProjectKind::Web | DotnetProjectKind::Blazor => base.port,
+                _ => None,
+            });
+
+        service.dev_command = Some(format!("dotnet watch --project {}", target));
+        service.build_command = Some(format!("dotnet build \"{}\"", project.project_file));
+        service.start_command = Some(format!("dotnet run --project {}", target));
+        service.update_stack_from_framework();
+
+        // Tasks of the directory stay on the first service; launch profiles
+        // belong to their project
+        if !services.is_empty() {
+            service.tasks.clear();
+        }
+        if let Some(settings) = &launch_settings {
+            service
+                .tasks
+                .extend(launch_profile_tasks(settings, &project.project_file));
+        }
+
+        services.push(service);
+    }
+
+    services
+}
+
+/// The quoted project file for `--project`, plus `--framework` for
+/// multi-targeted projects which `dotnet run` refuses to start otherwise
+fn project_target(project: &DotnetProject) -> String {
+    match project.csproj.target_frameworks.as_slice() {
+        [_, _, ..] => format!(
+            "\"{}\" --framework {}",
+            project.project_file,
+            project.csproj.get_target_framework().unwrap_or_default()
+        ),
+        _ => format!("\"{}\"", project.project_file),
+    }
+}
+
+/// Launch profiles as runnable tasks
+fn launch_profile_tasks(settings: &LaunchSettings, project_file: &str) -> Vec<DetectedTask> {
+    settings
+        .project_profiles()
+        .into_iter()
+        .map(|(name, profile)| DetectedTask {
+            name: name.clone(),
+            description: profile.application_url.clone(),
+            source: TaskSource::LaunchSettings,
+            command: TaskSource::LaunchSettings.run_command(&format!("{}:{}", project_file, name)),
+            steps: Vec::new(),
+        })
+        .collect()
+}
+
+fn framework_for_kind(kind: &DotnetProjectKind) -> Framework {
+    match kind {
+        DotnetProjectKind::Web => Framework::AspNetCore,
+        DotnetProjectKind::Blazor => Framework::Blazor,
+        _ => Framework::CSharp,
+    }
+}
+
+fn category_for_kind(kind: &DotnetProjectKind, dir: &Path) -> ServiceCategory {
+    match kind {
+        DotnetProjectKind::Web => ServiceCategory::Backend,
+        DotnetProjectKind::Blazor => {
+            if dir.join("Controllers").exists() {
+                ServiceCategory::Fullstack
+            } else {
+                ServiceCategory::Frontend
+            }
+        }
+        DotnetProjectKind::Worker => ServiceCategory::Worker,
+        _ => ServiceCategory::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_solution_commands_quote_project_paths() {
+        let fixture = Fixture::new(&[
+            (
+                "Shop.slnx",
+                r#"<Solution><Project Path="src/Shop Api/Shop Api.csproj" /></Solution>"#,
+            ),
+            (
+                "src/Shop Api/Shop Api.csproj",
+                r#"<Project Sdk="Microsoft.NET.Sdk.Web"><PropertyGroup><TargetFramework>net8.0</TargetFramework></PropertyGroup></Project>"#,
+            ),
+            (
+                "src/Shop Api/Properties/launchSettings.json",
+                r#"{ "profiles": { "http": { "commandName": "Project", "applicationUrl": "http://localhost:5080" } } }"#,
+            ),
+        ]);
+        let path = fixture.path();
+        let mut base = DetectedService::new(
+            "shop".to_string(),
+            path.to_string_lossy().to_string(),
+            String::new(),
+        );
+        base.framework = Framework::AspNetCore;
+
+        let services = detect_dotnet_services(path, &base);
+        assert_eq!(services.len(), 1);
+        let api = &services[0];
+        assert_eq!(api.port, Some(5080));
+        assert_eq!(
+            api.dev_command.as_deref(),
+            Some(r#"dotnet watch --project "src/Shop Api/Shop Api.csproj""#)
+        );
+        assert_eq!(
+            api.build_command.as_deref(),
+            Some(r#"dotnet build "src/Shop Api/Shop Api.csproj""#)
+        );
+        assert_eq!(
+            api.tasks[0].command,
+            r#"dotnet run --project "src/Shop Api/Shop Api.csproj" --launch-profile "http""#
+        );
+    }
+}
@@ -1,5 +1,11 @@
-use crate::models::detected_service::Framework;
-use crate::utils::parsers::{CargoToml, PackageJson};
+use crate::models::detected_service::{DetectionEvidence, Framework, FrameworkCandidate};
+use crate::utils::detectors::dotnet::detect_dotnet_framework;
+use crate::utils::detectors::jvm::detect_jvm_framework;
+use crate::utils::detectors::python::{find_manage_py, PythonProject};
+use crate::utils::detectors::rules::match_rule;
+use crate::utils::parsers::{
+    find_project_file, CargoToml, DetectionRule, DetectionRules, GoMod, PackageJson,
+};
 use std::fs;
 use std::path::Path;
 
@@ -21,36 +27,111 @@ use std::path::Path;
 //        (e.g. bare `package.json`, generic config files)
 //    1 – Corroborating signal: tips the scale when scores are otherwise equal
 
-struct Candidate {
+struct Candidate<'a> {
     framework: Framework,
     score: u32,
+    evidence: Vec<String>,
+    /// User-defined rule that produced the candidate
+    rule: Option<&'a DetectionRule>,
 }
 
-impl Candidate {
+impl Candidate<'_> {
     fn new(framework: Framework, score: u32) -> Self {
-        Self { framework, score }
+        Self {
+            framework,
+            score,
+            evidence: Vec::new(),
+            rule: None,
+        }
+    }
+
+    /// Record a file or dependency that contributed to the score
+    fn because(mut self, evidence: impl Into<String>) -> Self {
+        self.evidence.push(evidence.into());
+        self
+    }
+
+    fn because_if(self, condition: bool, evidence: impl Into<String>) -> Self {
+        if condition {
+            self.because(evidence)
+        } else {
+            self
+        }
     }
 }
 
-fn c(framework: Framework, score: u32) -> Candidate {
+fn c(framework: Framework, score: u32) -> Candidate<'static> {
     Candidate::new(framework, score)
 }
 
+/// Scores at or above this are backed by an exclusive marker
+const CONFIDENT_SCORE: u32 = 10;
+
 // ---------------------------------------------------------------------------
-// Public entry point
+// Public entry points
 // ---------------------------------------------------------------------------
 
-pub fn detect_framework(
+/// Framework of a directory, with every candidate's score and the evidence
+/// behind it, and the user rule behind the winner.
+///
+/// Confidence is the winner's share of the two best scores, scaled down when
+/// the winner has no exclusive marker: a lone `main.py` is not proof.
+pub fn detect_framework_explained<'a>(
     path: &Path,
     package_json: Option<&PackageJson>,
     cargo_toml: Option<&CargoToml>,
-) -> Framework {
-    // Tauri is always unambiguous: src-tauri/ only exists there.
-    if path.join("src-tauri").exists()
-        || path.join("tauri.conf.json").exists()
-        || path.join("src-tauri/tauri.conf.json").exists()
-    {
-        return Framework::Tauri;
+    rules: &'a DetectionRules,
+) -> (DetectionEvidence, Option<&'a DetectionRule>) {
+    let mut candidates = score_candidates(path, package_json, cargo_toml, rules);
+
+    // Winner = highest score; zero means Unknown
+    candidates.retain(|c| c.score > 0);
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+
+    let (framework, confidence) = match candidates.as_slice() {
+        [] => (Framework::Unknown, 0.0),
+        [winner, rest @ ..] => {
+            let runner_up = rest
+                .iter()
+                .find(|c| c.framework != winner.framework)
+                .map(|c| c.score)
+                .unwrap_or(0);
+            let share = winner.score as f32 / (winner.score + runner_up) as f32;
+            let strength = (winner.score.min(CONFIDENT_SCORE) as f32) / CONFIDENT_SCORE as f32;
+            (winner.framework.clone(), share * strength)
+        }
+    };
+
+    let rule = candidates.first().and_then(|winner| winner.rule);
+    let evidence = DetectionEvidence {
+        framework,
+        confidence: (confidence * 100.0).round() / 100.0,
+        candidates: candidates
+            .into_iter()
+            .map(|c| FrameworkCandidate {
+                framework: c.framework,
+                rule: c.rule.map(|rule| rule.framework.clone()),
+                score: c.score,
+                evidence: c.evidence,
+            })
+            .collect(),
+    };
+    (evidence, rule)
+}
+
+fn score_candidates<'a>(
+    path: &Path,
+    package_json: Option<&PackageJson>,
+    cargo_toml: Option<&CargoToml>,
+    rules: &'a DetectionRules,
+) -> Vec<Candidate<'a>> {
+    // Tauri is always unambiguous: src-tauri/ only exists there. As the
+    // only candidate, an exclusive marker's score makes it certain.
+    let tauri_marker = ["src-tauri", "tauri.conf.json", "src-tauri/tauri.conf.json"]
+        .into_iter()
+        .find(|marker| path.join(marker).exists());
+    if let Some(marker) = tauri_marker {
+        return vec![c(Framework::Tauri, CONFIDENT_SCORE).because(marker)];
     }
 
     let mut candidates: Vec<Candidate> = vec![
@@ -71,561 +152,553 @@ pub fn detect_framework(
         candidates.extend(score_node_frameworks(pkg, path));
     }
 
-    // Winner = highest score; zero means Unknown
-    candidates.sort_by(|a, b| b.score.cmp(&a.score));
+    // User rules go first so they win ties against built-in scorers
+    candidates.splice(0..0, score_rules(path, package_json, cargo_toml, rules));
 
-    match candidates.into_iter().next() {
-        Some(c) if c.score > 0 => c.framework,
-        _ => Framework::Unknown,
-    }
+    candidates
+}
+
+/// One Candidate per matching user-defined rule
+fn score_rules<'a>(
+    path: &Path,
+    package_json: Option<&PackageJson>,
+    cargo_toml: Option<&CargoToml>,
+    rules: &'a DetectionRules,
+) -> Vec<Candidate<'a>> {
+    rules
+        .rules
+        .iter()
+        .filter_map(|rule| {
+            let evidence = match_rule(rule, path, package_json, cargo_toml)?;
+            Some(Candidate {
+                framework: rule.get_framework(),
+                score: rule.get_score(),
+                evidence,
+                rule: Some(rule),
+            })
+        })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
 // Per-ecosystem scorers – each returns a single Candidate
 // ---------------------------------------------------------------------------
 
-fn score_rust(path: &Path, cargo_toml: Option<&CargoToml>) -> Candidate {
+fn score_rust(path: &Path, cargo_toml: Option<&CargoToml>) -> Candidate<'static> {
     let has_toml = path.join("Cargo.toml").exists();
     if !has_toml {
         return c(Framework::Rust, 0);
     }
 
-    let lock_bonus: u32 = if path.join("Cargo.lock").exists() {
-        3
-    } else {
-        0
-    };
+    let has_lock = path.join("Cargo.lock").exists();
+    let lock_bonus: u32 = if has_lock { 3 } else { 0 };
 
     if let Some(ct) = cargo_toml {
-        if ct.has_dependency("actix-web") {
-            return c(Framework::ActixWeb, 10 + 7 + lock_bonus);
-        }
-        if ct.has_dependency("axum") {
-            return c(Framework::Axum, 10 + 7 + lock_bonus);
-        }
-        if ct.has_dependency("rocket") {
-            return c(Framework::Rocket, 10 + 7 + lock_bonus);
-        }
-        if ct.has_dependency("warp") {
-            return c(Framework::Warp, 10 + 7 + lock_bonus);
-        }
-        if ct.has_dependency("tide") {
-            return c(Framework::Tide, 10 + 7 + lock_bonus);
+        for (dep, framework) in [
+            ("actix-web", Framework::ActixWeb),
+            ("axum", Framework::Axum),
+            ("rocket", Framework::Rocket),
+            ("warp", Framework::Warp),
+            ("tide", Framework::Tide),
+        ] {
+            if ct.has_dependency(dep) {
+                return c(framework, 10 + 7 + lock_bonus)
+                    .because("Cargo.toml")
+                    .because(format!("Cargo.toml: {}", dep))
+                    .because_if(has_lock, "Cargo.lock");
+            }
         }
     }
 
     c(Framework::Rust, 10 + lock_bonus)
+        .because("Cargo.toml")
+        .because_if(has_lock, "Cargo.lock")
 }
 
-fn score_php(path: &Path) -> Candidate {
+fn score_php(path: &Path) -> Candidate<'static> {
+    let has_composer = path.join("composer.json").exists();
+    let has_lock = path.join("composer.lock").exists();
+
     // `artisan` is 100% Laravel-exclusive
     if path.join("artisan").exists() {
-        let bonus = if path.join("composer.json").exists() {
-            7
-        } else {
-            0
-        } + if path.join("composer.lock").exists() {
-            5
-        } else {
-            0
-        };
-        return c(Framework::Laravel, 10 + bonus);
+        let bonus = if has_composer { 7 } else { 0 } + if has_lock { 5 } else { 0 };
+        return c(Framework::Laravel, 10 + bonus)
+            .because("artisan")
+            .because_if(has_composer, "composer.json")
+            .because_if(has_lock, "composer.lock");
     }
 
     if path.join("symfony.lock").exists() {
-        return c(Framework::Symfony, 10 + 5);
+        return c(Framework::Symfony, 10 + 5).because("symfony.lock");
     }
     if path.join("bin/console").exists() {
-        return c(Framework::Symfony, 10);
+        return c(Framework::Symfony, 10).because("bin/console");
     }
     if path.join("wp-config.php").exists() {
-        return c(Framework::WordPress, 10);
+        return c(Framework::WordPress, 10).because("wp-config.php");
     }
     if path.join("wp-content").exists() {
-        return c(Framework::WordPress, 7);
+        return c(Framework::WordPress, 7).because("wp-content/");
     }
     if path.join("core/lib/Drupal.php").exists() {
-        return c(Framework::Drupal, 10);
+        return c(Framework::Drupal, 10).because("core/lib/Drupal.php");
     }
 
     // Inspect composer.json content
     if let Ok(content) = fs::read_to_string(path.join("composer.json")) {
         let low = content.to_lowercase();
-        let lock_bonus: u32 = if path.join("composer.lock").exists() {
-            5
-        } else {
-            0
-        };
+        let lock_bonus: u32 = if has_lock { 5 } else { 0 };
 
-        let framework = if low.contains("laravel/framework") {
-            Framework::Laravel
+        let (framework, package) = if low.contains("laravel/framework") {
+            (Framework::Laravel, Some("laravel/framework"))
         } else if low.contains("symfony/framework-bundle") {
-            Framework::Symfony
+            (Framework::Symfony, Some("symfony/framework-bundle"))
         } else if low.contains("codeigniter") {
-            Framework::CodeIgniter
+            (Framework::CodeIgniter, Some("codeigniter"))
         } else if low.contains("yiisoft/yii2") {
-            Framework::Yii
+            (Framework::Yii, Some("yiisoft/yii2"))
         } else if low.contains("cakephp/cakephp") {
-            Framework::CakePHP
+            (Framework::CakePHP, Some("cakephp/cakephp"))
         } else {
-            Framework::Php
+            (Framework::Php, None)
         };
 
-        return c(framework, 7 + lock_bonus);
+        let candidate = c(framework, 7 + lock_bonus).because("composer.json");
+        let candidate = match package {
+            Some(package) => candidate.because(format!("composer.json: {}", package)),
+            None => candidate,
+        };
+        return candidate.because_if(has_lock, "composer.lock");
     }
 
     // Bare composer.lock without a readable composer.json
-    if path.join("composer.lock").exists() {
-        return c(Framework::Php, 7);
+    if has_lock {
+        return c(Framework::Php, 7).because("composer.lock");
     }
 
     c(Framework::Php, 0)
 }
 
-fn score_python(path: &Path) -> Candidate {
+fn score_python(path: &Path) -> Candidate<'static> {
     // manage.py is Django-exclusive
     if path.join("manage.py").exists() {
-        return c(Framework::Django, 10 + 3);
+        return c(Framework::Django, 10 + 3).because("manage.py");
     }
 
-    if let Ok(content) = fs::read_to_string(path.join("requirements.txt")) {
-        let low = content.to_lowercase();
-        let framework = if low.contains("django") {
-            Framework::Django
-        } else if low.contains("flask") {
-            Framework::Flask
-        } else if low.contains("fastapi") {
-            Framework::FastApi
-        } else if low.contains("pyramid") {
-            Framework::Pyramid
-        } else if low.contains("tornado") {
-            Framework::Tornado
-        } else {
-            Framework::Python
-        };
-        return c(framework, 10);
+    let project = PythonProject::load(path);
+
+    if project.has_manifest() {
+        let framework = project.detect_framework();
+        let manifests = [
+            (project.pyproject.is_some(), "pyproject.toml"),
+            (project.pipfile.is_some(), "Pipfile"),
+            (project.requirements.is_some(), "requirements.txt"),
+        ];
+        let mut candidate = manifests
+            .iter()
+            .fold(c(framework.clone(), 10), |candidate, (present, file)| {
+                candidate.because_if(*present, *file)
+            });
+        if framework != Framework::Python {
+            candidate = candidate.because(format!(
+                "dependency: {}",
+                framework_dependency_name(&framework)
+            ));
+        }
+
+        // Django projects often keep manage.py one level down (`src/`,
+        // `backend/`); the manifest confirms it belongs to this directory.
+        if framework == Framework::Django {
+            if let Some(manage_py) = find_manage_py(path) {
+                candidate.score += 3;
+                return candidate.because(manage_py);
+            }
+        }
+
+        return candidate;
     }
 
-    if let Ok(content) = fs::read_to_string(path.join("pyproject.toml")) {
-        let low = content.to_lowercase();
-        let framework = if low.contains("django") {
-            Framework::Django
-        } else if low.contains("flask") {
-            Framework::Flask
-        } else if low.contains("fastapi") {
-            Framework::FastApi
-        } else {
-            Framework::Python
-        };
-        return c(framework, 10);
+    let generic = ["app.py", "main.py", "setup.py", "Pipfile.lock"]
+        .into_iter()
+        .find(|file| path.join(file).exists());
+
+    if let Some(file) = generic {
+        return c(Framework::Python, 7).because(file);
+    }
+
+    // A nested manage.py without any manifest here is only a weak hint: the
+    // directory may just be a polyglot repo root.
+    if let Some(manage_py) = find_manage_py(path) {
+        return c(Framework::Django, 5).because(manage_py);
     }
 
-    let generic = path.join("app.py").exists()
-        || path.join("main.py").exists()
-        || path.join("setup.py").exists()
-        || path.join("Pipfile").exists()
-        || path.join("Pipfile.lock").exists();
+    c(Framework::Python, 0)
+}
 
-    c(Framework::Python, if generic { 7 } else { 0 })
+/// Dependency that identifies a Python framework in a manifest
+fn framework_dependency_name(framework: &Framework) -> &'static str {
+    match framework {
+        Framework::Django => "django",
+        Framework::FastApi => "fastapi",
+        Framework::Flask => "flask",
+        Framework::Pyramid => "pyramid",
+        Framework::Tornado => "tornado",
+        _ => "python",
+    }
 }
 
-fn score_ruby(path: &Path) -> Candidate {
-    if path.join("config/application.rb").exists() || path.join("bin/rails").exists() {
-        return c(Framework::Rails, 10 + 5);
+fn score_ruby(path: &Path) -> Candidate<'static> {
+    for marker in ["config/application.rb", "bin/rails"] {
+        if path.join(marker).exists() {
+            return c(Framework::Rails, 10 + 5).because(marker);
+        }
     }
 
+    let has_lock = path.join("Gemfile.lock").exists();
+
     if let Ok(content) = fs::read_to_string(path.join("Gemfile")) {
         let low = content.to_lowercase();
-        let lock_bonus: u32 = if path.join("Gemfile.lock").exists() {
-            3
-        } else {
-            0
-        };
-        let framework = if low.contains("rails") {
-            Framework::Rails
+        let lock_bonus: u32 = if has_lock { 3 } else { 0 };
+        let (framework, gem) = if low.contains("rails") {
+            (Framework::Rails, Some("rails"))
         } else if low.contains("sinatra") {
-            Framework::Sinatra
+            (Framework::Sinatra, Some("sinatra"))
         } else if low.contains("hanami") {
-            Framework::Hanami
+            (Framework::Hanami, Some("hanami"))
         } else {
-            Framework::Ruby
+            (Framework::Ruby, None)
+        };
+
+        let candidate = c(framework, 10 + lock_bonus).because("Gemfile");
+        let candidate = match gem {
+            Some(gem) => candidate.because(format!("Gemfile: {}", gem)),
+            None => candidate,
         };
-        return c(framework, 10 + lock_bonus);
+        return candidate.because_if(has_lock, "Gemfile.lock");
     }
 
-    c(
-        Framework::Ruby,
-        if path.join("Gemfile.lock").exists() {
-            7
-        } else {
-            0
-        },
-    )
+    if has_lock {
+        return c(Framework::Ruby, 7).because("Gemfile.lock");
+    }
+
+    c(Framework::Ruby, 0)
 }
 
-fn score_go(path: &Path) -> Candidate {
-    if let Ok(content) = fs::read_to_string(path.join("go.mod")) {
-        let low = content.to_lowercase();
-        let sum_bonus: u32 = if path.join("go.sum").exists() { 3 } else { 0 };
-        let framework = if low.contains("github.com/gin-gonic/gin") {
-            Framework::Gin
-        } else if low.contains("github.com/labstack/echo") {
-            Framework::Echo
-        } else if low.contains("github.com/gofiber/fiber") {
-            Framework::Fiber
-        } else if low.contains("github.com/go-chi/chi") {
-            Framework::Chi
-        } else if low.contains("github.com/beego/beego") {
-            Framework::Beego
-        } else {
-            Framework::Go
+fn score_go(path: &Path) -> Candidate<'static> {
+    let has_sum = path.join("go.sum").exists();
+
+    if let Some(go_mod) = GoMod::parse(path) {
+        let sum_bonus: u32 = if has_sum { 3 } else { 0 };
+        let modules = [
+            ("github.com/gin-gonic/gin", Framework::Gin),
+            ("github.com/labstack/echo", Framework::Echo),
+            ("github.com/gofiber/fiber", Framework::Fiber),
+            ("github.com/go-chi/chi", Framework::Chi),
+            ("github.com/beego/beego", Framework::Beego),
+            ("github.com/astaxie/beego", Framework::Beego),
+        ];
+
+        let candidate = match modules.iter().find(|(m, _)| go_mod.has_dependency(m)) {
+            Some((module, framework)) => c(framework.clone(), 10 + sum_bonus)
+                .because("go.mod")
+                .because(format!("go.mod: {}", module)),
+            None => c(Framework::Go, 10 + sum_bonus).because("go.mod"),
         };
-        return c(framework, 10 + sum_bonus);
+        return candidate.because_if(has_sum, "go.sum");
     }
 
-    c(
-        Framework::Go,
-        if path.join("go.sum").exists() { 7 } else { 0 },
-    )
+    if has_sum {
+        return c(Framework::Go, 7).because("go.sum");
+    }
+
+    c(Framework::Go, 0)
 }
 
-fn score_java(path: &Path) -> Candidate {
-    if path.join("pom.xml").exists() {
-        let framework = fs::read_to_string(path.join("pom.xml"))
-            .map(|ct| {
-                if ct.contains("spring-boot") {
-                    Framework::Spring
-                } else if ct.contains("quarkus") {
-                    Framework::Quarkus
-                } else if ct.contains("micronaut") {
-                    Framework::Micronaut
-                } else {
-                    Framework::Java
-                }
-            })
-            .unwrap_or(Framework::Java);
-        return c(framework, 10);
-    }
-
-    for gradle_file in ["build.gradle", "build.gradle.kts"] {
-        if path.join(gradle_file).exists() {
-            let framework = fs::read_to_string(path.join(gradle_file))
-                .map(|ct| {
-                    if ct.contains("spring-boot") || ct.contains("org.springframework") {
-                        Framework::Spring
-                    } else if ct.contains("quarkus") {
-                        Framework::Quarkus
-                    } else if ct.contains("micronaut") {
-                        Framework::Micronaut
-                    } else if ct.contains("io.ktor") {
-                        Framework::Ktor
-                    } else if gradle_file.ends_with(".kts") {
-                        Framework::Kotlin
-                    } else {
-                        Framework::Java
-                    }
-                })
-                .unwrap_or(Framework::Java);
-            return c(framework, 10);
-        }
-    }
+fn score_java(path: &Path) -> Candidate<'static> {
+    let build_file = ["pom.xml", "build.gradle.kts", "build.gradle"]
+        .into_iter()
+        .find(|file| path.join(file).exists())
+        .unwrap_or("settings.gradle");
 
-    c(Framework::Java, 0)
+    match detect_jvm_framework(path) {
+        Some(framework) => c(framework, 10).because(build_file),
+        None => c(Framework::Java, 0),
+    }
 }
 
-fn score_dotnet(path: &Path) -> Candidate {
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name = name.to_string_lossy();
-
-            if name.ends_with(".csproj") {
-                let framework = fs::read_to_string(entry.path())
-                    .map(|ct| {
-                        if ct.contains("Microsoft.NET.Sdk.Web") {
-                            Framework::AspNetCore
-                        } else if ct.contains("Microsoft.NET.Sdk.BlazorWebAssembly")
-                            || ct.contains("Blazor")
-                        {
-                            Framework::Blazor
-                        } else {
-                            Framework::CSharp
-                        }
-                    })
-                    .unwrap_or(Framework::CSharp);
-                return c(framework, 10);
-            }
-            if name.ends_with(".sln") {
-                return c(Framework::CSharp, 10);
-            }
-        }
+fn score_dotnet(path: &Path) -> Candidate<'static> {
+    let project_file = find_project_file(path)
+        .or_else(|| {
+            fs::read_dir(path)
+                .ok()?
+                .flatten()
+                .map(|e| e.path())
+                .find(|p| {
+                    p.extension()
+                        .is_some_and(|ext| ext == "sln" || ext == "slnx")
+                })
+        })
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+
+    match (detect_dotnet_framework(path), project_file) {
+        (Some(framework), Some(file)) => c(framework, 10).because(file),
+        (Some(framework), None) => c(framework, 10),
+        (None, _) => c(Framework::CSharp, 0),
     }
-    c(Framework::CSharp, 0)
 }
 
-fn score_elixir(path: &Path) -> Candidate {
-    let mix_exs: u32 = if path.join("mix.exs").exists() { 10 } else { 0 };
-    let mix_lock: u32 = if path.join("mix.lock").exists() { 5 } else { 0 };
+fn score_elixir(path: &Path) -> Candidate<'static> {
+    let has_mix = path.join("mix.exs").exists();
+    let has_lock = path.join("mix.lock").exists();
+    let mix_exs: u32 = if has_mix { 10 } else { 0 };
+    let mix_lock: u32 = if has_lock { 5 } else { 0 };
     c(Framework::Elixir, mix_exs + mix_lock)
+        .because_if(has_mix, "mix.exs")
+        .because_if(has_lock, "mix.lock")
 }
 
-fn score_deno(path: &Path) -> Candidate {
-    let score = if path.join("deno.json").exists() || path.join("deno.jsonc").exists() {
-        10
-    } else {
-        0
-    };
-    c(Framework::Deno, score)
+fn score_deno(path: &Path) -> Candidate<'static> {
+    match ["deno.json", "deno.jsonc"]
+        .into_iter()
+        .find(|file| path.join(file).exists())
+    {
+        Some(file) => c(Framework::Deno, 10).because(file),
+        None => c(Framework::Deno, 0),
+    }
 }
 
 /// Generic Node.js presence (no specific framework identified yet).
 /// Kept intentionally low so specific framework candidates from
 /// `score_node_frameworks` always outweigh this fallback.
-fn score_node(path: &Path) -> Candidate {
-    let has_pkg: u32 = if path.join("package.json").exists() {
-        3
-    } else {
-        0
-    };
-    let has_lock: u32 = if path.join("package-lock.json").exists()
-        || path.join("yarn.lock").exists()
-        || path.join("pnpm-lock.yaml").exists()
-        || path.join("bun.lock").exists()
-        || path.join("bun.lockb").exists()
-    {
-        5
-    } else {
-        0
-    };
-    c(Framework::Node, has_pkg + has_lock)
+fn score_node(path: &Path) -> Candidate<'static> {
+    let has_pkg = path.join("package.json").exists();
+    let lockfile = [
+        "package-lock.json",
+        "yarn.lock",
+        "pnpm-lock.yaml",
+        "bun.lock",
+        "bun.lockb",
+    ]
+    .into_iter()
+    .find(|file| path.join(file).exists());
+
+    let score = if has_pkg { 3 } else { 0 } + if lockfile.is_some() { 5 } else { 0 };
+    let candidate = c(Framework::Node, score).because_if(has_pkg, "package.json");
+    match lockfile {
+        Some(lockfile) => candidate.because(lockfile),
+        None => candidate,
+    }
 }
 
-/// Produces one Candidate per specific Node/JS framework detected from the
-/// package.json contents. These are merged into the global list so they
-/// compete head-to-head with non-JS ecosystems.
-fn score_node_frameworks(pkg: &PackageJson, path: &Path) -> Vec<Candidate> {
-    let mut out: Vec<Candidate> = Vec::new();
-
-    let mut maybe = |framework: Framework, score: u32| {
-        if score > 0 {
-            out.push(c(framework, score));
-        }
-    };
-
+/// Node/JS frameworks as (framework, score, packages, files): a package
+/// declared in package.json or a file beside it
+const NODE_FRAMEWORKS: &[(Framework, u32, &[&str], &[&str])] = &[
     // Meta-frameworks
-    maybe(
-        Framework::NextJs,
-        if pkg.has_dependency("next") { 10 } else { 0 },
-    );
-    maybe(
-        Framework::NuxtJs,
-        if pkg.has_dependency("nuxt") || pkg.has_dependency("nuxt3") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
+    (Framework::NextJs, 10, &["next"], &[]),
+    (Framework::NuxtJs, 10, &["nuxt", "nuxt3"], &[]),
+    (
         Framework::Remix,
-        if pkg.has_dependency("@remix-run/react") || pkg.has_dependency("@remix-run/node") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Astro,
-        if pkg.has_dependency("astro") { 10 } else { 0 },
-    );
-    maybe(
-        Framework::Gatsby,
-        if pkg.has_dependency("gatsby") { 10 } else { 0 },
-    );
-    maybe(
-        Framework::SvelteKit,
-        if pkg.has_dependency("@sveltejs/kit") {
-            10
-        } else {
-            0
-        },
-    );
-
+        10,
+        &["@remix-run/react", "@remix-run/node"],
+        &[],
+    ),
+    (Framework::Astro, 10, &["astro"], &[]),
+    (Framework::Gatsby, 10, &["gatsby"], &[]),
+    (Framework::SvelteKit, 10, &["@sveltejs/kit"], &[]),
     // Desktop
-    maybe(
+    (
         Framework::Electron,
-        if pkg.has_dependency("electron") || pkg.has_dependency("electron-builder") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
+        10,
+        &["electron", "electron-builder"],
+        &[],
+    ),
+    (
         Framework::Neutralino,
-        if pkg.has_dependency("@aspect/neutralino") || path.join("neutralino.config.json").exists()
-        {
-            10
-        } else {
-            0
-        },
-    );
-
+        10,
+        &["@aspect/neutralino"],
+        &["neutralino.config.json"],
+    ),
     // Mobile
-    maybe(
-        Framework::ReactNative,
-        if pkg.has_dependency("react-native") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Expo,
-        if pkg.has_dependency("expo") { 10 } else { 0 },
-    );
-    maybe(
+    (Framework::ReactNative, 10, &["react-native"], &[]),
+    (Framework::Expo, 10, &["expo"], &[]),
+    (
         Framework::Ionic,
-        if pkg.has_dependency("@ionic/core")
-            || pkg.has_dependency("@ionic/react")
-            || pkg.has_dependency("@ionic/vue")
-            || pkg.has_dependency("@ionic/angular")
-        {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Capacitor,
-        if pkg.has_dependency("@capacitor/core") {
-            10
-        } else {
-            0
-        },
-    );
-
+        10,
+        &[
+            "@ionic/core",
+            "@ionic/react",
+            "@ionic/vue",
+            "@ionic/angular",
+        ],
+        &[],
+    ),
+    (Framework::Capacitor, 10, &["@capacitor/core"], &[]),
     // Backend Node
-    maybe(
-        Framework::NestJs,
-        if pkg.has_dependency("@nestjs/core") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::AdonisJs,
-        if pkg.has_dependency("@adonisjs/core") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Strapi,
-        if pkg.has_dependency("strapi") || pkg.has_dependency("@strapi/strapi") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Fastify,
-        if pkg.has_dependency("fastify") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Koa,
-        if pkg.has_dependency("koa") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Hapi,
-        if pkg.has_dependency("@hapi/hapi") || pkg.has_dependency("hapi") {
-            9
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Express,
-        if pkg.has_dependency("express") { 8 } else { 0 },
-    );
-
+    (Framework::NestJs, 10, &["@nestjs/core"], &[]),
+    (Framework::AdonisJs, 10, &["@adonisjs/core"], &[]),
+    (Framework::Strapi, 10, &["strapi", "@strapi/strapi"], &[]),
+    (Framework::Fastify, 9, &["fastify"], &[]),
+    (Framework::Koa, 9, &["koa"], &[]),
+    (Framework::Hapi, 9, &["@hapi/hapi", "hapi"], &[]),
+    (Framework::Express, 8, &["express"], &[]),
     // Frontend
-    maybe(
-        Framework::Angular,
-        if pkg.has_dependency("@angular/core") {
-            10
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::Vue,
-        if pkg.has_dependency("vue") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Svelte,
-        if pkg.has_dependency("svelte") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Solid,
-        if pkg.has_dependency("solid-js") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Preact,
-        if pkg.has_dependency("preact") { 9 } else { 0 },
-    );
-    maybe(
-        Framework::Qwik,
-        if pkg.has_dependency("@builder.io/qwik") {
-            9
-        } else {
-            0
-        },
-    );
-    maybe(
-        Framework::React,
-        if pkg.has_dependency("react") || pkg.has_dependency("react-dom") {
-            8
-        } else {
-            0
-        },
-    );
-
+    (Framework::Angular, 10, &["@angular/core"], &[]),
+    (Framework::Vue, 9, &["vue"], &[]),
+    (Framework::Svelte, 9, &["svelte"], &[]),
+    (Framework::Solid, 9, &["solid-js"], &[]),
+    (Framework::Preact, 9, &["preact"], &[]),
+    (Framework::Qwik, 9, &["@builder.io/qwik"], &[]),
+    (Framework::React, 8, &["react", "react-dom"], &[]),
     // Build tools (low score – often appear alongside a real framework dep)
-    let has_vite = pkg.has_dependency("vite")
-        || path.join("vite.config.ts").exists()
-        || path.join("vite.config.js").exists();
-    maybe(Framework::Vite, if has_vite { 5 } else { 0 });
-    maybe(
-        Framework::Webpack,
-        if pkg.has_dependency("webpack") { 5 } else { 0 },
-    );
-    maybe(
-        Framework::Parcel,
-        if pkg.has_dependency("parcel") { 5 } else { 0 },
-    );
-    maybe(
-        Framework::Esbuild,
-        if pkg.has_dependency("esbuild") { 5 } else { 0 },
-    );
-    maybe(
+    (
+        Framework::Vite,
+        5,
+        &["vite"],
+        &["vite.config.ts", "vite.config.js"],
+    ),
+    (Framework::Webpack, 5, &["webpack"], &[]),
+    (Framework::Parcel, 5, &["parcel"], &[]),
+    (Framework::Esbuild, 5, &["esbuild"], &[]),
+    (
         Framework::Turbopack,
-        if pkg.has_dependency("turbopack") || pkg.has_dependency("@vercel/turbopack") {
-            5
-        } else {
-            0
-        },
-    );
-
+        5,
+        &["turbopack", "@vercel/turbopack"],
+        &[],
+    ),
     // Bun runtime
-    maybe(
-        Framework::Bun,
-        if path.join("bun.lock").exists() || path.join("bun.lockb").exists() {
-            8
-        } else {
-            0
-        },
-    );
+    (Framework::Bun, 8, &[], &["bun.lock", "bun.lockb"]),
+];
+
+/// Produces one Candidate per specific Node/JS framework detected from the
+/// package.json contents. These are merged into the global list so they
+/// compete head-to-head with non-JS ecosystems.
+fn score_node_frameworks(pkg: &PackageJson, path: &Path) -> Vec<Candidate<'static>> {
+    NODE_FRAMEWORKS
+        .iter()
+        .filter_map(|(framework, score, packages, files)| {
+            // Evidence names the package or file that actually matched
+            let evidence = packages
+                .iter()
+                .find(|package| pkg.has_dependency(package))
+                .map(|package| format!("package.json: {}", package))
+                .or_else(|| {
+                    files
+                        .iter()
+                        .find(|file| path.join(file).exists())
+                        .map(|file| file.to_string())
+                })?;
+            Some(c(framework.clone(), *score).because(evidence))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    fn explain(fixture: &Fixture) -> DetectionEvidence {
+        let package_json = PackageJson::parse(fixture.path());
+        let rules = DetectionRules::default();
+        detect_framework_explained(fixture.path(), package_json.as_ref(), None, &rules).0
+    }
+
+    #[test]
+    fn test_detect_framework_explained() {
+        let empty = Fixture::new(&[]);
+        let evidence = explain(&empty);
+        assert_eq!(evidence.framework, Framework::Unknown);
+        assert_eq!(evidence.confidence, 0.0);
+        assert!(evidence.candidates.is_empty());
+
+        // Tauri is the only candidate and certain
+        let tauri = Fixture::new(&[
+            ("package.json", r#"{"dependencies": {"react": "^18"}}"#),
+            ("src-tauri/Cargo.toml", "[package]\nname = \"app\"\n"),
+        ]);
+        let evidence = explain(&tauri);
+        assert_eq!(evidence.framework, Framework::Tauri);
+        assert_eq!(evidence.confidence, 1.0);
+        assert_eq!(evidence.candidates.len(), 1);
+        assert_eq!(evidence.candidates[0].evidence, vec!["src-tauri"]);
+
+        // Evidence names the package that matched, not every alternative
+        let nuxt = Fixture::new(&[
+            ("package.json", r#"{"dependencies": {"nuxt3": "^3"}}"#),
+            ("yarn.lock", ""),
+        ]);
+        let evidence = explain(&nuxt);
+        assert_eq!(evidence.framework, Framework::NuxtJs);
+        assert_eq!(evidence.candidates[0].evidence, vec!["package.json: nuxt3"]);
+        let node = evidence
+            .candidates
+            .iter()
+            .find(|c| c.framework == Framework::Node)
+            .unwrap();
+        assert_eq!(node.evidence, vec!["package.json", "yarn.lock"]);
+
+        // A build tool close behind leaves doubt
+        let express = Fixture::new(&[
+            ("package.json", r#"{"dependencies": {"express": "^4"}}"#),
+            ("vite.config.ts", ""),
+        ]);
+        let evidence = explain(&express);
+        assert_eq!(evidence.framework, Framework::Express);
+        assert_eq!(evidence.confidence, 0.49);
+        let vite = evidence
+            .candidates
+            .iter()
+            .find(|c| c.framework == Framework::Vite)
+            .unwrap();
+        assert_eq!(vite.evidence, vec!["vite.config.ts"]);
+
+        // An exclusive marker with its manifests beats the rest outright
+        let laravel = Fixture::new(&[
+            ("artisan", ""),
+            ("composer.json", "{}"),
+            ("package.json", "{}"),
+        ]);
+        let evidence = explain(&laravel);
+        assert_eq!(evidence.framework, Framework::Laravel);
+        assert_eq!(evidence.candidates[0].score, 17);
+        assert_eq!(
+            evidence.candidates[0].evidence,
+            vec!["artisan", "composer.json"]
+        );
+        assert_eq!(evidence.confidence, 0.85);
+
+        // A lone entry file is not proof
+        let script = Fixture::new(&[("main.py", "print('hi')\n")]);
+        let evidence = explain(&script);
+        assert_eq!(evidence.framework, Framework::Python);
+        assert_eq!(evidence.confidence, 0.7);
+    }
 
-    out
+    #[test]
+    fn test_matched_rule_is_returned() {
+        let fixture = Fixture::new(&[("gateway.yml", "routes: []\n")]);
+        // A repository rule and a global one sharing a label; only the
+        // second matches
+        let rules = DetectionRules::parse_str(
+            r#"
+[[rule]]
+framework = "Gateway"
+port = 7070
+[rule.match]
+files = ["gateway.toml"]
+
+[[rule]]
+framework = "Gateway"
+port = 9090
+[rule.match]
+files = ["gateway.yml"]
+"#,
+        )
+        .unwrap();
+
+        let (evidence, rule) = detect_framework_explained(fixture.path(), None, None, &rules);
+        assert_eq!(evidence.framework, Framework::Custom);
+        assert_eq!(evidence.candidates[0].rule.as_deref(), Some("Gateway"));
+        assert_eq!(rule.and_then(|rule| rule.port), Some(9090));
+    }
 }
@@ -0,0 +1,208 @@
+use crate::models::detected_service::{DetectedService, Framework, ServiceCategory};
+use crate::utils::detectors::task_runner::binary_category;
+use crate::utils::parsers::AirToml;
+use regex::Regex;
+use std::env::consts::OS;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PACKAGE_MAIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^package\s+main\b").unwrap());
+
+/// `http.ListenAndServe(":8080", ...)`, `r.Run(":8080")`, `e.Start(":1323")`,
+/// `app.Listen(":3000")`, `Addr: ":8080"`
+static GO_LISTEN_PORT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:ListenAndServe(?:TLS)?|Listen|Run|Start|Addr\s*:)\s*\(?\s*"[^"\s]*:(\d{2,5})""#,
+    )
+    .unwrap()
+});
+
+/// A `package main` found in the module
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoBinary {
+    pub name: String,
+    /// Package path relative to the module root (`./cmd/api`)
+    pub package: String,
+}
+
+/// Find every `package main` under `cmd/*`
+pub fn find_go_binaries(path: &Path) -> Vec<GoBinary> {
+    let Ok(entries) = fs::read_dir(path.join("cmd")) else {
+        return Vec::new();
+    };
+
+    let mut binaries: Vec<GoBinary> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir() && is_main_package(&e.path()))
+        .map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            GoBinary {
+                package: format!("./cmd/{}", name),
+                name,
+            }
+        })
+        .collect();
+
+    binaries.sort_by(|a, b| a.name.cmp(&b.name));
+    binaries
+}
+
+/// Whether a module builds a binary, at its root or under `cmd/`
+pub fn is_runnable_go_module(path: &Path) -> bool {
+    is_main_package(path) || !find_go_binaries(path).is_empty()
+}
+
+/// Expand a Go module with a `cmd/` layout into one service per binary.
+/// Returns an empty list when the module has no binaries under `cmd/`.
+pub fn detect_go_services(path: &Path, base: &DetectedService) -> Vec<DetectedService> {
+    if !is_go_framework(&base.framework) {
+        return Vec::new();
+    }
+
+    let binaries = find_go_binaries(path);
+    if binaries.is_empty() {
+        return Vec::new();
+    }
+
+    // air cannot start with a broken config: fall back to `go run`
+    let air = AirToml::parse(path).and_then(Result::ok);
+    let mut services = Vec::new();
+
+    for binary in &binaries {
+        let mut service = base.clone();
+        service.name = binary.name.clone();
+        service.category = binary_category(&binary.name, &base.category);
+
+        // Only when the config rebuilds this binary; air's default build
+        // is the module root, which is none of them
+        let binary_air = air.as_ref().filter(|air| {
+            air.builds_package(&binary.package)
+                || air.builds_package(binary.package.trim_start_matches("./"))
+        });
+
+        service.dev_command = Some(match binary_air {
+            Some(air) => air.get_dev_command(),
+            None => format!("go run {}", binary.package),
+        });
+        service.build_command = Some(format!(
+            "go build -o {} {}",
+            go_binary_path(&binary.name),
+            binary.package
+        ));
+        service.start_command = Some(go_start_command(&binary.name));
+
+        let explicit_port = find_listen_port(&path.join(binary.package.trim_start_matches("./")));
+        service.port = match service.category {
+            ServiceCategory::Worker | ServiceCategory::Unknown => explicit_port,
+            _ => explicit_port.or(base.port),
+        };
+
+        // Tasks belong to the module, not to each binary: keep them once
+        if !services.is_empty() {
+            service.tasks.clear();
+        }
+
+        services.push(service);
+    }
+
+    services
+}
+
+pub fn is_go_framework(framework: &Framework) -> bool {
+    matches!(
+        framework,
+        Framework::Gin
+            | Framework::Echo
+            | Framework::Fiber
+            | Framework::Chi
+            | Framework::Beego
+            | Framework::Go
+    )
+}
+
+/// Check whether a directory holds a (non-test) `package main`
+fn is_main_package(dir: &Path) -> bool {
+    go_sources(dir).any(|content| PACKAGE_MAIN_REGEX.is_match(&content))
+}
+
+fn find_listen_port(dir: &Path) -> Option<u16> {
+    go_sources(dir).find_map(|content| {
+        GO_LISTEN_PORT_REGEX
+            .captures(&content)
+            .and_then(|c| c.get(1))
+            .and_then(|p| p.as_str().parse::<u16>().ok())
+    })
+}
+
+/// Contents of the buildable `.go` files of a directory
+fn go_sources(dir: &Path) -> impl Iterator<Item = String> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.extension().is_some_and(|ext| ext == "go")
+                && !p.to_string_lossy().ends_with("_test.go")
+        })
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter(|content| !content.contains("//go:build ignore"))
+}
+
+/// Returns `bin\<name>.exe` on Windows, `bin/<name>` on Unix.
+fn go_binary_path(name: &str) -> String {
+    if OS == "windows" {
+        format!("bin\\{}.exe", name)
+    } else {
+        format!("bin/{}", name)
+    }
+}
+
+fn go_start_command(name: &str) -> String {
+    if OS == "windows" {
+        go_binary_path(name)
+    } else {
+        format!("./{}", go_binary_path(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_air_only_runs_the_binary_it_builds() {
+        let fixture = Fixture::new(&[
+            ("go.mod", "module github.com/acme/shop\n"),
+            ("cmd/api/main.go", "package main\n"),
+            ("cmd/worker/main.go", "package main\n"),
+            (
+                ".air.toml",
+                "[build]\ncmd = \"go build -o ./tmp/api ./cmd/api\"\n",
+            ),
+        ]);
+        let mut base = DetectedService::new(
+            "shop".to_string(),
+            fixture.path().to_string_lossy().to_string(),
+            String::new(),
+        );
+        base.framework = Framework::Go;
+
+        let services = detect_go_services(fixture.path(), &base);
+        assert_eq!(services[0].dev_command.as_deref(), Some("air"));
+        assert_eq!(
+            services[1].dev_command.as_deref(),
+            Some("go run ./cmd/worker")
+        );
+
+        // The default air build is the module root, not the lone binary
+        fs::remove_dir_all(fixture.join("cmd/worker")).unwrap();
+        fixture.write(".air.toml", "[build]\ncmd = \"go build -o ./tmp/main .\"\n");
+        let services = detect_go_services(fixture.path(), &base);
+        assert_eq!(services[0].dev_command.as_deref(), Some("go run ./cmd/api"));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::config_store::install_state::{hash_file, recorded_lockfile_hash};
+use crate::models::detected_service::{InstallStatus, PackageManager};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Lockfiles and the files their package manager writes on every install,
+/// any of which counts
+const LOCKFILE_MARKERS: [(&str, &[&str]); 11] = [
+    ("pnpm-lock.yaml", &["node_modules/.modules.yaml"]),
+    ("package-lock.json", &["node_modules/.package-lock.json"]),
+    (
+        "yarn.lock",
+        &[".yarn/install-state.gz", "node_modules/.yarn-integrity"],
+    ),
+    ("bun.lock", &["node_modules"]),
+    ("bun.lockb", &["node_modules"]),
+    ("composer.lock", &["vendor/composer/installed.json"]),
+    ("Cargo.lock", &["target"]),
+    ("uv.lock", &[".venv"]),
+    ("poetry.lock", &[".venv"]),
+    ("pdm.lock", &[".venv"]),
+    ("Pipfile.lock", &[".venv"]),
+];
+
+/// Manifests installed without a lockfile, checked in the service itself
+const MANIFEST_MARKERS: [(&str, &str); 3] = [
+    ("package.json", "node_modules"),
+    ("requirements.txt", ".venv"),
+    ("composer.json", "vendor"),
+];
+
+/// An install writes the lockfile too, sometimes just after its marker
+const MTIME_SLACK: Duration = Duration::from_secs(2);
+
+/// Lockfiles written by a package manager
+fn lockfile_names(package_manager: &PackageManager) -> &'static [&'static str] {
+    match package_manager {
+        PackageManager::Npm => &["package-lock.json"],
+        PackageManager::Yarn | PackageManager::YarnBerry => &["yarn.lock"],
+        PackageManager::Pnpm => &["pnpm-lock.yaml"],
+        PackageManager::Bun => &["bun.lock", "bun.lockb"],
+        PackageManager::Composer => &["composer.lock"],
+        PackageManager::Cargo => &["Cargo.lock"],
+        PackageManager::Uv => &["uv.lock"],
+        PackageManager::Poetry => &["poetry.lock"],
+        PackageManager::Pdm => &["pdm.lock"],
+        PackageManager::Pipenv => &["Pipfile.lock"],
+        _ => &[],
+    }
+}
+
+/// Lockfile a service's dependencies are installed from: its own, or the
+/// workspace's up to the repository root. Only the service's own package
+/// manager counts, so a Go service in a pnpm repository has none.
+pub fn find_lockfile(path: &Path, package_manager: &PackageManager) -> Option<PathBuf> {
+    let names = lockfile_names(package_manager);
+    if names.is_empty() {
+        return None;
+    }
+
+    for dir in path.ancestors().take(5) {
+        if let Some(lockfile) = names.iter().find(|lockfile| dir.join(lockfile).is_file()) {
+            return Some(dir.join(lockfile));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Whether the dependencies of a service are installed, by comparing its
+/// lockfile with what the last install left behind
+pub fn detect_install_status(
+    path: &Path,
+    package_manager: &PackageManager,
+) -> Option<InstallStatus> {
+    if let Some(lockfile) = find_lockfile(path, package_manager) {
+        let root = lockfile.parent()?;
+        let name = lockfile.file_name()?.to_string_lossy();
+        let (_, markers) = LOCKFILE_MARKERS.iter().find(|(file, _)| *file == name)?;
+
+        let Some(installed_at) = markers.iter().find_map(|m| marker_modified(root, m)) else {
+            return Some(InstallStatus::Missing);
+        };
+        let changed_at = fs::metadata(&lockfile).and_then(|m| m.modified()).ok()?;
+
+        let unchanged = changed_at <= installed_at + MTIME_SLACK
+            || recorded_lockfile_hash(&lockfile)
+                .is_some_and(|hash| Some(hash) == hash_file(&lockfile));
+        return Some(if unchanged {
+            InstallStatus::Installed
+        } else {
+            InstallStatus::Stale
+        });
+    }
+
+    let (_, marker) = MANIFEST_MARKERS
+        .iter()
+        .find(|(manifest, _)| path.join(manifest).is_file())?;
+    Some(if marker_modified(path, marker).is_some() {
+        InstallStatus::Installed
+    } else {
+        InstallStatus::Missing
+    })
+}
+
+/// When a marker was last written. A virtualenv counts from its
+/// site-packages, which changes with each package installed.
+fn marker_modified(root: &Path, marker: &str) -> Option<SystemTime> {
+    let path = root.join(marker);
+    let path = if marker == ".venv" {
+        site_packages(&path).unwrap_or(path)
+    } else {
+        path
+    };
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// `lib/python3.x/site-packages`, or `Lib/site-packages` on Windows
+fn site_packages(venv: &Path) -> Option<PathBuf> {
+    let windows = venv.join("Lib").join("site-packages");
+    if windows.is_dir() {
+        return Some(windows);
+    }
+    fs::read_dir(venv.join("lib"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join("site-packages"))
+        .find(|path| path.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+    use std::fs::File;
+
+    #[test]
+    fn test_detect_install_status() {
+        let fixture = Fixture::new(&[
+            ("packages/web/package.json", "{}"),
+            ("pnpm-lock.yaml", "lockfileVersion: '9.0'\n"),
+        ]);
+        fixture.mkdir(".git");
+        let root = fixture.path();
+        let member = root.join("packages/web");
+
+        let pnpm = PackageManager::Pnpm;
+        assert_eq!(
+            find_lockfile(&member, &pnpm),
+            Some(root.join("pnpm-lock.yaml"))
+        );
+        assert_eq!(
+            detect_install_status(&member, &pnpm),
+            Some(InstallStatus::Missing)
+        );
+
+        // Other ecosystems don't install from the JavaScript lockfile
+        let api = fixture.write("services/api/go.mod", "module api\n");
+        let api = api.parent().unwrap();
+        assert_eq!(find_lockfile(api, &PackageManager::GoMod), None);
+        assert_eq!(detect_install_status(api, &PackageManager::GoMod), None);
+        assert_eq!(find_lockfile(&member, &PackageManager::Npm), None);
+
+        fs::create_dir_all(root.join("node_modules")).unwrap();
+        let marker = root.join("node_modules/.modules.yaml");
+        fs::write(&marker, "layoutVersion: 5\n").unwrap();
+        assert_eq!(
+            detect_install_status(&member, &pnpm),
+            Some(InstallStatus::Installed)
+        );
+
+        let earlier = SystemTime::now() - Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&marker)
+            .unwrap()
+            .set_modified(earlier)
+            .unwrap();
+        fs::write(
+            root.join("pnpm-lock.yaml"),
+            "lockfileVersion: '9.0'\n# changed\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect_install_status(&member, &pnpm),
+            Some(InstallStatus::Stale)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::models::detected_service::{Framework, PackageManager};
+use crate::utils::parsers::{GradleBuild, GradleSettings, PomXml};
+use std::env::consts::OS;
+use std::path::{Path, PathBuf};
+
+/// Maven plugins that make a module runnable on its own
+const RUNNABLE_MAVEN_PLUGINS: [&str; 5] = [
+    "spring-boot-maven-plugin",
+    "quarkus-maven-plugin",
+    "micronaut-maven-plugin",
+    "exec-maven-plugin",
+    "javafx-maven-plugin",
+];
+
+/// Gradle plugins that make a project runnable on its own
+const RUNNABLE_GRADLE_PLUGINS: [&str; 5] = [
+    "org.springframework.boot",
+    "io.quarkus",
+    "io.micronaut.application",
+    "io.ktor.plugin",
+    "application",
+];
+
+/// How far up a module may be nested below the build root
+const MAX_BUILD_DEPTH: usize = 5;
+
+/// The Maven or Gradle build a JVM module belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct JvmBuild {
+    pub tool: PackageManager,
+    /// Root of the (possibly multi-module) build
+    pub root: PathBuf,
+    /// Build executable relative to the module (`../mvnw`, `gradle`, ...)
+    pub executable: String,
+    /// Gradle project path (`:services:api`), empty for the root project
+    pub project_path: String,
+}
+
+impl JvmBuild {
+    /// Full command for a goal (Maven) or task (Gradle) of this module
+    pub fn command(&self, goal: &str) -> String {
+        if self.project_path.is_empty() {
+            format!("{} {}", self.executable, goal)
+        } else {
+            format!("{} {}:{}", self.executable, self.project_path, goal)
+        }
+    }
+}
+
+/// Locate the Maven or Gradle build of a module, walking up to the build root
+/// so nested modules use the root wrapper.
+pub fn locate_jvm_build(path: &Path) -> Option<JvmBuild> {
+    if path.join("pom.xml").exists() {
+        return Some(locate_maven_build(path));
+    }
+
+    if path.join("build.gradle").exists()
+        || path.join("build.gradle.kts").exists()
+        || has_gradle_settings(path)
+    {
+        return Some(locate_gradle_build(path));
+    }
+
+    None
+}
+
+fn locate_maven_build(path: &Path) -> JvmBuild {
+    let (depth, root) = path
+        .ancestors()
+        .take(MAX_BUILD_DEPTH + 1)
+        .enumerate()
+        .take_while(|(_, dir)| dir.join("pom.xml").exists())
+        .last()
+        .map(|(depth, dir)| (depth, dir.to_path_buf()))
+        .unwrap_or((0, path.to_path_buf()));
+
+    // Only the root's wrapper builds the whole reactor
+    JvmBuild {
+        tool: PackageManager::Maven,
+        executable: if root.join("mvnw").exists() || root.join("mvnw.cmd").exists() {
+            wrapper_command("mvnw", "mvnw.cmd", depth)
+        } else {
+            "mvn".to_string()
+        },
+        root,
+        project_path: String::new(),
+    }
+}
+
+fn locate_gradle_build(path: &Path) -> JvmBuild {
+    let (depth, root) = path
+        .ancestors()
+        .take(MAX_BUILD_DEPTH + 1)
+        .enumerate()
+        .find(|(_, dir)| has_gradle_settings(dir))
+        .map(|(depth, dir)| (depth, dir.to_path_buf()))
+        .unwrap_or((0, path.to_path_buf()));
+
+    let project_path = if depth == 0 {
+        String::new()
+    } else {
+        gradle_project_path(&root, path)
+    };
+
+    JvmBuild {
+        tool: PackageManager::Gradle,
+        executable: if root.join("gradlew").exists() || root.join("gradlew.bat").exists() {
+            wrapper_command("gradlew", "gradlew.bat", depth)
+        } else {
+            "gradle".to_string()
+        },
+        root,
+        project_path,
+    }
+}
+
+/// Gradle project path of a directory, honouring `projectDir` overrides
+fn gradle_project_path(root: &Path, path: &Path) -> String {
+    if let Some(settings) = GradleSettings::parse(root) {
+        if let Some(project) = settings
+            .includes
+            .iter()
+            .find(|p| settings.project_dir(root, p) == path)
+        {
+            return project.clone();
+        }
+    }
+
+    path.strip_prefix(root)
+        .map(|relative| {
+            relative
+                .components()
+                .map(|c| format!(":{}", c.as_os_str().to_string_lossy()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns `./mvnw` / `../../mvnw` on Unix, `mvnw.cmd` / `..\..\mvnw.cmd` on
+/// Windows.
+fn wrapper_command(unix: &str, windows: &str, depth: usize) -> String {
+    if OS == "windows" {
+        format!("{}{}", "..\\".repeat(depth), windows)
+    } else if depth == 0 {
+        format!("./{}", unix)
+    } else {
+        format!("{}{}", "../".repeat(depth), unix)
+    }
+}
+
+fn has_gradle_settings(path: &Path) -> bool {
+    path.join("settings.gradle").exists() || path.join("settings.gradle.kts").exists()
+}
+
+/// Framework implied by a module's pom.xml or build.gradle
+pub fn detect_jvm_framework(path: &Path) -> Option<Framework> {
+    if let Some(pom) = PomXml::parse(path) {
+        let framework = if pom.has_group("org.springframework.boot") {
+            Framework::Spring
+        } else if pom.has_group("io.quarkus") {
+            Framework::Quarkus
+        } else if pom.has_group("io.micronaut") {
+            Framework::Micronaut
+        } else if pom.has_group("io.ktor") {
+            Framework::Ktor
+        } else {
+            Framework::Java
+        };
+        return Some(framework);
+    }
+
+    let gradle = GradleBuild::parse(path)?;
+    let framework = if gradle.has_plugin("org.springframework.boot")
+        || gradle.has_group("org.springframework")
+    {
+        Framework::Spring
+    } else if gradle.has_plugin("io.quarkus") || gradle.has_group("io.quarkus") {
+        Framework::Quarkus
+    } else if gradle.has_plugin("io.micronaut") || gradle.has_group("io.micronaut") {
+        Framework::Micronaut
+    } else if gradle.has_plugin("io.ktor") || gradle.has_group("io.ktor") {
+        Framework::Ktor
+    } else if gradle.is_kotlin_dsl || gradle.has_plugin("org.jetbrains.kotlin") {
+        Framework::Kotlin
+    } else {
+        Framework::Java
+    };
+    Some(framework)
+}
+
+/// Whether a module produces something that can be run (an application
+/// rather than a library)
+pub fn is_runnable_jvm_module(path: &Path) -> bool {
+    if let Some(pom) = PomXml::parse(path) {
+        return match pom.get_packaging() {
+            "war" => true,
+            "jar" => RUNNABLE_MAVEN_PLUGINS.iter().any(|p| pom.has_plugin(p)),
+            _ => false,
+        };
+    }
+
+    GradleBuild::parse(path).is_some_and(|gradle| {
+        RUNNABLE_GRADLE_PLUGINS
+            .iter()
+            .any(|plugin| gradle.plugins.iter().any(|p| p == plugin))
+    })
+}
+
+/// Every module of a multi-module Maven build, aggregators expanded
+pub fn maven_module_paths(path: &Path) -> Vec<PathBuf> {
+    let mut modules = Vec::new();
+    collect_maven_modules(path, 0, &mut modules);
+    modules
+}
+
+fn collect_maven_modules(path: &Path, depth: usize, modules: &mut Vec<PathBuf>) {
+    if depth >= MAX_BUILD_DEPTH {
+        return;
+    }
+
+    let Some(pom) = PomXml::parse(path) else {
+        return;
+    };
+
+    for module in &pom.modules {
+        // <module> may point at a pom file instead of a directory
+        let module_path = path.join(module.trim_end_matches("/pom.xml"));
+        if !module_path.join("pom.xml").exists() || modules.contains(&module_path) {
+            continue;
+        }
+
+        modules.push(module_path.clone());
+        collect_maven_modules(&module_path, depth + 1, modules);
+    }
+}
+
+/// Every included project of a multi-project Gradle build
+pub fn gradle_project_paths(path: &Path) -> Vec<PathBuf> {
+    let Some(settings) = GradleSettings::parse(path) else {
+        return Vec::new();
+    };
+
+    settings
+        .includes
+        .iter()
+        .map(|project| settings.project_dir(path, project))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_maven_wrapper_comes_from_build_root() {
+        let pom = "<project><packaging>jar</packaging></project>";
+        let fixture = Fixture::new(&[
+            (
+                "pom.xml",
+                "<project><modules><module>api</module></modules></project>",
+            ),
+            ("api/pom.xml", pom),
+            ("api/mvnw", ""),
+        ]);
+
+        // A wrapper inside a module does not build the reactor
+        let api = locate_jvm_build(&fixture.join("api")).unwrap();
+        assert_eq!(api.root, fixture.path());
+        assert_eq!(api.executable, "mvn");
+
+        fixture.write("mvnw", "");
+        let api = locate_jvm_build(&fixture.join("api")).unwrap();
+        if OS != "windows" {
+            assert_eq!(api.executable, "../mvnw");
+        }
+        assert_eq!(
+            api.command("package"),
+            format!("{} package", api.executable)
+        );
+    }
+}
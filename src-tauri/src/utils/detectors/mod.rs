@@ -1,15 +1,31 @@
 pub mod commands;
 pub mod docker;
+pub mod dotnet;
 pub mod framework;
+pub mod go;
+pub mod install_status;
+pub mod jvm;
 pub mod monorepo;
 pub mod package_manager;
 pub mod port;
+pub mod python;
+pub mod rules;
+pub mod rust;
 pub mod service_type;
+pub mod task_runner;
+pub mod toolchain;
 
 pub use commands::*;
 pub use docker::*;
+pub use dotnet::*;
 pub use framework::*;
+pub use go::*;
+pub use install_status::*;
 pub use monorepo::*;
 pub use package_manager::*;
 pub use port::*;
+pub use rules::*;
+pub use rust::*;
 pub use service_type::*;
+pub use task_runner::*;
+pub use toolchain::*;
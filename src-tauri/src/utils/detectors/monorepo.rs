@@ -1,5 +1,12 @@
-use crate::models::detected_service::MonorepoTool;
-use crate::utils::parsers::PackageJson;
+use crate::models::detected_service::{DetectedTask, MonorepoTool, PackageManager, TaskSource};
+use crate::utils::detectors::go::is_runnable_go_module;
+use crate::utils::detectors::jvm::{
+    gradle_project_paths, is_runnable_jvm_module, maven_module_paths,
+};
+use crate::utils::detectors::package_manager::detect_package_manager;
+use crate::utils::detectors::rust::is_runnable_crate;
+use crate::utils::parsers::{GoWork, NxProject, PackageJson, RushJson, TurboJson};
+use crate::utils::scan_ignore::ScanIgnore;
 use glob::glob;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -8,62 +15,159 @@ use std::path::{Path, PathBuf};
 #[derive(Debug, Clone)]
 pub struct MonorepoInfo {
     pub tool: MonorepoTool,
+    /// Directory holding the workspace definition
+    pub root: PathBuf,
     #[allow(dead_code)]
     pub workspace_patterns: Vec<String>,
+    /// Every member, including libraries that are not scanned as services
+    pub members: Vec<PathBuf>,
     pub workspace_paths: Vec<PathBuf>,
 }
 
-/// Detect if the project is a monorepo and which tool it uses
-pub fn detect_monorepo(path: &Path) -> Option<MonorepoInfo> {
+impl MonorepoInfo {
+    fn new(
+        tool: MonorepoTool,
+        root: &Path,
+        workspace_patterns: Vec<String>,
+        workspace_paths: Vec<PathBuf>,
+    ) -> Self {
+        Self {
+            tool,
+            root: root.to_path_buf(),
+            workspace_patterns,
+            members: workspace_paths.clone(),
+            workspace_paths,
+        }
+    }
+
+    /// Whether a directory belongs to this workspace system, either as a
+    /// member or anywhere inside a nested workspace root
+    pub fn covers(&self, path: &Path, project_root: &Path) -> bool {
+        (self.root != project_root && path.starts_with(&self.root))
+            || self.members.iter().any(|member| path.starts_with(member))
+    }
+}
+
+/// Detect every workspace system in a repository: several may sit side by
+/// side (a pnpm workspace in `web/`, a Cargo workspace in `server/`) and
+/// workspaces may nest, so directories are searched down to `max_depth`.
+pub fn detect_workspace_systems(
+    path: &Path,
+    max_depth: u8,
+    ignore: &ScanIgnore,
+) -> Vec<MonorepoInfo> {
+    let mut systems: Vec<MonorepoInfo> = detect_monorepo_tools(path)
+        .into_iter()
+        .map(|mut info| {
+            info.members.retain(|p| !ignore.is_ignored(p));
+            info.workspace_paths.retain(|p| !ignore.is_ignored(p));
+            info
+        })
+        .collect();
+
+    if max_depth == 0 {
+        return systems;
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return systems;
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.is_dir() && !ignore.is_ignored(dir))
+        .collect();
+    dirs.sort();
+
+    for dir in dirs {
+        for system in detect_workspace_systems(&dir, max_depth - 1, ignore) {
+            // A nested Maven aggregator or Gradle build is already part of
+            // the build that lists it
+            let listed = systems.iter().any(|found| {
+                found.tool == system.tool
+                    && (found.root == system.root
+                        || found.members.iter().any(|m| system.root.starts_with(m)))
+            });
+            if !listed {
+                systems.push(system);
+            }
+        }
+    }
+
+    systems
+}
+
+/// Workspace systems defined in one directory. JavaScript tools layer on top
+/// of each other (Turborepo over npm workspaces), so only the most specific
+/// one counts; Cargo, JVM and Go workspaces sit beside it.
+fn detect_monorepo_tools(path: &Path) -> Vec<MonorepoInfo> {
+    let mut systems = Vec::new();
+
+    systems.extend(detect_js_workspaces(path));
+
+    // Cargo workspaces
+    if path.join("Cargo.toml").exists() {
+        systems.extend(detect_cargo_workspaces(path));
+    }
+
+    // Maven multi-module builds
+    if path.join("pom.xml").exists() {
+        systems.extend(detect_maven_modules(path));
+    }
+
+    // Gradle multi-project builds
+    if path.join("settings.gradle").exists() || path.join("settings.gradle.kts").exists() {
+        systems.extend(detect_gradle_projects(path));
+    }
+
+    // Go workspaces
+    if path.join("go.work").exists() {
+        systems.extend(detect_go_workspace(path));
+    }
+
+    systems
+}
+
+fn detect_js_workspaces(path: &Path) -> Option<MonorepoInfo> {
+    // Turborepo, Nx and Lerna run on top of the package manager's workspaces
+    let package_workspaces = if path.join("pnpm-workspace.yaml").exists() {
+        detect_pnpm_workspaces(path)
+    } else {
+        detect_npm_workspaces(path)
+    };
+
     // Check for specific monorepo tools first (most specific)
 
     // Turborepo
     if path.join("turbo.json").exists() {
-        return detect_npm_workspaces(path).map(|mut info| {
+        if let Some(mut info) = package_workspaces.clone() {
             info.tool = MonorepoTool::Turborepo;
-            info
-        });
+            return Some(info);
+        }
     }
 
     // Nx
     if path.join("nx.json").exists() {
-        return detect_nx_workspaces(path);
+        return Some(detect_nx_workspaces(path, package_workspaces));
     }
 
     // Lerna
     if path.join("lerna.json").exists() {
-        return detect_lerna_workspaces(path);
+        if let Some(info) = detect_lerna_workspaces(path, package_workspaces.as_ref()) {
+            return Some(info);
+        }
     }
 
     // Rush
     if path.join("rush.json").exists() {
-        return Some(MonorepoInfo {
-            tool: MonorepoTool::Rush,
-            workspace_patterns: vec![],
-            workspace_paths: vec![],
-        });
-    }
-
-    // pnpm workspaces
-    if path.join("pnpm-workspace.yaml").exists() {
-        return detect_pnpm_workspaces(path);
-    }
-
-    // Cargo workspaces
-    if path.join("Cargo.toml").exists() {
-        if let Some(info) = detect_cargo_workspaces(path) {
+        if let Some(info) = detect_rush_projects(path) {
             return Some(info);
         }
     }
 
-    // npm/yarn/bun workspaces (from package.json)
-    if let Some(pkg) = PackageJson::parse(path) {
-        if pkg.is_monorepo() {
-            return detect_npm_workspaces(path);
-        }
-    }
-
-    None
+    // pnpm / npm / yarn / bun workspaces
+    package_workspaces
 }
 
 /// Detect npm/yarn/bun workspaces from package.json
@@ -78,19 +182,13 @@ fn detect_npm_workspaces(path: &Path) -> Option<MonorepoInfo> {
     let workspace_paths = resolve_workspace_patterns(path, &patterns);
 
     // Determine which package manager
-    let tool = if path.join("bun.lock").exists() || path.join("bun.lockb").exists() {
-        MonorepoTool::BunWorkspaces
-    } else if path.join("yarn.lock").exists() {
-        MonorepoTool::YarnWorkspaces
-    } else {
-        MonorepoTool::NpmWorkspaces
+    let tool = match detect_package_manager(path) {
+        PackageManager::Bun => MonorepoTool::BunWorkspaces,
+        PackageManager::Yarn | PackageManager::YarnBerry => MonorepoTool::YarnWorkspaces,
+        _ => MonorepoTool::NpmWorkspaces,
     };
 
-    Some(MonorepoInfo {
-        tool,
-        workspace_patterns: patterns,
-        workspace_paths,
-    })
+    Some(MonorepoInfo::new(tool, path, patterns, workspace_paths))
 }
 
 /// Detect pnpm workspaces from pnpm-workspace.yaml
@@ -114,50 +212,63 @@ fn detect_pnpm_workspaces(path: &Path) -> Option<MonorepoInfo> {
 
     let workspace_paths = resolve_workspace_patterns(path, &patterns);
 
-    Some(MonorepoInfo {
-        tool: MonorepoTool::PnpmWorkspaces,
-        workspace_patterns: patterns,
+    Some(MonorepoInfo::new(
+        MonorepoTool::PnpmWorkspaces,
+        path,
+        patterns,
         workspace_paths,
-    })
+    ))
 }
 
-/// Detect Nx workspaces
-fn detect_nx_workspaces(path: &Path) -> Option<MonorepoInfo> {
-    // Nx typically uses apps/ and libs/ directories
+/// Detect Nx workspaces: projects in the conventional `apps/`, `libs/` and
+/// `packages/` folders plus the package manager's workspaces
+fn detect_nx_workspaces(path: &Path, package_workspaces: Option<MonorepoInfo>) -> MonorepoInfo {
+    let mut workspace_patterns = vec![
+        "apps/*".to_string(),
+        "libs/*".to_string(),
+        "packages/*".to_string(),
+    ];
     let mut workspace_paths = Vec::new();
 
     for dir in ["apps", "libs", "packages"] {
-        let dir_path = path.join(dir);
-        if dir_path.exists() {
-            if let Ok(entries) = fs::read_dir(&dir_path) {
-                for entry in entries.flatten() {
-                    let entry_path = entry.path();
-                    if entry_path.is_dir() {
-                        // Check if it's a valid project
-                        if entry_path.join("package.json").exists()
-                            || entry_path.join("project.json").exists()
-                        {
-                            workspace_paths.push(entry_path);
-                        }
-                    }
-                }
+        let Ok(entries) = fs::read_dir(path.join(dir)) else {
+            continue;
+        };
+        let mut projects: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|entry_path| {
+                entry_path.is_dir()
+                    && (entry_path.join("package.json").exists()
+                        || entry_path.join("project.json").exists())
+            })
+            .collect();
+        projects.sort();
+        workspace_paths.extend(projects);
+    }
+
+    if let Some(info) = package_workspaces {
+        for pattern in info.workspace_patterns {
+            if !workspace_patterns.contains(&pattern) {
+                workspace_patterns.push(pattern);
+            }
+        }
+        for workspace_path in info.workspace_paths {
+            if !workspace_paths.contains(&workspace_path) {
+                workspace_paths.push(workspace_path);
             }
         }
     }
 
-    Some(MonorepoInfo {
-        tool: MonorepoTool::Nx,
-        workspace_patterns: vec![
-            "apps/*".to_string(),
-            "libs/*".to_string(),
-            "packages/*".to_string(),
-        ],
-        workspace_paths,
-    })
+    MonorepoInfo::new(MonorepoTool::Nx, path, workspace_patterns, workspace_paths)
 }
 
-/// Detect Lerna workspaces
-fn detect_lerna_workspaces(path: &Path) -> Option<MonorepoInfo> {
+/// Detect Lerna workspaces. Without `packages` in lerna.json Lerna uses the
+/// package manager's workspaces, and `packages/*` when there are none.
+fn detect_lerna_workspaces(
+    path: &Path,
+    package_workspaces: Option<&MonorepoInfo>,
+) -> Option<MonorepoInfo> {
     let lerna_file = path.join("lerna.json");
     let content = fs::read_to_string(&lerna_file).ok()?;
 
@@ -171,15 +282,41 @@ fn detect_lerna_workspaces(path: &Path) -> Option<MonorepoInfo> {
                 .filter_map(|v| v.as_str().map(String::from))
                 .collect()
         })
+        .or_else(|| package_workspaces.map(|info| info.workspace_patterns.clone()))
         .unwrap_or_else(|| vec!["packages/*".to_string()]);
 
     let workspace_paths = resolve_workspace_patterns(path, &patterns);
 
-    Some(MonorepoInfo {
-        tool: MonorepoTool::Lerna,
-        workspace_patterns: patterns,
+    Some(MonorepoInfo::new(
+        MonorepoTool::Lerna,
+        path,
+        patterns,
         workspace_paths,
-    })
+    ))
+}
+
+/// Detect Rush monorepos from the projects listed in rush.json
+fn detect_rush_projects(path: &Path) -> Option<MonorepoInfo> {
+    let rush = RushJson::parse(path)?;
+
+    let patterns: Vec<String> = rush
+        .projects
+        .into_iter()
+        .map(|project| project.project_folder)
+        .collect();
+
+    let workspace_paths = patterns
+        .iter()
+        .map(|folder| path.join(folder))
+        .filter(|folder| folder.join("package.json").exists())
+        .collect();
+
+    Some(MonorepoInfo::new(
+        MonorepoTool::Rush,
+        path,
+        patterns,
+        workspace_paths,
+    ))
 }
 
 /// Detect Cargo workspaces
@@ -202,11 +339,26 @@ fn detect_cargo_workspaces(path: &Path) -> Option<MonorepoInfo> {
 
     if let Some(patterns) = members {
         if !patterns.is_empty() {
-            let workspace_paths = resolve_workspace_patterns(path, &patterns);
+            // Library crates are built through the binaries that use them;
+            // a root package with binaries runs alongside the members
+            let members = resolve_workspace_patterns(path, &patterns);
+            let mut workspace_paths: Vec<PathBuf> = members
+                .iter()
+                .filter(|member| is_runnable_crate(member))
+                .cloned()
+                .collect();
+            if workspace_paths.is_empty() {
+                workspace_paths = members.clone();
+            }
+            if cargo.get("package").is_some() && is_runnable_crate(path) {
+                workspace_paths.insert(0, path.to_path_buf());
+            }
 
             return Some(MonorepoInfo {
                 tool: MonorepoTool::CargoWorkspace,
+                root: path.to_path_buf(),
                 workspace_patterns: patterns,
+                members,
                 workspace_paths,
             });
         }
@@ -215,6 +367,149 @@ fn detect_cargo_workspaces(path: &Path) -> Option<MonorepoInfo> {
     None
 }
 
+/// Detect Maven multi-module builds. Only runnable modules become
+/// workspaces; libraries are built through them.
+fn detect_maven_modules(path: &Path) -> Option<MonorepoInfo> {
+    let modules = maven_module_paths(path);
+    jvm_monorepo_info(path, MonorepoTool::Maven, modules)
+}
+
+/// Detect Gradle multi-project builds from settings.gradle(.kts)
+fn detect_gradle_projects(path: &Path) -> Option<MonorepoInfo> {
+    let projects = gradle_project_paths(path);
+    jvm_monorepo_info(path, MonorepoTool::Gradle, projects)
+}
+
+fn jvm_monorepo_info(
+    path: &Path,
+    tool: MonorepoTool,
+    modules: Vec<PathBuf>,
+) -> Option<MonorepoInfo> {
+    let workspace_patterns = modules
+        .iter()
+        .filter_map(|m| m.strip_prefix(path).ok())
+        .map(|m| m.to_string_lossy().replace('\\', "/"))
+        .collect();
+
+    let workspace_paths: Vec<PathBuf> = modules
+        .iter()
+        .filter(|m| is_runnable_jvm_module(m))
+        .cloned()
+        .collect();
+
+    if workspace_paths.is_empty() {
+        return None;
+    }
+
+    Some(MonorepoInfo {
+        tool,
+        root: path.to_path_buf(),
+        workspace_patterns,
+        members: modules,
+        workspace_paths,
+    })
+}
+
+/// Detect Go workspaces from the `use` directives of go.work
+fn detect_go_workspace(path: &Path) -> Option<MonorepoInfo> {
+    let patterns = GoWork::parse(path)?.uses;
+
+    let members: Vec<PathBuf> = patterns
+        .iter()
+        .map(|module| path.join(module))
+        .filter(|module| module != path && module.join("go.mod").exists())
+        .collect();
+
+    if members.is_empty() {
+        return None;
+    }
+
+    // Like Cargo workspaces, library modules are built through the binaries
+    let mut workspace_paths: Vec<PathBuf> = members
+        .iter()
+        .filter(|module| is_runnable_go_module(module))
+        .cloned()
+        .collect();
+    if workspace_paths.is_empty() {
+        workspace_paths = members.clone();
+    }
+
+    Some(MonorepoInfo {
+        tool: MonorepoTool::GoWorkspace,
+        root: path.to_path_buf(),
+        workspace_patterns: patterns,
+        members,
+        workspace_paths,
+    })
+}
+
+/// Tasks a workspace tool defines for one of its members: Nx `project.json`
+/// targets and the Turborepo tasks the package has a script for. The tool's
+/// CLI is run through the workspace's package manager.
+pub fn workspace_tasks(info: &MonorepoInfo, member: &Path) -> Vec<DetectedTask> {
+    let package_manager = detect_package_manager(&info.root);
+    let mut tasks = match info.tool {
+        MonorepoTool::Nx => nx_tasks(member),
+        MonorepoTool::Turborepo => turbo_tasks(&info.root, member),
+        _ => Vec::new(),
+    };
+
+    for task in &mut tasks {
+        if let Some(command) = package_manager.exec_command(&task.command) {
+            task.command = command;
+        }
+    }
+    tasks
+}
+
+fn nx_tasks(member: &Path) -> Vec<DetectedTask> {
+    let Some(project) = NxProject::parse(member) else {
+        return Vec::new();
+    };
+    let name = project.get_name(member);
+
+    let mut targets: Vec<&String> = project.targets.keys().collect();
+    targets.sort();
+
+    targets
+        .into_iter()
+        .map(|target| DetectedTask {
+            name: target.clone(),
+            description: None,
+            source: TaskSource::Nx,
+            command: TaskSource::Nx.run_command(&format!("{}:{}", name, target)),
+            steps: Vec::new(),
+        })
+        .collect()
+}
+
+fn turbo_tasks(root: &Path, member: &Path) -> Vec<DetectedTask> {
+    let (Some(turbo), Some(pkg)) = (TurboJson::parse(root), PackageJson::parse(member)) else {
+        return Vec::new();
+    };
+    let Some(package) = pkg.name.clone() else {
+        return Vec::new();
+    };
+
+    // Turborepo runs a package's script of the same name; without one the
+    // task is a no-op for the package
+    turbo
+        .task_names(&package)
+        .into_iter()
+        .filter_map(|name| {
+            let script = pkg.scripts.get(&name)?;
+            Some(DetectedTask {
+                command: TaskSource::Turborepo
+                    .run_command(&format!("{} --filter={}", name, package)),
+                description: None,
+                name,
+                source: TaskSource::Turborepo,
+                steps: vec![script.clone()],
+            })
+        })
+        .collect()
+}
+
 /// Resolve glob patterns to actual paths
 fn resolve_workspace_patterns(base_path: &Path, patterns: &[String]) -> Vec<PathBuf> {
     let mut paths = Vec::new();
@@ -248,19 +543,27 @@ fn resolve_workspace_patterns(base_path: &Path, patterns: &[String]) -> Vec<Path
     paths
 }
 
-/// Get all workspace packages/projects for scanning
-pub fn get_workspace_projects(path: &Path, max_depth: u8) -> Vec<PathBuf> {
-    let mut projects = Vec::new();
-
-    if let Some(monorepo) = detect_monorepo(path) {
-        projects.extend(monorepo.workspace_paths);
+/// Get all workspace packages/projects for scanning: the members of every
+/// workspace system, then project folders none of the systems covers
+pub fn get_workspace_projects(
+    path: &Path,
+    max_depth: u8,
+    ignore: &ScanIgnore,
+    systems: &[MonorepoInfo],
+) -> Vec<PathBuf> {
+    let mut projects: Vec<PathBuf> = Vec::new();
+
+    for workspace_path in systems.iter().flat_map(|s| &s.workspace_paths) {
+        if !projects.contains(workspace_path) {
+            projects.push(workspace_path.clone());
+        }
     }
 
     // If no monorepo or we want deeper scanning
     if max_depth > 1 || projects.is_empty() {
-        let additional = scan_for_projects(path, max_depth);
+        let additional = scan_for_projects(path, max_depth, ignore);
         for project in additional {
-            if !projects.contains(&project) {
+            if !projects.contains(&project) && !systems.iter().any(|s| s.covers(&project, path)) {
                 projects.push(project);
             }
         }
@@ -270,7 +573,7 @@ pub fn get_workspace_projects(path: &Path, max_depth: u8) -> Vec<PathBuf> {
 }
 
 /// Scan directory for projects up to a certain depth
-fn scan_for_projects(path: &Path, max_depth: u8) -> Vec<PathBuf> {
+fn scan_for_projects(path: &Path, max_depth: u8, ignore: &ScanIgnore) -> Vec<PathBuf> {
     let mut projects = Vec::new();
 
     if max_depth == 0 {
@@ -289,27 +592,7 @@ fn scan_for_projects(path: &Path, max_depth: u8) -> Vec<PathBuf> {
             continue;
         }
 
-        let folder_name = entry_path
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_default();
-
-        // Skip hidden directories and common non-project directories
-        if folder_name.starts_with('.')
-            || matches!(
-                folder_name.as_str(),
-                "node_modules"
-                    | "target"
-                    | "dist"
-                    | "build"
-                    | "__pycache__"
-                    | "vendor"
-                    | ".git"
-                    | "coverage"
-                    | ".next"
-                    | ".nuxt"
-            )
-        {
+        if ignore.is_ignored(&entry_path) {
             continue;
         }
 
@@ -320,7 +603,7 @@ fn scan_for_projects(path: &Path, max_depth: u8) -> Vec<PathBuf> {
 
         // Recursively scan subdirectories
         if max_depth > 1 {
-            let sub_projects = scan_for_projects(&entry_path, max_depth - 1);
+            let sub_projects = scan_for_projects(&entry_path, max_depth - 1, ignore);
             projects.extend(sub_projects);
         }
     }
@@ -340,6 +623,7 @@ fn is_project_directory(path: &Path) -> bool {
         "go.mod",
         "pom.xml",
         "build.gradle",
+        "build.gradle.kts",
         "mix.exs",
     ];
 
@@ -347,3 +631,194 @@ fn is_project_directory(path: &Path) -> bool {
         .iter()
         .any(|indicator| path.join(indicator).exists())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    fn members(info: &MonorepoInfo) -> Vec<String> {
+        info.workspace_paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(&info.root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_workspace_systems() {
+        let fixture = Fixture::new(&[
+            ("web/package.json", r#"{ "name": "web" }"#),
+            ("web/pnpm-workspace.yaml", "packages:\n  - \"apps/*\"\n"),
+            ("web/apps/site/package.json", r#"{ "name": "site" }"#),
+            ("server/Cargo.toml", "[workspace]\nmembers = [\"api\"]\n"),
+            (
+                "server/api/Cargo.toml",
+                "[package]\nname = \"api\"\nversion = \"0.1.0\"\n",
+            ),
+            ("server/api/src/main.rs", "fn main() {}\n"),
+            ("deep/a/b/go.work", "go 1.22\n\nuse ./svc\n"),
+        ]);
+        let root = fixture.path();
+        let ignore = ScanIgnore::new(root, &[]);
+
+        let systems = detect_workspace_systems(root, 2, &ignore);
+        let found: Vec<(MonorepoTool, PathBuf)> = systems
+            .iter()
+            .map(|s| {
+                (
+                    s.tool.clone(),
+                    s.root.strip_prefix(root).unwrap().to_path_buf(),
+                )
+            })
+            .collect();
+        // `deep/a/b` is below the depth limit
+        assert_eq!(
+            found,
+            vec![
+                (MonorepoTool::CargoWorkspace, PathBuf::from("server")),
+                (MonorepoTool::PnpmWorkspaces, PathBuf::from("web")),
+            ]
+        );
+        assert_eq!(members(&systems[0]), vec!["api"]);
+        assert_eq!(members(&systems[1]), vec!["apps/site"]);
+
+        assert!(detect_workspace_systems(root, 0, &ignore).is_empty());
+    }
+
+    #[test]
+    fn test_nested_maven_aggregator_is_listed_once() {
+        let app = "<project><packaging>war</packaging></project>";
+        let fixture = Fixture::new(&[
+            ("pom.xml", "<project><modules><module>services</module></modules></project>"),
+            (
+                "services/pom.xml",
+                "<project><packaging>pom</packaging><modules><module>api</module></modules></project>",
+            ),
+            ("services/api/pom.xml", app),
+        ]);
+        let root = fixture.path();
+
+        let systems = detect_workspace_systems(root, 3, &ScanIgnore::new(root, &[]));
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].root, root);
+        assert_eq!(members(&systems[0]), vec!["services/api"]);
+    }
+
+    #[test]
+    fn test_rush_projects() {
+        let fixture = Fixture::new(&[
+            (
+                "rush.json",
+                r#"{
+  // generated by rush init
+  "rushVersion": "5.112.0",
+  "projects": [
+    { "packageName": "@acme/web", "projectFolder": "apps/web" },
+    { "packageName": "@acme/ui", "projectFolder": "libs/ui" },
+    { "packageName": "@acme/gone", "projectFolder": "libs/gone" }
+  ]
+}"#,
+            ),
+            ("apps/web/package.json", r#"{ "name": "@acme/web" }"#),
+            ("libs/ui/package.json", r#"{ "name": "@acme/ui" }"#),
+        ]);
+        let root = fixture.path();
+
+        let systems = detect_monorepo_tools(root);
+        assert_eq!(systems.len(), 1);
+        assert_eq!(systems[0].tool, MonorepoTool::Rush);
+        assert_eq!(members(&systems[0]), vec!["apps/web", "libs/ui"]);
+    }
+
+    #[test]
+    fn test_turborepo_tasks() {
+        let fixture = Fixture::new(&[
+            ("package.json", r#"{ "name": "shop", "private": true }"#),
+            ("pnpm-workspace.yaml", "packages:\n  - \"apps/*\"\n"),
+            (
+                "turbo.json",
+                r#"{ "tasks": { "build": { "dependsOn": ["^build"] }, "dev": { "persistent": true }, "lint": {} } }"#,
+            ),
+            (
+                "apps/web/package.json",
+                r#"{ "name": "web", "scripts": { "dev": "next dev", "build": "next build" } }"#,
+            ),
+        ]);
+        let root = fixture.path();
+
+        let systems = detect_monorepo_tools(root);
+        assert_eq!(systems[0].tool, MonorepoTool::Turborepo);
+        assert_eq!(members(&systems[0]), vec!["apps/web"]);
+
+        let tasks = workspace_tasks(&systems[0], &root.join("apps/web"));
+        let commands: Vec<&str> = tasks.iter().map(|t| t.command.as_str()).collect();
+        // `lint` has no script in the package
+        assert_eq!(
+            commands,
+            vec![
+                "pnpm exec turbo run build --filter=web",
+                "pnpm exec turbo run dev --filter=web"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nx_targets() {
+        let fixture = Fixture::new(&[
+            ("nx.json", "{}"),
+            (
+                "package.json",
+                r#"{ "name": "org", "workspaces": ["tools/*"] }"#,
+            ),
+            (
+                "apps/shop/project.json",
+                r#"{ "name": "storefront", "targets": { "serve": {}, "build": {}, "test": {} } }"#,
+            ),
+            ("tools/seed/package.json", r#"{ "name": "seed" }"#),
+        ]);
+        let root = fixture.path();
+
+        let systems = detect_monorepo_tools(root);
+        assert_eq!(systems[0].tool, MonorepoTool::Nx);
+        assert_eq!(members(&systems[0]), vec!["apps/shop", "tools/seed"]);
+
+        let tasks = workspace_tasks(&systems[0], &root.join("apps/shop"));
+        let commands: Vec<&str> = tasks.iter().map(|t| t.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "npx nx run storefront:build",
+                "npx nx run storefront:serve",
+                "npx nx run storefront:test"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lerna_packages() {
+        let fixture = Fixture::new(&[
+            ("lerna.json", r#"{ "packages": ["modules/*"] }"#),
+            (
+                "package.json",
+                r#"{ "name": "mono", "workspaces": ["packages/*"] }"#,
+            ),
+            ("modules/core/package.json", r#"{ "name": "core" }"#),
+            ("packages/ignored/package.json", r#"{ "name": "ignored" }"#),
+        ]);
+        let root = fixture.path();
+
+        let systems = detect_monorepo_tools(root);
+        assert_eq!(systems[0].tool, MonorepoTool::Lerna);
+        assert_eq!(members(&systems[0]), vec!["modules/core"]);
+
+        // Without `packages`, Lerna follows the package manager's workspaces
+        fs::write(root.join("lerna.json"), "{}").unwrap();
+        let systems = detect_monorepo_tools(root);
+        assert_eq!(members(&systems[0]), vec!["packages/ignored"]);
+    }
+}
@@ -1,17 +1,31 @@
 use crate::models::detected_service::PackageManager;
+use crate::utils::parsers::{PackageJson, PyProject};
 use std::path::Path;
 
 /// Detect the package manager used in a project
 pub fn detect_package_manager(path: &Path) -> PackageManager {
+    // Corepack's `packageManager` field is authoritative
+    if let Some(declared) = declared_package_manager(path) {
+        return declared;
+    }
+
     // JavaScript/TypeScript (priority order - most specific first)
-    if path.join("bun.lock").exists() || path.join("bun.lockb").exists() {
+    if path.join("bun.lock").exists()
+        || path.join("bun.lockb").exists()
+        || path.join("bunfig.toml").exists()
+    {
         return PackageManager::Bun;
     }
 
-    if path.join("pnpm-lock.yaml").exists() {
+    if path.join("pnpm-lock.yaml").exists() || path.join("pnpm-workspace.yaml").exists() {
         return PackageManager::Pnpm;
     }
 
+    // Plug'n'Play installs only exist since Yarn 2
+    if path.join(".pnp.cjs").exists() || path.join(".pnp.loader.mjs").exists() {
+        return PackageManager::YarnBerry;
+    }
+
     if path.join("yarn.lock").exists() {
         // Check for Yarn Berry (v2+)
         if path.join(".yarnrc.yml").exists() || path.join(".yarnrc.yaml").exists() {
@@ -25,7 +39,10 @@ pub fn detect_package_manager(path: &Path) -> PackageManager {
     }
 
     // Deno
-    if path.join("deno.json").exists() || path.join("deno.jsonc").exists() {
+    if path.join("deno.json").exists()
+        || path.join("deno.jsonc").exists()
+        || path.join("deno.lock").exists()
+    {
         return PackageManager::Deno;
     }
 
@@ -39,12 +56,26 @@ pub fn detect_package_manager(path: &Path) -> PackageManager {
         return PackageManager::Uv;
     }
 
-    if path.join("poetry.lock").exists() || path.join("pyproject.toml").exists() {
-        // Check if it's actually poetry
-        if let Ok(content) = std::fs::read_to_string(path.join("pyproject.toml")) {
-            if content.contains("[tool.poetry]") {
-                return PackageManager::Poetry;
-            }
+    if path.join("poetry.lock").exists() {
+        return PackageManager::Poetry;
+    }
+
+    if path.join("pdm.lock").exists() {
+        return PackageManager::Pdm;
+    }
+
+    if let Some(pyproject) = PyProject::parse(path) {
+        if pyproject.is_poetry() {
+            return PackageManager::Poetry;
+        }
+        if pyproject.is_pdm() {
+            return PackageManager::Pdm;
+        }
+        if pyproject.is_uv() {
+            return PackageManager::Uv;
+        }
+        if pyproject.is_hatch() {
+            return PackageManager::Hatch;
         }
     }
 
@@ -56,7 +87,7 @@ pub fn detect_package_manager(path: &Path) -> PackageManager {
         return PackageManager::Conda;
     }
 
-    if path.join("requirements.txt").exists() {
+    if path.join("requirements.txt").exists() || path.join("pyproject.toml").exists() {
         return PackageManager::Pip;
     }
 
@@ -106,6 +137,30 @@ pub fn detect_package_manager(path: &Path) -> PackageManager {
     PackageManager::Unknown
 }
 
+/// Package manager pinned through Corepack, by the package itself or by the
+/// workspace root above it (workspace members rarely repeat the field).
+/// Only JavaScript packages inherit the root's field: a Cargo or Go member
+/// of a pnpm repository is not installed with pnpm.
+pub fn declared_package_manager(path: &Path) -> Option<PackageManager> {
+    if !path.join("package.json").is_file() {
+        return None;
+    }
+
+    for dir in path.ancestors().take(5) {
+        if let Some(declared) =
+            PackageJson::parse(dir).and_then(|pkg| pkg.get_declared_package_manager())
+        {
+            return Some(declared);
+        }
+
+        // Never look past the repository
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
 /// Check if path has C# project files
 fn has_csproj_or_sln(path: &Path) -> bool {
     if let Ok(entries) = std::fs::read_dir(path) {
@@ -116,6 +171,7 @@ fn has_csproj_or_sln(path: &Path) -> bool {
                 || name.ends_with(".fsproj")
                 || name.ends_with(".vbproj")
                 || name.ends_with(".sln")
+                || name.ends_with(".slnx")
             {
                 return true;
             }
@@ -136,3 +192,41 @@ pub fn get_run_command(package_manager: &PackageManager, script: &str) -> String
         _ => script.to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_root_package_manager_only_for_js_members() {
+        let fixture = Fixture::new(&[
+            (
+                "package.json",
+                r#"{ "name": "mono", "packageManager": "pnpm@9.1.0" }"#,
+            ),
+            ("apps/web/package.json", r#"{ "name": "web" }"#),
+            ("services/api/Cargo.toml", "[package]\nname = \"api\"\n"),
+            ("services/worker/go.mod", "module example.com/worker\n"),
+            ("services/ml/requirements.txt", "fastapi\n"),
+        ]);
+        fixture.mkdir(".git");
+
+        assert_eq!(
+            detect_package_manager(&fixture.join("apps/web")),
+            PackageManager::Pnpm
+        );
+        assert_eq!(
+            detect_package_manager(&fixture.join("services/api")),
+            PackageManager::Cargo
+        );
+        assert_eq!(
+            detect_package_manager(&fixture.join("services/worker")),
+            PackageManager::GoMod
+        );
+        assert_eq!(
+            detect_package_manager(&fixture.join("services/ml")),
+            PackageManager::Pip
+        );
+    }
+}
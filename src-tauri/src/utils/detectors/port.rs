@@ -1,8 +1,8 @@
 use crate::models::detected_service::Framework;
-use crate::utils::parsers::{PackageJson, TauriConf};
+use crate::utils::parsers::{ApplicationConfig, LaunchSettings, PackageJson, TauriConf};
 use regex::Regex;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 static VITE_PORT_REGEX: LazyLock<Regex> =
@@ -111,6 +111,34 @@ fn detect_port_from_config(
             return Some(8000);
         }
 
+        Framework::ActixWeb
+        | Framework::Axum
+        | Framework::Rocket
+        | Framework::Warp
+        | Framework::Tide
+        | Framework::Rust => {
+            if let Some(port) = parse_rocket_port(path).or_else(|| parse_rust_config_port(path)) {
+                return Some(port);
+            }
+        }
+
+        Framework::AspNetCore | Framework::Blazor | Framework::CSharp => {
+            if let Some(port) = LaunchSettings::parse(path).and_then(|s| s.get_port()) {
+                return Some(port);
+            }
+        }
+
+        Framework::Spring
+        | Framework::Quarkus
+        | Framework::Micronaut
+        | Framework::Ktor
+        | Framework::Java
+        | Framework::Kotlin => {
+            if let Some(port) = ApplicationConfig::parse(path).and_then(|c| c.get_port()) {
+                return Some(port);
+            }
+        }
+
         Framework::Rails => {
             return Some(3000);
         }
@@ -179,6 +207,62 @@ fn parse_nuxt_port(path: &Path) -> Option<u16> {
     None
 }
 
+/// Rocket.toml: `port` in the `[debug]`, `[default]` or `[global]` profile
+fn parse_rocket_port(path: &Path) -> Option<u16> {
+    let content = fs::read_to_string(path.join("Rocket.toml")).ok()?;
+    let config: toml::Value = toml::from_str(&content).ok()?;
+
+    ["debug", "default", "global"].iter().find_map(|profile| {
+        config
+            .get(profile)
+            .and_then(|p| p.get("port"))
+            .and_then(|p| p.as_integer())
+            .and_then(|p| u16::try_from(p).ok())
+    })
+}
+
+/// `config/*.toml` (config-rs layouts): a top-level `port` or one under a
+/// `[server]`, `[http]`, `[app]` or `[application]` table. Development
+/// files win over defaults.
+fn parse_rust_config_port(path: &Path) -> Option<u16> {
+    let mut files: Vec<PathBuf> = fs::read_dir(path.join("config"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+
+    let preference = ["development", "local", "default", "base"];
+    files.sort_by_key(|file| {
+        let stem = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        (
+            preference
+                .iter()
+                .position(|p| *p == stem)
+                .unwrap_or(preference.len()),
+            stem,
+        )
+    });
+
+    files.iter().find_map(|file| {
+        let content = fs::read_to_string(file).ok()?;
+        let config: toml::Value = toml::from_str(&content).ok()?;
+
+        std::iter::once(Some(&config))
+            .chain(
+                ["server", "http", "app", "application"]
+                    .iter()
+                    .map(|table| config.get(table)),
+            )
+            .flatten()
+            .find_map(|table| table.get("port").and_then(|p| p.as_integer()))
+            .and_then(|p| u16::try_from(p).ok())
+    })
+}
+
 fn parse_flask_port(path: &Path) -> Option<u16> {
     let app_files = ["app.py", "main.py", "run.py"];
 
@@ -195,14 +279,15 @@ fn parse_flask_port(path: &Path) -> Option<u16> {
     None
 }
 
-fn detect_port_from_env(path: &Path) -> Option<u16> {
-    let env_files = [
-        ".env",
-        ".env.local",
-        ".env.development",
-        ".env.development.local",
-    ];
+/// Env files a dev server reads, in the order they are looked at
+pub const DEV_ENV_FILES: [&str; 4] = [
+    ".env",
+    ".env.local",
+    ".env.development",
+    ".env.development.local",
+];
 
+fn detect_port_from_env(path: &Path) -> Option<u16> {
     let port_patterns = [
         &*ENV_PORT_REGEX,
         &*ENV_VITE_PORT_REGEX,
@@ -211,7 +296,7 @@ fn detect_port_from_env(path: &Path) -> Option<u16> {
         &*ENV_APP_PORT_REGEX,
     ];
 
-    for file in env_files {
+    for file in DEV_ENV_FILES {
         if let Ok(content) = fs::read_to_string(path.join(file)) {
             for line in content.lines() {
                 for pattern in &port_patterns {
@@ -0,0 +1,362 @@
+use crate::models::detected_service::{DetectedTask, Framework, PackageManager, TaskSource};
+use crate::utils::detectors::package_manager::detect_package_manager;
+use crate::utils::parsers::{Pipfile, PyProject, Requirements};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use walkdir::WalkDir;
+
+static FASTAPI_APP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([A-Za-z_]\w*)\s*(?::[^=\n]+)?=\s*(?:fastapi\.)?FastAPI\(").unwrap()
+});
+
+static FLASK_APP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([A-Za-z_]\w*)\s*(?::[^=\n]+)?=\s*(?:flask\.)?Flask\(").unwrap()
+});
+
+/// Directories never searched for application modules
+const SKIPPED_DIRS: [&str; 9] = [
+    "venv",
+    "env",
+    "node_modules",
+    "__pycache__",
+    "site-packages",
+    "tests",
+    "test",
+    "migrations",
+    "docs",
+];
+
+/// Entry-point file names, most likely first. Only these are searched for
+/// the application object.
+const APP_FILE_PREFERENCE: [&str; 6] = [
+    "main.py",
+    "app.py",
+    "asgi.py",
+    "wsgi.py",
+    "server.py",
+    "api.py",
+];
+
+/// Everything Warden reads from a Python project's manifests
+#[derive(Debug, Clone, Default)]
+pub struct PythonProject {
+    pub pyproject: Option<PyProject>,
+    pub pipfile: Option<Pipfile>,
+    pub requirements: Option<Requirements>,
+}
+
+impl PythonProject {
+    pub fn load(path: &Path) -> Self {
+        Self {
+            pyproject: PyProject::parse(path),
+            pipfile: Pipfile::parse(path),
+            requirements: Requirements::parse(path),
+        }
+    }
+
+    pub fn has_manifest(&self) -> bool {
+        self.pyproject.is_some() || self.pipfile.is_some() || self.requirements.is_some()
+    }
+
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.pyproject
+            .as_ref()
+            .is_some_and(|p| p.has_dependency(name))
+            || self
+                .pipfile
+                .as_ref()
+                .is_some_and(|p| p.has_dependency(name))
+            || self
+                .requirements
+                .as_ref()
+                .is_some_and(|r| r.has_dependency(name))
+    }
+
+    /// Framework implied by the declared dependencies
+    pub fn detect_framework(&self) -> Framework {
+        if self.has_dependency("django") {
+            Framework::Django
+        } else if self.has_dependency("fastapi") {
+            Framework::FastApi
+        } else if self.has_dependency("flask") {
+            Framework::Flask
+        } else if self.has_dependency("pyramid") {
+            Framework::Pyramid
+        } else if self.has_dependency("tornado") {
+            Framework::Tornado
+        } else {
+            Framework::Python
+        }
+    }
+}
+
+/// Location of an application object, e.g. `app.main:app`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PythonApp {
+    pub module: String,
+    pub variable: String,
+    /// Directory to put on `sys.path` for src layouts (`src`)
+    pub app_dir: Option<String>,
+    /// File path relative to the project root
+    pub file: String,
+}
+
+impl PythonApp {
+    pub fn target(&self) -> String {
+        format!("{}:{}", self.module, self.variable)
+    }
+}
+
+/// Find the module defining the FastAPI or Flask application object
+pub fn find_python_app(path: &Path, framework: &Framework) -> Option<PythonApp> {
+    let regex = match framework {
+        Framework::FastApi => &*FASTAPI_APP_REGEX,
+        Framework::Flask => &*FLASK_APP_REGEX,
+        _ => return None,
+    };
+
+    let mut candidates: Vec<(usize, usize, PythonApp)> = Vec::new();
+
+    let walker = WalkDir::new(path)
+        .max_depth(4)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !e.file_type().is_dir()
+                || !is_skipped_dir(&e.file_name().to_string_lossy())
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(preference) = APP_FILE_PREFERENCE
+            .iter()
+            .position(|name| *name == file_name)
+        else {
+            continue;
+        };
+
+        let is_small = entry
+            .metadata()
+            .map(|m| m.len() < 256 * 1024)
+            .unwrap_or(false);
+        if !is_small {
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+
+        let Some(captures) = regex.captures(&content) else {
+            continue;
+        };
+
+        let Ok(relative) = entry.path().strip_prefix(path) else {
+            continue;
+        };
+
+        let mut components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        let app_dir = if components.len() > 1 && components[0] == "src" {
+            components.remove(0);
+            Some("src".to_string())
+        } else {
+            None
+        };
+
+        if let Some(last) = components.last_mut() {
+            *last = last.trim_end_matches(".py").to_string();
+        }
+
+        candidates.push((
+            entry.depth(),
+            preference,
+            PythonApp {
+                module: components.join("."),
+                variable: captures[1].to_string(),
+                app_dir,
+                file: relative.to_string_lossy().replace('\\', "/"),
+            },
+        ));
+    }
+
+    candidates.sort_by_key(|(depth, preference, _)| (*depth, *preference));
+    candidates.into_iter().next().map(|(_, _, app)| app)
+}
+
+/// Find Django's `manage.py`, which often lives in a nested directory
+/// (`backend/manage.py`, `src/manage.py`). Returns the path relative to
+/// `path` using forward slashes.
+pub fn find_manage_py(path: &Path) -> Option<String> {
+    if path.join("manage.py").is_file() {
+        return Some("manage.py".to_string());
+    }
+
+    WalkDir::new(path)
+        .max_depth(3)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !e.file_type().is_dir()
+                || !is_skipped_dir(&e.file_name().to_string_lossy())
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "manage.py")
+        .min_by_key(|e| e.depth())
+        .and_then(|e| {
+            e.path()
+                .strip_prefix(path)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+}
+
+/// Prefix a command with the project's Python runner (`uv run`,
+/// `poetry run`, ...). Plain pip/conda environments run it as-is.
+pub fn python_command(package_manager: &PackageManager, command: &str) -> String {
+    match package_manager {
+        PackageManager::Uv
+        | PackageManager::Poetry
+        | PackageManager::Pipenv
+        | PackageManager::Pdm
+        | PackageManager::Hatch => format!("{} {}", package_manager.run_prefix(), command),
+        _ => command.to_string(),
+    }
+}
+
+/// Console scripts and runner scripts (PDM, Hatch, Pipenv) as tasks
+pub fn detect_python_tasks(path: &Path) -> Vec<DetectedTask> {
+    let pyproject = PyProject::parse(path);
+    let pipfile = Pipfile::parse(path);
+
+    if pyproject.is_none() && pipfile.is_none() {
+        return Vec::new();
+    }
+
+    let package_manager = detect_package_manager(path);
+    let mut tasks = Vec::new();
+
+    if let Some(pyproject) = &pyproject {
+        for script in pyproject.get_console_scripts() {
+            tasks.push(DetectedTask {
+                command: python_command(
+                    &package_manager,
+                    &TaskSource::PyProject.run_command(&script),
+                ),
+                name: script,
+                description: None,
+                source: TaskSource::PyProject,
+                steps: Vec::new(),
+            });
+        }
+
+        for (name, command) in pyproject.get_pdm_scripts() {
+            tasks.push(DetectedTask {
+                command: format!("pdm run {}", name),
+                name,
+                description: None,
+                source: TaskSource::PyProject,
+                steps: command.into_iter().collect(),
+            });
+        }
+
+        for (name, command) in pyproject.get_hatch_scripts() {
+            tasks.push(DetectedTask {
+                command: format!("hatch run {}", name),
+                name,
+                description: None,
+                source: TaskSource::PyProject,
+                steps: command.into_iter().collect(),
+            });
+        }
+    }
+
+    if let Some(pipfile) = &pipfile {
+        let mut scripts: Vec<(&String, &String)> = pipfile.scripts.iter().collect();
+        scripts.sort();
+
+        for (name, command) in scripts {
+            tasks.push(DetectedTask {
+                command: TaskSource::Pipfile.run_command(name),
+                name: name.clone(),
+                description: None,
+                source: TaskSource::Pipfile,
+                steps: vec![command.clone()],
+            });
+        }
+    }
+
+    tasks
+}
+
+fn is_skipped_dir(name: &str) -> bool {
+    name.starts_with('.') || SKIPPED_DIRS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_find_python_app() {
+        let fixture = Fixture::new(&[
+            (
+                "src/billing/main.py",
+                "from fastapi import FastAPI\n\napi: FastAPI = FastAPI()\n",
+            ),
+            ("src/billing/routes.py", "router = FastAPI()\n"),
+            ("tests/main.py", "app = FastAPI()\n"),
+        ]);
+
+        let app = find_python_app(fixture.path(), &Framework::FastApi).unwrap();
+        assert_eq!(app.target(), "billing.main:api");
+        assert_eq!(app.app_dir.as_deref(), Some("src"));
+        assert_eq!(app.file, "src/billing/main.py");
+
+        // Shallower entry files win, then the preferred names
+        fixture.write("server.py", "import flask\napp = flask.Flask(__name__)\n");
+        fixture.write("app.py", "application = Flask(__name__)\n");
+        let app = find_python_app(fixture.path(), &Framework::Flask).unwrap();
+        assert_eq!(app.target(), "app:application");
+        assert_eq!(app.app_dir, None);
+
+        assert!(find_python_app(fixture.path(), &Framework::Django).is_none());
+    }
+
+    #[test]
+    fn test_python_project() {
+        let fixture = Fixture::new(&[
+            ("requirements.txt", "Flask==3.0\n"),
+            ("Pipfile", "[packages]\ndjango = \"*\"\n"),
+            ("backend/manage.py", ""),
+        ]);
+
+        let project = PythonProject::load(fixture.path());
+        assert!(project.has_manifest());
+        assert!(project.has_dependency("flask"));
+        assert_eq!(project.detect_framework(), Framework::Django);
+        assert_eq!(
+            find_manage_py(fixture.path()).as_deref(),
+            Some("backend/manage.py")
+        );
+
+        assert_eq!(
+            python_command(&PackageManager::Poetry, "python manage.py runserver"),
+            "poetry run python manage.py runserver"
+        );
+        assert_eq!(
+            python_command(&PackageManager::Pip, "flask run"),
+            "flask run"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::models::detected_service::DetectedService;
+use crate::utils::detectors::python::PythonProject;
+use crate::utils::parsers::{CargoToml, CsProj, DetectionRule, GoMod, PackageJson};
+use std::fs;
+use std::path::Path;
+
+/// Evidence for a user-defined rule, or None when one of its conditions fails
+pub fn match_rule(
+    rule: &DetectionRule,
+    path: &Path,
+    package_json: Option<&PackageJson>,
+    cargo_toml: Option<&CargoToml>,
+) -> Option<Vec<String>> {
+    let mut evidence = Vec::new();
+
+    for pattern in &rule.matches.files {
+        let file = matching_file(path, pattern)?;
+        evidence.push(file);
+    }
+
+    for dependency in &rule.matches.dependencies {
+        let manifest = declaring_manifest(path, dependency, package_json, cargo_toml)?;
+        evidence.push(format!("{}: {}", manifest, dependency));
+    }
+
+    for content in &rule.matches.contains {
+        let regex = content.regex.as_ref()?;
+        let text = fs::read_to_string(path.join(&content.file)).ok()?;
+        if !regex.is_match(&text) {
+            return None;
+        }
+        evidence.push(format!("{}: /{}/", content.file, content.pattern));
+    }
+
+    Some(evidence)
+}
+
+/// Apply what a rule sets on top of the detected service
+pub fn apply_rule(service: &mut DetectedService, rule: &DetectionRule) {
+    service.framework_label = Some(rule.framework.clone());
+
+    if let Some(category) = &rule.category {
+        service.category = category.clone();
+    }
+    if rule.port.is_some() {
+        service.port = rule.port;
+    }
+    if rule.dev.is_some() {
+        service.dev_command = rule.dev.clone();
+    }
+    if rule.build.is_some() {
+        service.build_command = rule.build.clone();
+    }
+    if rule.start.is_some() {
+        service.start_command = rule.start.clone();
+    }
+}
+
+/// First file matching a path or glob pattern, relative to `path`
+fn matching_file(path: &Path, pattern: &str) -> Option<String> {
+    if !pattern.contains(['*', '?', '[']) {
+        return path.join(pattern).exists().then(|| pattern.to_string());
+    }
+
+    let full_pattern = path.join(pattern);
+    glob::glob(&full_pattern.to_string_lossy())
+        .ok()?
+        .flatten()
+        .next()
+        .and_then(|p| {
+            p.strip_prefix(path)
+                .ok()
+                .map(|p| p.to_string_lossy().to_string())
+        })
+}
+
+/// Manifest of the directory that declares a dependency
+fn declaring_manifest(
+    path: &Path,
+    dependency: &str,
+    package_json: Option<&PackageJson>,
+    cargo_toml: Option<&CargoToml>,
+) -> Option<&'static str> {
+    if package_json.is_some_and(|pkg| pkg.has_dependency(dependency)) {
+        return Some("package.json");
+    }
+    if cargo_toml.is_some_and(|cargo| cargo.has_dependency(dependency)) {
+        return Some("Cargo.toml");
+    }
+    if GoMod::parse(path).is_some_and(|go_mod| go_mod.has_dependency(dependency)) {
+        return Some("go.mod");
+    }
+    if PythonProject::load(path).has_dependency(dependency) {
+        return Some("python manifest");
+    }
+    if CsProj::parse(path).is_some_and(|csproj| csproj.has_package(dependency)) {
+        return Some("project file");
+    }
+
+    // composer.json and Gemfile quote the name; JVM builds spell
+    // `group:artifact` or `<artifactId>`, where a substring is enough
+    let quoted = [format!("\"{}\"", dependency), format!("'{}'", dependency)];
+    let declares = |manifest: &str, content: &str| match manifest {
+        "composer.json" | "Gemfile" => quoted.iter().any(|q| content.contains(q.as_str())),
+        _ => content.contains(dependency),
+    };
+
+    [
+        "composer.json",
+        "Gemfile",
+        "pom.xml",
+        "build.gradle",
+        "build.gradle.kts",
+    ]
+    .into_iter()
+    .find(|manifest| {
+        fs::read_to_string(path.join(manifest)).is_ok_and(|content| declares(manifest, &content))
+    })
+}
@@ -0,0 +1,213 @@
+use crate::models::detected_service::{
+    DetectedService, DetectedTask, Framework, ServiceCategory, TaskSource,
+};
+use crate::utils::detectors::task_runner::binary_category;
+use crate::utils::parsers::{CargoTarget, CargoToml};
+use std::path::{Path, PathBuf};
+
+/// Find the workspace a crate belongs to (the crate itself included)
+pub fn find_cargo_workspace_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .take(4)
+        .find(|dir| CargoToml::parse(dir).is_some_and(|cargo| cargo.is_workspace()))
+        .map(Path::to_path_buf)
+}
+
+/// Whether a crate has at least one binary target
+pub fn is_runnable_crate(path: &Path) -> bool {
+    CargoToml::parse(path).is_some_and(|cargo| !cargo.get_binaries(path).is_empty())
+}
+
+/// Split a crate into one service per binary when it has several binaries
+/// or lives in a workspace, so each runs with `cargo run -p <pkg> --bin <bin>`.
+pub fn detect_cargo_services(path: &Path, base: &DetectedService) -> Vec<DetectedService> {
+    if !is_rust_framework(&base.framework) {
+        return Vec::new();
+    }
+
+    let Some(cargo) = CargoToml::parse(path) else {
+        return Vec::new();
+    };
+    let Some(package_name) = cargo.get_name() else {
+        return Vec::new();
+    };
+
+    let mut binaries = cargo.get_binaries(path);
+    let in_workspace = find_cargo_workspace_root(path).is_some();
+    if binaries.is_empty() || (binaries.len() == 1 && !in_workspace) {
+        return Vec::new();
+    }
+
+    // List the binary `cargo run` picks first: `default-run`, or the one
+    // named after the package
+    let main_bin = cargo
+        .package
+        .as_ref()
+        .and_then(|p| p.default_run.clone())
+        .unwrap_or_else(|| package_name.clone());
+    binaries.sort_by_key(|bin| bin.name.as_deref() != Some(main_bin.as_str()));
+
+    let examples = example_tasks(&cargo, path, &package_name);
+    let mut services = Vec::new();
+
+    for bin in &binaries {
+        let bin_name = bin.name.clone().unwrap_or_else(|| package_name.clone());
+        let run_args = format!(
+            "-p {} --bin {}{}",
+            package_name,
+            bin_name,
+            features_flag(&cargo, bin)
+        );
+
+        let mut service = base.clone();
+        service.name = bin_name.clone();
+        service.category = if bin_name == package_name {
+            base.category.clone()
+        } else {
+            binary_category(&bin_name, &base.category)
+        };
+
+        service.dev_command = Some(if cargo.is_web_server() {
+            format!("cargo watch -x \"run {}\"", run_args)
+        } else {
+            format!("cargo run {}", run_args)
+        });
+        service.build_command = Some(format!("cargo build --release {}", run_args));
+        service.start_command = Some(format!("cargo run --release {}", run_args));
+
+        service.port = match service.category {
+            ServiceCategory::Worker | ServiceCategory::Unknown => None,
+            _ => base.port,
+        };
+
+        // Tasks and examples belong to the package: keep them once
+        if services.is_empty() {
+            service.tasks.extend(examples.iter().cloned());
+        } else {
+            service.tasks.clear();
+        }
+
+        services.push(service);
+    }
+
+    services
+}
+
+fn is_rust_framework(framework: &Framework) -> bool {
+    matches!(
+        framework,
+        Framework::ActixWeb
+            | Framework::Axum
+            | Framework::Rocket
+            | Framework::Warp
+            | Framework::Tide
+            | Framework::Rust
+    )
+}
+
+/// Examples as runnable tasks
+fn example_tasks(cargo: &CargoToml, path: &Path, package_name: &str) -> Vec<DetectedTask> {
+    cargo
+        .get_examples(path)
+        .into_iter()
+        .filter_map(|example| {
+            let name = example.name.clone()?;
+            Some(DetectedTask {
+                command: format!(
+                    "{}{}",
+                    TaskSource::Cargo.run_command(&format!("{}:{}", package_name, name)),
+                    features_flag(cargo, &example)
+                ),
+                description: example.path.clone(),
+                source: TaskSource::Cargo,
+                steps: Vec::new(),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// `--features a,b` for the `required-features` that `default` leaves off
+fn features_flag(cargo: &CargoToml, target: &CargoTarget) -> String {
+    let missing = cargo.get_missing_features(target);
+    if missing.is_empty() {
+        String::new()
+    } else {
+        format!(" --features {}", missing.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_detect_cargo_services() {
+        let fixture = Fixture::new(&[
+            ("Cargo.toml", "[workspace]\nmembers = [\"api\"]\n"),
+            (
+                "api/Cargo.toml",
+                r#"
+[package]
+name = "api"
+
+[dependencies]
+axum = "0.7"
+
+[features]
+default = ["server"]
+server = []
+queue = []
+
+[[bin]]
+name = "worker"
+path = "src/bin/worker.rs"
+required-features = ["server", "queue"]
+"#,
+            ),
+            ("api/src/main.rs", ""),
+            ("api/src/bin/worker.rs", ""),
+            ("api/examples/seed.rs", ""),
+        ]);
+        let path = fixture.join("api");
+        let mut base = DetectedService::new(
+            "api".to_string(),
+            path.to_string_lossy().to_string(),
+            "api".to_string(),
+        );
+        base.framework = Framework::Axum;
+        base.category = ServiceCategory::Backend;
+        base.port = Some(3000);
+
+        let services = detect_cargo_services(&path, &base);
+        assert_eq!(services.len(), 2);
+
+        let api = &services[0];
+        assert_eq!(api.name, "api");
+        assert_eq!(api.port, Some(3000));
+        assert_eq!(
+            api.dev_command.as_deref(),
+            Some("cargo watch -x \"run -p api --bin api\"")
+        );
+        assert_eq!(api.tasks.len(), 1);
+        assert_eq!(api.tasks[0].command, "cargo run -p api --example seed");
+
+        let worker = &services[1];
+        assert_eq!(worker.category, ServiceCategory::Worker);
+        assert_eq!(worker.port, None);
+        assert_eq!(
+            worker.start_command.as_deref(),
+            Some("cargo run --release -p api --bin worker --features queue")
+        );
+        assert!(worker.tasks.is_empty());
+
+        // A lone binary outside a workspace stays a single service
+        let single = Fixture::new(&[
+            ("Cargo.toml", "[package]\nname = \"cli\"\n"),
+            ("src/main.rs", ""),
+        ]);
+        base.framework = Framework::Rust;
+        assert!(detect_cargo_services(single.path(), &base).is_empty());
+    }
+}
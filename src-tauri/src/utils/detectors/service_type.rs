@@ -97,7 +97,8 @@ pub fn detect_service_category(
         | Framework::Java
         | Framework::Kotlin
         | Framework::CSharp
-        | Framework::Elixir => detect_from_project_structure(path, package_json),
+        | Framework::Elixir
+        | Framework::Custom => detect_from_project_structure(path, package_json),
 
         Framework::Unknown => ServiceCategory::Unknown,
     }
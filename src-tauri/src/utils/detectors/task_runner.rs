@@ -0,0 +1,199 @@
+use crate::models::detected_service::{DetectedService, DetectedTask, ServiceCategory, TaskSource};
+use crate::utils::detectors::python::detect_python_tasks;
+use crate::utils::parsers::{DenoJson, Justfile, Makefile, Procfile, Taskfile};
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PROCESS_PORT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:-p|--port|-b\s+\S*:)[=\s]*(\d{4,5})").unwrap());
+
+/// Binary names that are one-off tools rather than long-running services
+const TOOL_BINARY_NAMES: [&str; 8] = [
+    "cli",
+    "migrate",
+    "migration",
+    "migrations",
+    "seed",
+    "tool",
+    "tools",
+    "gen",
+];
+
+/// Task names that conventionally start a development server, in order of
+/// preference.
+const DEV_TASK_NAMES: [&str; 3] = ["dev", "run", "serve"];
+
+/// Collect tasks from every task runner file in the directory.
+///
+/// Dedicated task runners (just, go-task) come before Makefiles so that, when
+/// a repo has both, the more intentional definition is preferred.
+pub fn detect_tasks(path: &Path) -> Vec<DetectedTask> {
+    let mut tasks = Vec::new();
+
+    if let Some(justfile) = Justfile::parse(path) {
+        tasks.extend(justfile.recipes.into_iter().map(|recipe| DetectedTask {
+            command: TaskSource::Justfile.run_command(&recipe.name),
+            name: recipe.name,
+            description: recipe.description,
+            source: TaskSource::Justfile,
+            steps: recipe.commands,
+        }));
+    }
+
+    if let Some(taskfile) = Taskfile::parse(path) {
+        tasks.extend(taskfile.tasks.into_iter().map(|task| DetectedTask {
+            command: TaskSource::Taskfile.run_command(&task.name),
+            name: task.name,
+            description: task.description,
+            source: TaskSource::Taskfile,
+            steps: task.commands,
+        }));
+    }
+
+    if let Some(makefile) = Makefile::parse(path) {
+        tasks.extend(makefile.targets.into_iter().map(|target| DetectedTask {
+            command: TaskSource::Makefile.run_command(&target.name),
+            name: target.name,
+            description: target.description,
+            source: TaskSource::Makefile,
+            steps: target.commands,
+        }));
+    }
+
+    if let Some(deno) = DenoJson::parse(path) {
+        tasks.extend(deno.tasks.into_iter().map(|(name, task)| DetectedTask {
+            command: TaskSource::Deno.run_command(&name),
+            description: task.description().map(String::from),
+            source: TaskSource::Deno,
+            steps: vec![task.command().to_string()],
+            name,
+        }));
+    }
+
+    tasks.extend(detect_python_tasks(path));
+
+    tasks
+}
+
+/// Find the task that starts the development server (`dev`, `run`, `serve`)
+pub fn find_dev_task(tasks: &[DetectedTask]) -> Option<&DetectedTask> {
+    DEV_TASK_NAMES
+        .iter()
+        .find_map(|name| tasks.iter().find(|t| t.name == *name))
+}
+
+/// Find the task that builds the project
+pub fn find_build_task(tasks: &[DetectedTask]) -> Option<&DetectedTask> {
+    tasks.iter().find(|t| t.name == "build")
+}
+
+/// Detect one service per process type declared in a Procfile.
+///
+/// `base` is the service detected for the same directory; each process
+/// inherits its framework, package manager and install command. `release`
+/// processes are one-off hooks and are skipped.
+pub fn detect_procfile_services(path: &Path, base: &DetectedService) -> Vec<DetectedService> {
+    let Some(procfile) = Procfile::parse(path) else {
+        return Vec::new();
+    };
+
+    let mut services = Vec::new();
+
+    for process in procfile.processes {
+        if process.name == "release" {
+            continue;
+        }
+
+        let mut service = base.clone();
+        service.name = process.name.clone();
+        service.dev_command = Some(process.command.clone());
+        service.start_command = Some(process.command.clone());
+        service.category = process_category(&process.name, &base.category);
+
+        let explicit_port = PROCESS_PORT_REGEX
+            .captures(&process.command)
+            .and_then(|c| c.get(1))
+            .and_then(|p| p.as_str().parse::<u16>().ok());
+
+        service.port = match explicit_port {
+            Some(port) => Some(port),
+            None if process.name == "web" => base.port,
+            None => None,
+        };
+
+        // Tasks belong to the directory, not to each process: keep them once
+        if !services.is_empty() {
+            service.tasks.clear();
+        }
+
+        services.push(service);
+    }
+
+    services
+}
+
+/// Category implied by a process name (`web`, `worker`, `api`, ...)
+pub fn process_category(name: &str, base_category: &ServiceCategory) -> ServiceCategory {
+    let name_lower = name.to_lowercase();
+
+    if name_lower == "web" {
+        return match base_category {
+            ServiceCategory::Unknown => ServiceCategory::Backend,
+            other => other.clone(),
+        };
+    }
+
+    if [
+        "worker",
+        "job",
+        "queue",
+        "sidekiq",
+        "celery",
+        "clock",
+        "scheduler",
+        "cron",
+        "beat",
+    ]
+    .iter()
+    .any(|marker| name_lower.contains(marker))
+    {
+        return ServiceCategory::Worker;
+    }
+
+    // Match whole segments so `build` is not mistaken for `ui`
+    let has_segment = |markers: &[&str]| {
+        name_lower
+            .split(['-', '_'])
+            .any(|segment| markers.contains(&segment))
+    };
+
+    if has_segment(&["css", "js", "assets", "vite", "frontend", "client", "ui"]) {
+        return ServiceCategory::Frontend;
+    }
+
+    if has_segment(&["api", "server", "backend"]) {
+        return ServiceCategory::Backend;
+    }
+
+    base_category.clone()
+}
+
+/// Category implied by an executable name (`cmd/<name>`, `src/bin/<name>.rs`):
+/// one-off tools have no category, the rest follows [`process_category`].
+pub fn binary_category(name: &str, base_category: &ServiceCategory) -> ServiceCategory {
+    let name_lower = name.to_lowercase();
+
+    if TOOL_BINARY_NAMES
+        .iter()
+        .any(|tool| name_lower == *tool || name_lower.ends_with(&format!("-{}", tool)))
+    {
+        return ServiceCategory::Unknown;
+    }
+
+    if name_lower.contains("grpc") || name_lower.contains("gateway") {
+        return ServiceCategory::Api;
+    }
+
+    process_category(name, base_category)
+}
@@ -0,0 +1,440 @@
+use crate::models::detected_service::{Toolchain, ToolchainRequirement};
+use crate::utils::launch_env::shell_environment;
+use crate::utils::parsers::{GoMod, MiseToml, PackageJson, RustToolchain, ToolVersions};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+
+static VERSION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").unwrap());
+
+type VersionCache = HashMap<(Toolchain, PathBuf), Option<String>>;
+
+/// Versions found on PATH, probed once per toolchain and directory since
+/// version manager shims pick the version from the working directory
+static INSTALLED_VERSIONS: LazyLock<Mutex<VersionCache>> = LazyLock::new(Default::default);
+
+const TOOLCHAINS: [Toolchain; 4] = [
+    Toolchain::Node,
+    Toolchain::Python,
+    Toolchain::Rust,
+    Toolchain::Go,
+];
+
+/// Node LTS codenames nvm accepts as `lts/<name>`
+const NODE_LTS_RELEASES: [(&str, &str); 10] = [
+    ("argon", "4"),
+    ("boron", "6"),
+    ("carbon", "8"),
+    ("dubnium", "10"),
+    ("erbium", "12"),
+    ("fermium", "14"),
+    ("gallium", "16"),
+    ("hydrogen", "18"),
+    ("iron", "20"),
+    ("jod", "22"),
+];
+
+/// Plugin names asdf and mise use for a toolchain
+fn plugin_names(toolchain: Toolchain) -> &'static [&'static str] {
+    match toolchain {
+        Toolchain::Node => &["nodejs", "node"],
+        Toolchain::Python => &["python"],
+        Toolchain::Rust => &["rust"],
+        Toolchain::Go => &["golang", "go"],
+    }
+}
+
+/// Whether a directory holds a project built with the toolchain
+fn uses_toolchain(path: &Path, toolchain: Toolchain) -> bool {
+    let markers: &[&str] = match toolchain {
+        Toolchain::Node => &["package.json"],
+        Toolchain::Python => &[
+            "pyproject.toml",
+            "requirements.txt",
+            "Pipfile",
+            "setup.py",
+            "manage.py",
+        ],
+        Toolchain::Rust => &["Cargo.toml"],
+        Toolchain::Go => &["go.mod"],
+    };
+    markers.iter().any(|marker| path.join(marker).exists())
+}
+
+/// First line of a one-version file such as `.nvmrc`
+fn read_version_file(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Versions pinned in one directory as (toolchain, version, file), the
+/// file a version manager honours first listed first
+fn pinned_versions(dir: &Path) -> Vec<(Toolchain, String, String)> {
+    let mut pinned = Vec::new();
+
+    if let Some((mise, file)) = MiseToml::parse(dir) {
+        for toolchain in TOOLCHAINS {
+            if let Some(version) = plugin_names(toolchain).iter().find_map(|n| mise.get(n)) {
+                pinned.push((toolchain, version.to_string(), file.to_string()));
+            }
+        }
+    }
+
+    if let Some(tools) = ToolVersions::parse(dir) {
+        for toolchain in TOOLCHAINS {
+            if let Some(version) = plugin_names(toolchain).iter().find_map(|n| tools.get(n)) {
+                pinned.push((toolchain, version.to_string(), ".tool-versions".to_string()));
+            }
+        }
+    }
+
+    let version_files = [
+        (Toolchain::Node, ".nvmrc"),
+        (Toolchain::Node, ".node-version"),
+        (Toolchain::Python, ".python-version"),
+    ];
+    for (toolchain, file) in version_files {
+        if let Some(version) = read_version_file(&dir.join(file)) {
+            pinned.push((toolchain, version, file.to_string()));
+        }
+    }
+
+    if let Some((rust, file)) = RustToolchain::parse(dir) {
+        if let Some(channel) = rust.toolchain.channel {
+            pinned.push((Toolchain::Rust, channel, file.to_string()));
+        }
+    }
+
+    // The `go` directive is the oldest Go the module builds with
+    if let Some(version) = GoMod::parse(dir).and_then(|go_mod| go_mod.go_version) {
+        pinned.push((
+            Toolchain::Go,
+            format!(">={}", version),
+            "go.mod".to_string(),
+        ));
+    }
+
+    if let Some(range) = PackageJson::parse(dir)
+        .as_ref()
+        .and_then(|package| package.get_engine("node"))
+    {
+        pinned.push((
+            Toolchain::Node,
+            range.to_string(),
+            "package.json".to_string(),
+        ));
+    }
+
+    pinned
+}
+
+/// Toolchain versions a service needs: the closest version file between the
+/// service and the project root wins. Files above the service only count
+/// for toolchains the service is built with.
+pub fn detect_toolchains(path: &Path, root: &Path) -> Vec<ToolchainRequirement> {
+    let dirs: Vec<&Path> = if path.starts_with(root) {
+        path.ancestors()
+            .take_while(|dir| dir.starts_with(root))
+            .collect()
+    } else {
+        vec![path]
+    };
+
+    let mut requirements: Vec<ToolchainRequirement> = Vec::new();
+    for dir in dirs {
+        for (toolchain, required, file) in pinned_versions(dir) {
+            if requirements.iter().any(|r| r.toolchain == toolchain)
+                || (dir != path && !uses_toolchain(path, toolchain))
+            {
+                continue;
+            }
+
+            let source = dir.join(&file);
+            let source = source
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(file);
+            let installed = installed_version(toolchain, path);
+            let mismatch = match &installed {
+                Some(installed) => version_satisfies(&required, installed) == Some(false),
+                None => true,
+            };
+
+            requirements.push(ToolchainRequirement {
+                toolchain,
+                required,
+                source,
+                installed,
+                mismatch,
+            });
+        }
+    }
+
+    requirements
+}
+
+/// Version of a toolchain on the login shell's PATH as run from `dir`,
+/// e.g. `20.11.0`
+pub fn installed_version(toolchain: Toolchain, dir: &Path) -> Option<String> {
+    let key = (toolchain, dir.to_path_buf());
+    if let Some(version) = installed_versions().get(&key) {
+        return version.clone();
+    }
+
+    // Capturing the login shell and probing take seconds at worst, so the
+    // cache stays unlocked meanwhile; two scans may probe the same key
+    let version = probe_version(toolchain, dir, shell_environment().get("PATH"));
+    installed_versions().insert(key, version.clone());
+    version
+}
+
+/// The cache only holds finished probes, so a panic elsewhere leaves it usable
+fn installed_versions() -> MutexGuard<'static, VersionCache> {
+    INSTALLED_VERSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+fn probe_version(toolchain: Toolchain, dir: &Path, search_path: Option<&String>) -> Option<String> {
+    let probes: &[(&str, &str)] = match toolchain {
+        Toolchain::Node => &[("node", "--version")],
+        Toolchain::Python => &[("python3", "--version"), ("python", "--version")],
+        Toolchain::Rust => &[("rustc", "--version")],
+        Toolchain::Go => &[("go", "version")],
+    };
+
+    probes.iter().find_map(|(program, arg)| {
+        let mut probe = Command::new(program);
+        probe.current_dir(dir);
+        if let Some(path) = search_path {
+            probe.env("PATH", path);
+        }
+        let output = probe.arg(arg).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Python 2 prints its version to stderr
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        VERSION_REGEX.find(&text).map(|m| m.as_str().to_string())
+    })
+}
+
+/// Whether an installed version meets a requirement: an exact or partial
+/// version (`20`, `3.12.1`), an npm-style range (`>=18 <21 || ^22`) or an
+/// nvm LTS alias. `None` when the requirement names no version, like
+/// `stable` or `lts/*`.
+pub fn version_satisfies(required: &str, installed: &str) -> Option<bool> {
+    let installed = parse_version(installed)?;
+    let required = required.trim();
+    let required = required
+        .strip_prefix("lts/")
+        .and_then(|name| {
+            NODE_LTS_RELEASES
+                .iter()
+                .find(|(codename, _)| name.eq_ignore_ascii_case(codename))
+        })
+        .map(|(_, major)| *major)
+        .unwrap_or(required);
+
+    let mut satisfied = false;
+    for range in required.split("||") {
+        satisfied |= range_satisfied(range, &installed)?;
+    }
+    Some(satisfied)
+}
+
+fn range_satisfied(range: &str, installed: &[u64]) -> Option<bool> {
+    let range = range.replace(',', " ");
+
+    if let Some((low, high)) = range.split_once(" - ") {
+        return Some(
+            compare_prefix(installed, &parse_version(low)?) != Ordering::Less
+                && compare_prefix(installed, &parse_version(high)?) != Ordering::Greater,
+        );
+    }
+
+    let mut tokens = range.split_whitespace();
+    let mut satisfied = None;
+    while let Some(token) = tokens.next() {
+        // `>= 18` separates the operator from the version
+        let comparator = if token.chars().all(|c| "<>=^~".contains(c)) {
+            format!("{}{}", token, tokens.next()?)
+        } else {
+            token.to_string()
+        };
+        let matched = comparator_satisfied(&comparator, installed)?;
+        satisfied = Some(satisfied.unwrap_or(true) && matched);
+    }
+    satisfied
+}
+
+fn comparator_satisfied(comparator: &str, installed: &[u64]) -> Option<bool> {
+    let split = comparator
+        .find(|c: char| !"<>=^~".contains(c))
+        .unwrap_or(comparator.len());
+    let (operator, version) = comparator.split_at(split);
+    let version = parse_version(version)?;
+    let ordering = compare_prefix(installed, &version);
+
+    Some(match operator {
+        "" | "=" => ordering == Ordering::Equal,
+        ">=" => ordering != Ordering::Less,
+        ">" => ordering == Ordering::Greater,
+        "<=" => ordering != Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "^" => {
+            compare_prefix(installed, &version[..version.len().min(1)]) == Ordering::Equal
+                && ordering != Ordering::Less
+        }
+        "~" | "~>" => {
+            compare_prefix(installed, &version[..version.len().min(2)]) == Ordering::Equal
+                && ordering != Ordering::Less
+        }
+        _ => return None,
+    })
+}
+
+/// Numeric components of a version; `20.x` and `*` stop at the wildcard
+pub fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches(['v', '=']);
+    let mut parts = Vec::new();
+
+    for part in version.split('.') {
+        if matches!(part, "x" | "X" | "*") {
+            return Some(parts);
+        }
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        if digits.is_empty() {
+            break;
+        }
+        parts.push(digits.parse().ok()?);
+        // Pre-release and build suffixes (`0-rc.1`, `12t`)
+        if digits.len() < part.len() {
+            break;
+        }
+    }
+
+    (!parts.is_empty()).then_some(parts)
+}
+
+/// Compare only as many components as the requirement spells out, so
+/// `20.11.1` equals `20`
+fn compare_prefix(installed: &[u64], required: &[u64]) -> Ordering {
+    required
+        .iter()
+        .enumerate()
+        .map(|(i, part)| installed.get(i).copied().unwrap_or(0).cmp(part))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_version_satisfies() {
+        assert_eq!(version_satisfies("20", "20.11.1"), Some(true));
+        assert_eq!(version_satisfies("v20.11.1", "18.19.0"), Some(false));
+        assert_eq!(version_satisfies("3.12", "3.12.4"), Some(true));
+        assert_eq!(version_satisfies("1.77.2", "1.75.0"), Some(false));
+        assert_eq!(version_satisfies(">=1.22", "1.22.1"), Some(true));
+        assert_eq!(version_satisfies(">= 18 <21", "20.0.0"), Some(true));
+        assert_eq!(version_satisfies(">=18 <21", "21.1.0"), Some(false));
+        assert_eq!(
+            version_satisfies("^18.17.0 || >=20", "18.16.0"),
+            Some(false)
+        );
+        assert_eq!(version_satisfies("^18.17.0 || >=20", "22.1.0"), Some(true));
+        assert_eq!(version_satisfies("~3.11.2", "3.12.0"), Some(false));
+        assert_eq!(version_satisfies("20.x", "20.3.0"), Some(true));
+        assert_eq!(version_satisfies("16 - 18", "18.4.0"), Some(true));
+        assert_eq!(version_satisfies("lts/iron", "18.19.0"), Some(false));
+        assert_eq!(version_satisfies("lts/*", "20.11.1"), None);
+        assert_eq!(version_satisfies("stable", "1.77.2"), None);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v20.11.1"), Some(vec![20, 11, 1]));
+        assert_eq!(parse_version("3.13.0rc1"), Some(vec![3, 13, 0]));
+        assert_eq!(parse_version("1.0.0-beta.2"), Some(vec![1, 0, 0]));
+        assert_eq!(parse_version("20.x"), Some(vec![20]));
+        assert_eq!(parse_version("*"), Some(vec![]));
+        assert_eq!(parse_version("stable"), None);
+    }
+
+    #[test]
+    fn test_detect_toolchains() {
+        let fixture = Fixture::new(&[
+            (".tool-versions", "nodejs 18.19.0\npython 3.11.4\n"),
+            (".nvmrc", "20\n"),
+            ("web/package.json", r#"{"engines": {"node": ">=18"}}"#),
+            ("web/.node-version", "22.1.0\n"),
+            ("api/go.mod", "module example.com/api\n\ngo 1.22\n"),
+        ]);
+
+        // The closest file wins; the root's Python pin is ignored for a
+        // service without Python
+        let web = detect_toolchains(&fixture.join("web"), fixture.path());
+        assert_eq!(web.len(), 1);
+        assert_eq!(web[0].toolchain, Toolchain::Node);
+        assert_eq!(web[0].required, "22.1.0");
+        assert_eq!(web[0].source, "web/.node-version");
+
+        let api = detect_toolchains(&fixture.join("api"), fixture.path());
+        assert_eq!(api.len(), 1);
+        assert_eq!(api[0].toolchain, Toolchain::Go);
+        assert_eq!(api[0].required, ">=1.22");
+        assert_eq!(api[0].source, "api/go.mod");
+
+        // `.tool-versions` is listed before `.nvmrc` in the same directory
+        fixture.write("package.json", "{}");
+        let root = detect_toolchains(fixture.path(), fixture.path());
+        let node = root
+            .iter()
+            .find(|r| r.toolchain == Toolchain::Node)
+            .unwrap();
+        assert_eq!(node.required, "18.19.0");
+        assert_eq!(node.source, ".tool-versions");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_runs_in_service_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A fake `node` that answers like a shim reading the local pin; PATH
+        // holds only its directory, so it sticks to shell builtins
+        let fixture = Fixture::new(&[
+            ("one/.node-version", "18.19.0\n"),
+            ("two/.node-version", "20.11.1\n"),
+        ]);
+        let node = fixture.write(
+            "bin/node",
+            "#!/bin/sh\nread pin < .node-version && echo \"v$pin\"\n",
+        );
+        fs::set_permissions(&node, fs::Permissions::from_mode(0o755)).unwrap();
+        let search_path = fixture.join("bin").to_string_lossy().to_string();
+
+        let probe =
+            |dir: &str| probe_version(Toolchain::Node, &fixture.join(dir), Some(&search_path));
+        assert_eq!(probe("one").as_deref(), Some("18.19.0"));
+        assert_eq!(probe("two").as_deref(), Some("20.11.1"));
+        assert_eq!(probe("bin"), None);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::utils::scan_ignore::ScanIgnore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use walkdir::WalkDir;
+
+/// Dependency and build output directories that can be regenerated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ArtifactKind {
+    NodeModules,
+    Target,
+    Next,
+    Dist,
+    Pycache,
+    Gradle,
+}
+
+impl ArtifactKind {
+    pub const ALL: [ArtifactKind; 6] = [
+        ArtifactKind::NodeModules,
+        ArtifactKind::Target,
+        ArtifactKind::Next,
+        ArtifactKind::Dist,
+        ArtifactKind::Pycache,
+        ArtifactKind::Gradle,
+    ];
+
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            ArtifactKind::NodeModules => "node_modules",
+            ArtifactKind::Target => "target",
+            ArtifactKind::Next => ".next",
+            ArtifactKind::Dist => "dist",
+            ArtifactKind::Pycache => "__pycache__",
+            ArtifactKind::Gradle => ".gradle",
+        }
+    }
+
+    /// Whether a directory with this kind's name is build output rather
+    /// than sources that share the name: `target` and `dist` only count
+    /// next to the manifest of a tool that writes them
+    fn is_artifact(&self, dir: &Path) -> bool {
+        let beside = |files: &[&str]| {
+            dir.parent()
+                .is_some_and(|parent| files.iter().any(|file| parent.join(file).exists()))
+        };
+
+        match self {
+            ArtifactKind::Target => beside(&["Cargo.toml", "pom.xml"]),
+            ArtifactKind::Next => beside(&["package.json"]),
+            ArtifactKind::Dist => beside(&["package.json", "pyproject.toml", "setup.py"]),
+            ArtifactKind::NodeModules | ArtifactKind::Pycache | ArtifactKind::Gradle => true,
+        }
+    }
+}
+
+/// An artifact directory and the space it takes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    pub kind: ArtifactKind,
+    pub path: String,
+    /// Bytes, summed over files; hard links shared with a package store
+    /// (pnpm) count in full
+    pub size: u64,
+    pub files: u64,
+}
+
+/// How `clean_project` gets rid of artifacts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CleanMode {
+    /// Move to the system trash, so they can be restored
+    #[default]
+    Trash,
+    Delete,
+}
+
+/// Artifact directories under a project, largest first, or `None` when
+/// cancelled before every size was known. Artifacts are not searched for
+/// nested ones; other folders are skipped when `ignore` excludes them.
+/// Directories tracked by git are sources, whatever their name.
+pub fn find_artifacts(
+    root: &Path,
+    ignore: &ScanIgnore,
+    cancelled: &AtomicBool,
+) -> Option<Vec<Artifact>> {
+    let mut artifacts = Vec::new();
+    let mut entries = WalkDir::new(root).follow_links(false).into_iter();
+
+    while let Some(entry) = entries.next() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if entry.depth() == 0 || !entry.file_type().is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy();
+        if name == ".git" {
+            entries.skip_current_dir();
+            continue;
+        }
+
+        let kind = ArtifactKind::ALL
+            .into_iter()
+            .find(|kind| kind.dir_name() == name && kind.is_artifact(entry.path()));
+        let Some(kind) = kind.filter(|_| !is_tracked(entry.path())) else {
+            if ignore.is_ignored(entry.path()) {
+                entries.skip_current_dir();
+            }
+            continue;
+        };
+
+        let (size, files) = directory_size(entry.path(), cancelled);
+        artifacts.push(Artifact {
+            kind,
+            path: entry.path().to_string_lossy().to_string(),
+            size,
+            files,
+        });
+        entries.skip_current_dir();
+    }
+
+    if cancelled.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    artifacts.sort_by_key(|artifact| std::cmp::Reverse(artifact.size));
+    Some(artifacts)
+}
+
+/// Whether git tracks any file under the directory. Outside a repository,
+/// or without git, nothing is.
+fn is_tracked(dir: &Path) -> bool {
+    Command::new("git")
+        .args(["ls-files", "--", "."])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+/// Total bytes and number of files in a directory, symlinks not followed
+fn directory_size(path: &Path, cancelled: &AtomicBool) -> (u64, u64) {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .take_while(|_| !cancelled.load(Ordering::Relaxed))
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .fold((0, 0), |(size, files), metadata| {
+            (size + metadata.len(), files + 1)
+        })
+}
+
+pub fn remove_artifact(artifact: &Artifact, mode: CleanMode) -> Result<(), String> {
+    match mode {
+        CleanMode::Trash => trash::delete(&artifact.path).map_err(|e| e.to_string()),
+        CleanMode::Delete => fs::remove_dir_all(&artifact.path).map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_find_artifacts() {
+        let fixture = Fixture::new(&[
+            ("apps/web/package.json", "{}"),
+            ("apps/web/node_modules/vite/index.js", &"x".repeat(300)),
+            ("apps/web/dist/index.html", &"x".repeat(100)),
+            ("docs/dist/guide.md", "x"),
+            ("api/target/debug/api", "x"),
+        ]);
+        fixture.mkdir("apps/web/node_modules/vite/node_modules/esbuild");
+        let web = fixture.join("apps/web");
+
+        let ignore = ScanIgnore::new(fixture.path(), &[]);
+        let artifacts = find_artifacts(fixture.path(), &ignore, &AtomicBool::new(false)).unwrap();
+        let kinds: Vec<ArtifactKind> = artifacts.iter().map(|a| a.kind).collect();
+
+        // `docs/dist` and `api/target` have no manifest next to them
+        assert_eq!(kinds, [ArtifactKind::NodeModules, ArtifactKind::Dist]);
+        assert_eq!(artifacts[0].size, 300);
+        assert_eq!(artifacts[0].files, 1);
+
+        remove_artifact(&artifacts[1], CleanMode::Delete).unwrap();
+        assert!(!web.join("dist").exists());
+
+        // Ignored folders are not walked, but artifacts are found whatever
+        // the patterns say about their own names
+        let ignore = ScanIgnore::new(fixture.path(), &["apps".to_string()]);
+        let artifacts = find_artifacts(fixture.path(), &ignore, &AtomicBool::new(false)).unwrap();
+        assert!(artifacts.is_empty());
+        let ignore = ScanIgnore::new(fixture.path(), &["node_modules".to_string()]);
+        let artifacts = find_artifacts(fixture.path(), &ignore, &AtomicBool::new(false)).unwrap();
+        assert_eq!(artifacts.len(), 1);
+
+        assert!(find_artifacts(fixture.path(), &ignore, &AtomicBool::new(true)).is_none());
+    }
+
+    #[test]
+    fn test_tracked_artifacts_are_skipped() {
+        let fixture = Fixture::new(&[
+            ("package.json", "{}"),
+            ("dist/index.js", "x"),
+            ("node_modules/vite/index.js", "x"),
+            (".gitignore", "node_modules\n"),
+        ]);
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(fixture.path())
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"]) {
+            return;
+        }
+        assert!(git(&["add", "dist"]));
+
+        let ignore = ScanIgnore::new(fixture.path(), &[]);
+        let artifacts = find_artifacts(fixture.path(), &ignore, &AtomicBool::new(false)).unwrap();
+        let kinds: Vec<ArtifactKind> = artifacts.iter().map(|a| a.kind).collect();
+        assert_eq!(kinds, [ArtifactKind::NodeModules]);
+    }
+}
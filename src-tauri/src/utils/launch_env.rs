@@ -0,0 +1,360 @@
+use crate::utils::detectors::{parse_version, version_satisfies};
+use crate::utils::parsers::MiseToml;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Longest the login shell may take to print its environment
+#[cfg(not(target_os = "windows"))]
+const SHELL_ENV_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Surrounds `env -0` output so banners printed by rc files are skipped
+#[cfg(not(target_os = "windows"))]
+const ENV_MARKER: &str = "__WARDEN_ENV__";
+
+/// Variables describing the capturing shell rather than the user's setup
+#[cfg(not(target_os = "windows"))]
+const SHELL_SESSION_VARS: [&str; 4] = ["SHLVL", "PWD", "OLDPWD", "_"];
+
+static SHELL_ENVIRONMENT: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Version manager a service command runs through
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionManager {
+    Mise,
+    Asdf,
+    Nvm,
+    Pyenv,
+}
+
+/// A service command ready to spawn with `sh -c` (`cmd /C` on Windows)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchCommand {
+    pub command: String,
+    pub version_manager: Option<VersionManager>,
+    /// Directory put first on PATH for the version manager, if any
+    pub path_prefix: Option<String>,
+    /// Full environment to spawn the command with. It holds whatever the
+    /// login shell exports, secrets included, so it never leaves the backend.
+    #[serde(skip)]
+    pub env: HashMap<String, String>,
+}
+
+impl LaunchCommand {
+    /// The command run by the platform shell in `dir`, with exactly the
+    /// resolved environment
+    pub fn to_command(&self, dir: &Path) -> Command {
+        #[cfg(target_os = "windows")]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.args(["/C", &self.command]);
+            command
+        };
+        #[cfg(not(target_os = "windows"))]
+        let mut command = {
+            let mut command = Command::new("sh");
+            command.args(["-c", &self.command]);
+            command
+        };
+        command.current_dir(dir).env_clear().envs(&self.env);
+        command
+    }
+}
+
+/// Pass each line of a child's output on as it comes. Invalid UTF-8 is
+/// replaced rather than ending the stream, as `BufRead::lines` would.
+pub fn read_lines(output: impl Read, on_line: &mut dyn FnMut(String)) {
+    let mut reader = BufReader::new(output);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        let text = String::from_utf8_lossy(&line);
+        on_line(text.trim_end_matches(['\n', '\r']).to_string());
+    }
+}
+
+/// The user's login-shell environment, captured on first use.
+///
+/// GUI applications do not inherit the PATH set up in `.zshrc` / `.bashrc`
+/// (see `commands/ide.rs`), so version managers and the runtimes they
+/// install are missing from our own environment.
+pub fn shell_environment() -> &'static HashMap<String, String> {
+    SHELL_ENVIRONMENT.get_or_init(|| {
+        let mut environment: HashMap<String, String> = env::vars().collect();
+        #[cfg(not(target_os = "windows"))]
+        if let Some(captured) = capture_shell_environment() {
+            environment.extend(captured);
+        }
+        environment
+    })
+}
+
+/// Run `<shell> -l -i -c 'env -0'` and parse what it prints. `None` when the
+/// shell fails or hangs, e.g. on a prompt in an rc file.
+#[cfg(not(target_os = "windows"))]
+fn capture_shell_environment() -> Option<HashMap<String, String>> {
+    use std::io::Read;
+    use std::process::Stdio;
+    use std::sync::mpsc;
+
+    let shell = env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let script = format!("printf '{0}'; env -0; printf '{0}'", ENV_MARKER);
+    let mut child = Command::new(&shell)
+        .args(["-l", "-i", "-c", &script])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let mut stdout = child.stdout.take()?;
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).ok();
+        sender.send(output).ok();
+    });
+
+    let output = match receiver.recv_timeout(SHELL_ENV_TIMEOUT) {
+        Ok(output) => output,
+        Err(_) => {
+            child.kill().ok();
+            child.wait().ok();
+            return None;
+        }
+    };
+    child.wait().ok();
+
+    let output = String::from_utf8_lossy(&output);
+    let start = output.find(ENV_MARKER)? + ENV_MARKER.len();
+    let end = start + output[start..].find(ENV_MARKER)?;
+
+    Some(
+        output[start..end]
+            .split('\0')
+            .filter_map(|entry| entry.split_once('='))
+            .filter(|(key, _)| !key.is_empty() && !SHELL_SESSION_VARS.contains(key))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+/// Wraps a string in single quotes and escapes any contained single quotes,
+/// producing a safe POSIX shell word even for paths with spaces.
+#[cfg(not(target_os = "windows"))]
+pub fn shell_escape(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Wrap a service command so the version manager pinned by the closest
+/// version file between the service and the project root selects the
+/// runtime: `mise exec`, `asdf exec` or `pyenv exec`, or the nvm-installed
+/// Node put first on PATH.
+pub fn resolve_launch_command(path: &Path, root: &Path, command: &str) -> LaunchCommand {
+    let mut environment = shell_environment().clone();
+    let search_path = environment.get("PATH").cloned().unwrap_or_default();
+
+    let dirs: Vec<&Path> = if path.starts_with(root) {
+        path.ancestors()
+            .take_while(|dir| dir.starts_with(root))
+            .collect()
+    } else {
+        vec![path]
+    };
+
+    let Some((manager, bin_dir)) = dirs
+        .into_iter()
+        .find_map(|dir| pick_version_manager(dir, &environment, &search_path))
+    else {
+        return LaunchCommand {
+            command: command.to_string(),
+            version_manager: None,
+            path_prefix: None,
+            env: environment,
+        };
+    };
+
+    let shims = bin_dir.clone();
+    let path_prefix = bin_dir
+        .as_ref()
+        .map(|dir| dir.to_string_lossy().to_string());
+    let mut paths: Vec<PathBuf> = env::split_paths(&search_path).collect();
+    if let Some(bin_dir) = bin_dir {
+        paths.insert(0, bin_dir);
+    }
+    if let Ok(joined) = env::join_paths(paths) {
+        environment.insert("PATH".to_string(), joined.to_string_lossy().to_string());
+    }
+
+    let command = match manager {
+        VersionManager::Mise => format!("mise exec -- {}", as_single_program(command)),
+        // Shims only exist for programs the manager installed; anything
+        // else still finds the right runtime through the shims on PATH
+        VersionManager::Asdf | VersionManager::Pyenv => {
+            let program = command.split_whitespace().next().unwrap_or_default();
+            let name = if manager == VersionManager::Asdf {
+                "asdf"
+            } else {
+                "pyenv"
+            };
+            if !is_compound(command) && shims.is_some_and(|s| s.join(program).exists()) {
+                format!("{} exec {}", name, command)
+            } else {
+                command.to_string()
+            }
+        }
+        VersionManager::Nvm => command.to_string(),
+    };
+
+    LaunchCommand {
+        command,
+        version_manager: Some(manager),
+        path_prefix,
+        env: environment,
+    }
+}
+
+/// The manager honouring the version files of one directory, with a
+/// directory to put first on PATH
+fn pick_version_manager(
+    dir: &Path,
+    environment: &HashMap<String, String>,
+    search_path: &str,
+) -> Option<(VersionManager, Option<PathBuf>)> {
+    let has_mise = find_program(search_path, "mise").is_some();
+    let shims = |manager| manager_home(manager, environment).map(|home| home.join("shims"));
+
+    if has_mise && MiseToml::parse(dir).is_some() {
+        return Some((VersionManager::Mise, None));
+    }
+
+    if dir.join(".tool-versions").exists() {
+        if find_program(search_path, "asdf").is_some() {
+            return Some((VersionManager::Asdf, shims(VersionManager::Asdf)));
+        }
+        // mise reads asdf's file as well
+        if has_mise {
+            return Some((VersionManager::Mise, None));
+        }
+    }
+
+    for file in [".nvmrc", ".node-version"] {
+        let Some(spec) = fs::read_to_string(dir.join(file)).ok() else {
+            continue;
+        };
+        if let Some(bin) = nvm_node_bin(spec.trim(), environment) {
+            return Some((VersionManager::Nvm, Some(bin)));
+        }
+    }
+
+    if dir.join(".python-version").exists() && find_program(search_path, "pyenv").is_some() {
+        return Some((VersionManager::Pyenv, shims(VersionManager::Pyenv)));
+    }
+
+    None
+}
+
+/// Where a version manager keeps its installs and shims
+fn manager_home(manager: VersionManager, environment: &HashMap<String, String>) -> Option<PathBuf> {
+    let (variable, default) = match manager {
+        VersionManager::Asdf => ("ASDF_DATA_DIR", ".asdf"),
+        VersionManager::Pyenv => ("PYENV_ROOT", ".pyenv"),
+        VersionManager::Nvm => ("NVM_DIR", ".nvm"),
+        VersionManager::Mise => return None,
+    };
+    environment
+        .get(variable)
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(default)))
+}
+
+/// `bin` directory of the newest nvm-installed Node matching `.nvmrc`.
+/// nvm is a shell function, so there is no program to exec through.
+fn nvm_node_bin(spec: &str, environment: &HashMap<String, String>) -> Option<PathBuf> {
+    let versions = manager_home(VersionManager::Nvm, environment)?.join("versions/node");
+    let newest = matches!(spec, "node" | "stable" | "latest");
+
+    fs::read_dir(versions)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let satisfied = newest || version_satisfies(spec, &name) == Some(true);
+            satisfied.then(|| (parse_version(&name), entry.path().join("bin")))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, bin)| bin)
+}
+
+/// Executable on a PATH-style search path
+pub fn find_program(search_path: &str, name: &str) -> Option<PathBuf> {
+    let names: Vec<String> = if cfg!(target_os = "windows") {
+        vec![format!("{}.exe", name), format!("{}.cmd", name)]
+    } else {
+        vec![name.to_string()]
+    };
+    env::split_paths(search_path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Whether a command needs a shell: pipes, `&&`, redirects, variables...
+fn is_compound(command: &str) -> bool {
+    let program = command.split_whitespace().next().unwrap_or_default();
+    command.contains(['&', '|', ';', '<', '>', '$', '`']) || program.contains('=')
+}
+
+/// A command a version manager can exec directly
+fn as_single_program(command: &str) -> String {
+    if !is_compound(command) {
+        return command.to_string();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        format!("cmd /C {}", command)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        format!("sh -c {}", shell_escape(command))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_nvm_node_bin_picks_newest_match() {
+        let fixture = Fixture::new(&[]);
+        for version in ["v18.19.0", "v20.9.0", "v20.11.1", "v22.2.0"] {
+            fixture.mkdir(&format!("versions/node/{}/bin", version));
+        }
+        let nvm = fixture.path();
+        let environment =
+            HashMap::from([("NVM_DIR".to_string(), nvm.to_string_lossy().to_string())]);
+        let bin = |spec| nvm_node_bin(spec, &environment);
+
+        assert_eq!(bin("20"), Some(nvm.join("versions/node/v20.11.1/bin")));
+        assert_eq!(
+            bin("lts/hydrogen"),
+            Some(nvm.join("versions/node/v18.19.0/bin"))
+        );
+        assert_eq!(bin("node"), Some(nvm.join("versions/node/v22.2.0/bin")));
+        assert_eq!(bin("16"), None);
+
+        assert_eq!(as_single_program("npm run dev"), "npm run dev");
+        assert!(is_compound("PORT=3000 npm run dev"));
+    }
+}
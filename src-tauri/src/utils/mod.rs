@@ -1,3 +1,10 @@
 pub mod detectors;
+pub mod disk_usage;
+pub mod launch_env;
 pub mod parsers;
 pub mod project_scanner;
+pub mod scaffold;
+pub mod scan_ignore;
+#[cfg(test)]
+pub mod test_fixtures;
+pub mod workspace_scan;
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Parsed air (Go live reload) configuration
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct AirToml {
+    pub root: Option<String>,
+    pub build: Option<AirBuild>,
+    /// File name the config was read from (`.air.toml` or `air.toml`)
+    #[serde(skip)]
+    pub file_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct AirBuild {
+    pub cmd: Option<String>,
+    pub bin: Option<String>,
+}
+
+impl AirToml {
+    /// Parse `.air.toml` or `air.toml` from a directory. `None` when there is
+    /// neither; an error, naming the file, when it is not valid TOML.
+    pub fn parse(path: &Path) -> Option<Result<Self, String>> {
+        [".air.toml", "air.toml"].into_iter().find_map(|file_name| {
+            let content = fs::read_to_string(path.join(file_name)).ok()?;
+            Some(Self::parse_str(&content, file_name))
+        })
+    }
+
+    pub fn parse_str(content: &str, file_name: &str) -> Result<Self, String> {
+        let mut config: AirToml =
+            toml::from_str(content).map_err(|e| format!("{}: {}", file_name, e.message()))?;
+        config.file_name = file_name.to_string();
+        Ok(config)
+    }
+
+    /// Command that starts air with this config
+    pub fn get_dev_command(&self) -> String {
+        if self.file_name == ".air.toml" {
+            "air".to_string()
+        } else {
+            format!("air -c {}", self.file_name)
+        }
+    }
+
+    /// Check whether the configured build command targets a package
+    /// (e.g. `./cmd/api`)
+    pub fn builds_package(&self, package: &str) -> bool {
+        self.build
+            .as_ref()
+            .and_then(|b| b.cmd.as_ref())
+            .is_some_and(|cmd| {
+                cmd.split_whitespace()
+                    .any(|arg| arg.trim_end_matches('/') == package)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_parse_air_toml() {
+        let fixture = Fixture::new(&[(
+            "air.toml",
+            "root = \".\"\n[build]\ncmd = \"go build -o ./tmp/api ./cmd/api/\"\nbin = \"tmp/api\"\n",
+        )]);
+
+        let air = AirToml::parse(fixture.path()).unwrap().unwrap();
+        assert_eq!(air.get_dev_command(), "air -c air.toml");
+        assert!(air.builds_package("./cmd/api"));
+        assert!(!air.builds_package("./cmd/worker"));
+
+        fixture.write(".air.toml", "[build\ncmd = 1\n");
+        let error = AirToml::parse(fixture.path()).unwrap().unwrap_err();
+        assert!(error.starts_with(".air.toml: "));
+
+        assert!(AirToml::parse(&fixture.join("cmd")).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `${PORT:8081}` -> `8081`
+static PLACEHOLDER_DEFAULT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\$\{[^:}]+:([^}]*)\}$").unwrap());
+
+/// Keys holding the HTTP port, per framework
+const PORT_KEYS: [&str; 6] = [
+    "server.port",
+    "quarkus.http.port",
+    "micronaut.server.port",
+    "ktor.deployment.port",
+    "play.server.http.port",
+    "http.port",
+];
+
+/// Flattened Spring/Quarkus/Micronaut/Ktor/Play application configuration
+/// from `src/main/resources` (or `conf` for Play)
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationConfig {
+    pub properties: HashMap<String, String>,
+}
+
+impl ApplicationConfig {
+    /// Parse application.properties / application.yml / application.conf of
+    /// a JVM module. Properties win over YAML, like in Spring Boot.
+    pub fn parse(path: &Path) -> Option<Self> {
+        let resources = path.join("src").join("main").join("resources");
+        let mut properties: HashMap<String, String> = HashMap::new();
+        let mut found = false;
+
+        for file in ["application.yaml", "application.yml"] {
+            if let Ok(content) = fs::read_to_string(resources.join(file)) {
+                found = true;
+
+                // Multi-document files: later documents are profile overrides
+                for document in serde_yaml::Deserializer::from_str(&content) {
+                    let Ok(yaml) = serde_yaml::Value::deserialize(document) else {
+                        break;
+                    };
+                    let mut flattened = HashMap::new();
+                    flatten_yaml("", &yaml, &mut flattened);
+
+                    if !flattened.keys().any(|k| k.contains("on-profile")) {
+                        for (key, value) in flattened {
+                            properties.entry(key).or_insert(value);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Ok(content) = fs::read_to_string(resources.join("application.properties")) {
+            properties.extend(parse_properties(&content));
+            found = true;
+        }
+
+        let conf = [
+            resources.join("application.conf"),
+            path.join("conf").join("application.conf"),
+        ]
+        .into_iter()
+        .find_map(|file| fs::read_to_string(file).ok());
+        if let Some(content) = conf {
+            for (key, value) in parse_hocon(&content) {
+                properties.entry(key).or_insert(value);
+            }
+            found = true;
+        }
+
+        found.then_some(ApplicationConfig { properties })
+    }
+
+    /// Look up a property, resolving `${ENV:default}` placeholders to their
+    /// default value
+    pub fn get(&self, key: &str) -> Option<String> {
+        let value = self.properties.get(key)?.trim();
+
+        match PLACEHOLDER_DEFAULT_REGEX.captures(value) {
+            Some(c) => Some(c[1].to_string()),
+            None if value.starts_with("${") => None,
+            None => Some(value.to_string()),
+        }
+    }
+
+    /// HTTP port configured for the application
+    pub fn get_port(&self) -> Option<u16> {
+        PORT_KEYS
+            .iter()
+            .find_map(|key| self.get(key).and_then(|v| v.parse::<u16>().ok()))
+            .filter(|port| *port > 0)
+    }
+}
+
+fn parse_properties(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| {
+            let split = line.find(['=', ':'])?;
+            Some((
+                line[..split].trim().to_string(),
+                line[split + 1..].trim().to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Flatten the `key = value` settings of a HOCON file, following `{ }`
+/// blocks and dotted keys. A later value wins, except substitutions such as
+/// `${?PORT}`, which only apply when the variable is set.
+fn parse_hocon(content: &str) -> HashMap<String, String> {
+    let mut scope: Vec<String> = Vec::new();
+    let mut settings = HashMap::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        if line.starts_with('}') {
+            scope.pop();
+            continue;
+        }
+        if let Some(key) = line.strip_suffix('{') {
+            scope.push(key.trim_end_matches(['=', ':', ' ']).trim().to_string());
+            continue;
+        }
+
+        let Some(split) = line.find(['=', ':']) else {
+            continue;
+        };
+        let value = line[split + 1..].trim().trim_matches('"');
+        if value.starts_with("${") {
+            continue;
+        }
+        let key = line[..split].trim().trim_matches('"');
+        let full_key = scope
+            .iter()
+            .map(String::as_str)
+            .chain([key])
+            .collect::<Vec<_>>()
+            .join(".");
+        settings.insert(full_key, value.to_string());
+    }
+
+    settings
+}
+
+fn flatten_yaml(prefix: &str, value: &serde_yaml::Value, out: &mut HashMap<String, String>) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            for (key, child) in map {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let full_key = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_yaml(&full_key, child, out);
+            }
+        }
+        serde_yaml::Value::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        serde_yaml::Value::Number(n) => {
+            out.insert(prefix.to_string(), n.to_string());
+        }
+        serde_yaml::Value::Bool(b) => {
+            out.insert(prefix.to_string(), b.to_string());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_spring_config() {
+        let fixture = Fixture::new(&[
+            (
+                "src/main/resources/application.yml",
+                "server:\n  port: ${PORT:8081}\nspring:\n  application:\n    name: shop\n---\nspring:\n  config:\n    activate:\n      on-profile: prod\nserver:\n  port: 80\n",
+            ),
+            (
+                "src/main/resources/application.properties",
+                "# overrides\nspring.application.name = orders\n",
+            ),
+        ]);
+
+        let config = ApplicationConfig::parse(fixture.path()).unwrap();
+        assert_eq!(config.get_port(), Some(8081));
+        assert_eq!(
+            config.get("spring.application.name").as_deref(),
+            Some("orders")
+        );
+        assert!(ApplicationConfig::parse(&fixture.join("src")).is_none());
+    }
+
+    #[test]
+    fn test_hocon_config() {
+        let ktor = Fixture::new(&[(
+            "src/main/resources/application.conf",
+            r#"
+database {
+    port = 5432
+}
+ktor {
+    deployment {
+        port = 8085
+        port = ${?PORT}
+    }
+}
+"#,
+        )]);
+        assert_eq!(
+            ApplicationConfig::parse(ktor.path()).unwrap().get_port(),
+            Some(8085)
+        );
+
+        let play = Fixture::new(&[(
+            "conf/application.conf",
+            "db.default.port = 5432\nplay.server.http.port = 9001\n",
+        )]);
+        assert_eq!(
+            ApplicationConfig::parse(play.path()).unwrap().get_port(),
+            Some(9001)
+        );
+
+        let unrelated = Fixture::new(&[(
+            "src/main/resources/application.conf",
+            "redis {\n  port = 6379\n}\n",
+        )]);
+        assert_eq!(
+            ApplicationConfig::parse(unrelated.path())
+                .unwrap()
+                .get_port(),
+            None
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
@@ -14,6 +14,9 @@ pub struct CargoToml {
     #[serde(rename = "build-dependencies")]
     pub build_dependencies: HashMap<String, toml::Value>,
     pub workspace: Option<CargoWorkspace>,
+    pub features: HashMap<String, Vec<String>>,
+    pub bin: Vec<CargoTarget>,
+    pub example: Vec<CargoTarget>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -24,6 +27,20 @@ pub struct CargoPackage {
     pub description: Option<String>,
     pub authors: Option<Vec<String>>,
     pub edition: Option<String>,
+    pub autobins: Option<bool>,
+    pub autoexamples: Option<bool>,
+    #[serde(rename = "default-run")]
+    pub default_run: Option<String>,
+}
+
+/// A `[[bin]]` or `[[example]]` target, explicit or inferred from the layout
+#[derive(Debug, Clone, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct CargoTarget {
+    pub name: Option<String>,
+    pub path: Option<String>,
+    #[serde(rename = "required-features")]
+    pub required_features: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -55,22 +72,107 @@ impl CargoToml {
             || self.build_dependencies.contains_key(name)
     }
 
-    #[allow(dead_code)]
     pub fn get_name(&self) -> Option<String> {
         self.package.as_ref().and_then(|p| p.name.clone())
     }
 
-    #[allow(dead_code)]
     pub fn is_workspace(&self) -> bool {
         self.workspace.is_some()
     }
 
-    #[allow(dead_code)]
-    pub fn get_workspace_members(&self) -> Vec<String> {
-        self.workspace
-            .as_ref()
-            .map(|w| w.members.clone())
-            .unwrap_or_default()
+    /// Features enabled by `default`, including the ones they enable in turn
+    pub fn get_default_features(&self) -> HashSet<String> {
+        let mut enabled = HashSet::new();
+        let mut pending = vec!["default".to_string()];
+
+        while let Some(feature) = pending.pop() {
+            let Some(implied) = self.features.get(&feature) else {
+                continue;
+            };
+            // `dep:name` and `crate/feature` enable dependencies, not our features
+            for name in implied {
+                if !name.contains(':') && !name.contains('/') && enabled.insert(name.clone()) {
+                    pending.push(name.clone());
+                }
+            }
+        }
+
+        enabled
+    }
+
+    /// Required features of a target that `default` does not already enable
+    pub fn get_missing_features(&self, target: &CargoTarget) -> Vec<String> {
+        let defaults = self.get_default_features();
+        target
+            .required_features
+            .iter()
+            .filter(|feature| !defaults.contains(*feature))
+            .cloned()
+            .collect()
+    }
+
+    /// Runnable binaries: `[[bin]]` targets plus the ones Cargo infers from
+    /// `src/main.rs` and `src/bin/` (unless `autobins = false`).
+    pub fn get_binaries(&self, dir: &Path) -> Vec<CargoTarget> {
+        let Some(package) = &self.package else {
+            return Vec::new();
+        };
+        let package_name = package.name.clone().unwrap_or_default();
+
+        let mut targets: Vec<CargoTarget> = self
+            .bin
+            .iter()
+            .map(|bin| CargoTarget {
+                name: bin.name.clone().or_else(|| Some(package_name.clone())),
+                ..bin.clone()
+            })
+            .collect();
+
+        if package.autobins != Some(false) {
+            let mut inferred = Vec::new();
+            if dir.join("src").join("main.rs").is_file() {
+                inferred.push((package_name.clone(), "src/main.rs".to_string()));
+            }
+            inferred.extend(infer_targets(dir, "src/bin"));
+
+            for (name, path) in inferred {
+                let declared = targets.iter().any(|t| {
+                    t.name.as_deref() == Some(name.as_str()) || t.path.as_deref() == Some(&path)
+                });
+                if !declared {
+                    targets.push(CargoTarget {
+                        name: Some(name),
+                        path: Some(path),
+                        required_features: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        targets
+    }
+
+    /// `[[example]]` targets plus the ones inferred from `examples/`
+    pub fn get_examples(&self, dir: &Path) -> Vec<CargoTarget> {
+        let mut targets = self.example.clone();
+
+        if self.package.as_ref().and_then(|p| p.autoexamples) != Some(false) {
+            for (name, path) in infer_targets(dir, "examples") {
+                if !targets
+                    .iter()
+                    .any(|t| t.name.as_deref() == Some(name.as_str()))
+                {
+                    targets.push(CargoTarget {
+                        name: Some(name),
+                        path: Some(path),
+                        required_features: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        targets.retain(|t| t.name.is_some());
+        targets
     }
 
     /// Check if this is a Tauri project
@@ -95,30 +197,6 @@ impl CargoToml {
         web_deps.iter().any(|dep| self.has_dependency(dep))
     }
 
-    #[allow(dead_code)]
-    pub fn detect_web_framework(&self) -> Option<&'static str> {
-        if self.has_dependency("actix-web") {
-            Some("actix-web")
-        } else if self.has_dependency("axum") {
-            Some("axum")
-        } else if self.has_dependency("rocket") {
-            Some("rocket")
-        } else if self.has_dependency("warp") {
-            Some("warp")
-        } else if self.has_dependency("tide") {
-            Some("tide")
-        } else {
-            None
-        }
-    }
-
-    #[allow(dead_code)]
-    pub fn is_cli(&self) -> bool {
-        let cli_deps = ["clap", "structopt", "argh", "pico-args"];
-
-        cli_deps.iter().any(|dep| self.has_dependency(dep))
-    }
-
     /// Get default dev command for Rust projects
     pub fn get_dev_command(&self) -> String {
         if self.is_tauri() {
@@ -139,3 +217,141 @@ impl CargoToml {
         }
     }
 }
+
+/// Targets Cargo infers from a directory: `<dir>/<name>.rs` and
+/// `<dir>/<name>/main.rs`, sorted by name.
+fn infer_targets(root: &Path, dir: &str) -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir(root.join(dir)) else {
+        return Vec::new();
+    };
+
+    let mut targets: Vec<(String, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            if path.is_file() {
+                let name = file_name.strip_suffix(".rs")?;
+                Some((name.to_string(), format!("{}/{}", dir, file_name)))
+            } else if path.join("main.rs").is_file() {
+                Some((file_name.clone(), format!("{}/{}/main.rs", dir, file_name)))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    targets.sort();
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    fn parse(content: &str) -> CargoToml {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_infer_targets() {
+        let fixture = Fixture::new(&[
+            ("src/bin/worker.rs", ""),
+            ("src/bin/admin/main.rs", ""),
+            ("src/bin/helpers/mod.rs", ""),
+            ("src/bin/README.md", ""),
+        ]);
+
+        assert_eq!(
+            infer_targets(fixture.path(), "src/bin"),
+            vec![
+                ("admin".to_string(), "src/bin/admin/main.rs".to_string()),
+                ("worker".to_string(), "src/bin/worker.rs".to_string()),
+            ]
+        );
+        assert!(infer_targets(fixture.path(), "examples").is_empty());
+    }
+
+    #[test]
+    fn test_get_binaries() {
+        let fixture = Fixture::new(&[
+            ("src/main.rs", ""),
+            ("src/bin/worker.rs", ""),
+            ("src/bin/migrate.rs", ""),
+        ]);
+        let cargo = parse(
+            r#"
+[package]
+name = "api"
+
+[[bin]]
+name = "migrate"
+path = "src/bin/migrate.rs"
+required-features = ["db"]
+"#,
+        );
+
+        let binaries = cargo.get_binaries(fixture.path());
+        let names: Vec<_> = binaries.iter().filter_map(|b| b.name.as_deref()).collect();
+        assert_eq!(names, vec!["migrate", "api", "worker"]);
+        assert_eq!(binaries[0].required_features, vec!["db"]);
+
+        let no_autobins = parse("[package]\nname = \"api\"\nautobins = false\n");
+        assert!(no_autobins.get_binaries(fixture.path()).is_empty());
+        assert!(parse("[workspace]\nmembers = [\"api\"]\n")
+            .get_binaries(fixture.path())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_examples() {
+        let fixture = Fixture::new(&[("examples/hello.rs", ""), ("examples/chat/main.rs", "")]);
+        let cargo = parse(
+            r#"
+[package]
+name = "api"
+
+[[example]]
+name = "hello"
+required-features = ["cli"]
+"#,
+        );
+
+        let examples = cargo.get_examples(fixture.path());
+        let names: Vec<_> = examples.iter().filter_map(|e| e.name.as_deref()).collect();
+        assert_eq!(names, vec!["hello", "chat"]);
+        assert_eq!(examples[0].required_features, vec!["cli"]);
+
+        let no_autoexamples = parse("[package]\nname = \"api\"\nautoexamples = false\n");
+        assert!(no_autoexamples.get_examples(fixture.path()).is_empty());
+    }
+
+    #[test]
+    fn test_missing_features() {
+        let cargo = parse(
+            r#"
+[package]
+name = "api"
+
+[features]
+default = ["server"]
+server = ["tls", "dep:axum"]
+tls = ["rustls/ring"]
+db = []
+"#,
+        );
+
+        let defaults = cargo.get_default_features();
+        assert!(defaults.contains("server") && defaults.contains("tls"));
+        assert!(!defaults.contains("db"));
+
+        let target = CargoTarget {
+            name: Some("migrate".to_string()),
+            path: None,
+            required_features: vec!["tls".to_string(), "db".to_string()],
+        };
+        assert_eq!(cargo.get_missing_features(&target), vec!["db"]);
+    }
+}
@@ -0,0 +1,168 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PROJECT_SDK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<Project\s[^>]*Sdk="([^"]+)""#).unwrap());
+
+static SDK_ELEMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<Sdk\s+Name="([^"]+)""#).unwrap());
+
+static PACKAGE_REFERENCE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<PackageReference\s+Include="([^"]+)""#).unwrap());
+
+/// Kind of .NET project, derived from its SDK and properties
+#[derive(Debug, Clone, PartialEq)]
+pub enum DotnetProjectKind {
+    Web,
+    Blazor,
+    Worker,
+    Console,
+    Library,
+    Test,
+}
+
+/// Parsed .csproj / .fsproj / .vbproj
+#[derive(Debug, Clone, Default)]
+pub struct CsProj {
+    pub sdk: Option<String>,
+    pub target_frameworks: Vec<String>,
+    pub output_type: Option<String>,
+    pub is_test_project: bool,
+    pub package_references: Vec<String>,
+}
+
+impl CsProj {
+    /// Parse a project file. Accepts the file itself or a directory holding
+    /// a single project file.
+    pub fn parse(path: &Path) -> Option<Self> {
+        let project_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            find_project_file(path)?
+        };
+
+        let content = fs::read_to_string(&project_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let sdk = PROJECT_SDK_REGEX
+            .captures(content)
+            .or_else(|| SDK_ELEMENT_REGEX.captures(content))
+            .map(|c| c[1].split('/').next().unwrap_or_default().to_string());
+
+        let target_frameworks = property(content, "TargetFrameworks")
+            .or_else(|| property(content, "TargetFramework"))
+            .map(|tfm| {
+                tfm.split(';')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        CsProj {
+            sdk,
+            target_frameworks,
+            output_type: property(content, "OutputType"),
+            is_test_project: property(content, "IsTestProject")
+                .is_some_and(|v| v.eq_ignore_ascii_case("true")),
+            package_references: PACKAGE_REFERENCE_REGEX
+                .captures_iter(content)
+                .map(|c| c[1].to_string())
+                .collect(),
+        }
+    }
+
+    pub fn has_package(&self, name: &str) -> bool {
+        self.package_references
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(name))
+    }
+
+    pub fn kind(&self) -> DotnetProjectKind {
+        let sdk = self.sdk.as_deref().unwrap_or("Microsoft.NET.Sdk");
+
+        if self.is_test_project || self.has_package("Microsoft.NET.Test.Sdk") {
+            return DotnetProjectKind::Test;
+        }
+
+        match sdk {
+            "Microsoft.NET.Sdk.BlazorWebAssembly" => DotnetProjectKind::Blazor,
+            "Microsoft.NET.Sdk.Web"
+                if self.has_package("Microsoft.AspNetCore.Components.WebAssembly.Server") =>
+            {
+                DotnetProjectKind::Blazor
+            }
+            "Microsoft.NET.Sdk.Web" => DotnetProjectKind::Web,
+            "Microsoft.NET.Sdk.Worker" => DotnetProjectKind::Worker,
+            "Microsoft.NET.Sdk.Razor" => DotnetProjectKind::Library,
+            _ => match self.output_type.as_deref() {
+                Some(t) if t.eq_ignore_ascii_case("exe") || t.eq_ignore_ascii_case("winexe") => {
+                    DotnetProjectKind::Console
+                }
+                _ if sdk.starts_with("Aspire.AppHost") => DotnetProjectKind::Console,
+                _ => DotnetProjectKind::Library,
+            },
+        }
+    }
+
+    /// First target framework (`net8.0`)
+    pub fn get_target_framework(&self) -> Option<&str> {
+        self.target_frameworks.first().map(String::as_str)
+    }
+}
+
+/// The single project file in a directory, if any
+pub fn find_project_file(path: &Path) -> Option<std::path::PathBuf> {
+    let mut files: Vec<_> = fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext == "csproj" || ext == "fsproj" || ext == "vbproj")
+        })
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// Text of the first `<Name>value</Name>` property
+fn property(content: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = content.find(&open)? + open.len();
+    let end = content[start..].find(&close)? + start;
+    let value = content[start..end].trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_kind() {
+        let web = CsProj::parse_str(
+            r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <PropertyGroup><TargetFrameworks>net8.0;net9.0</TargetFrameworks></PropertyGroup>
+</Project>"#,
+        );
+        assert_eq!(web.kind(), DotnetProjectKind::Web);
+        assert_eq!(web.target_frameworks, vec!["net8.0", "net9.0"]);
+
+        let console = CsProj::parse_str(
+            r#"<Project Sdk="Microsoft.NET.Sdk"><PropertyGroup><OutputType>Exe</OutputType></PropertyGroup></Project>"#,
+        );
+        assert_eq!(console.kind(), DotnetProjectKind::Console);
+
+        let tests = CsProj::parse_str(
+            r#"<Project Sdk="Microsoft.NET.Sdk"><ItemGroup><PackageReference Include="Microsoft.NET.Test.Sdk" Version="17.0.0" /></ItemGroup></Project>"#,
+        );
+        assert_eq!(tests.kind(), DotnetProjectKind::Test);
+    }
+}
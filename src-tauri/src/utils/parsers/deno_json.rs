@@ -0,0 +1,90 @@
+use crate::utils::parsers::strip_json_comments;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Parsed deno.json / deno.jsonc structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct DenoJson {
+    pub tasks: BTreeMap<String, DenoTask>,
+}
+
+/// A task is either a command or, since Deno 2.1, an object
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum DenoTask {
+    Command(String),
+    Detailed {
+        #[serde(default)]
+        command: String,
+        description: Option<String>,
+    },
+}
+
+impl DenoTask {
+    pub fn command(&self) -> &str {
+        match self {
+            DenoTask::Command(command) => command,
+            DenoTask::Detailed { command, .. } => command,
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            DenoTask::Command(_) => None,
+            DenoTask::Detailed { description, .. } => description.as_deref(),
+        }
+    }
+}
+
+impl DenoJson {
+    /// Parse deno.json or deno.jsonc from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = ["deno.json", "deno.jsonc"]
+            .iter()
+            .find_map(|file| fs::read_to_string(path.join(file)).ok())?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        serde_json::from_str(&strip_json_comments(content)).ok()
+    }
+
+    /// `deno task <name>` when the task exists
+    pub fn task_command(&self, name: &str) -> Option<String> {
+        self.tasks
+            .contains_key(name)
+            .then(|| format!("deno task {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deno_json() {
+        let deno = DenoJson::parse_str(
+            r#"{
+  // Fresh project
+  "tasks": {
+    "dev": "deno run -A --watch=static/,routes/ dev.ts",
+    "build": { "command": "deno run -A dev.ts build", "description": "Build for production" }
+  },
+  "imports": { "$fresh/": "https://deno.land/x/fresh@1.6.8/" }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(deno.tasks.len(), 2);
+        assert_eq!(deno.tasks["build"].command(), "deno run -A dev.ts build");
+        assert_eq!(
+            deno.tasks["build"].description(),
+            Some("Build for production")
+        );
+        assert_eq!(deno.task_command("dev").as_deref(), Some("deno task dev"));
+        assert_eq!(deno.task_command("start"), None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::config_store::config::get_config_dir;
+use crate::models::detected_service::{Framework, ServiceCategory};
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Score given to a matching rule unless it sets its own. Beats every
+/// built-in scorer except the exclusive markers (artisan, manage.py...).
+const DEFAULT_RULE_SCORE: u32 = 20;
+
+/// User-defined detection rules from `rules.toml` / `rules.yaml` in the
+/// Warden config dir and `.warden/rules.toml` in a repository.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct DetectionRules {
+    #[serde(alias = "rule")]
+    pub rules: Vec<DetectionRule>,
+    /// Unreadable files and the rules dropped from them
+    #[serde(skip)]
+    pub errors: Vec<String>,
+}
+
+/// One rule: what to look for and what it implies
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct DetectionRule {
+    /// Framework label shown instead of the built-in name
+    pub framework: String,
+    /// Built-in framework the rule builds on (stack, default commands)
+    pub extends: Option<Framework>,
+    pub category: Option<ServiceCategory>,
+    pub port: Option<u16>,
+    pub score: Option<u32>,
+    pub dev: Option<String>,
+    pub build: Option<String>,
+    pub start: Option<String>,
+    #[serde(rename = "match")]
+    pub matches: RuleMatch,
+}
+
+/// Conditions of a rule; all of them must hold
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct RuleMatch {
+    /// Files or glob patterns relative to the service directory
+    pub files: Vec<String>,
+    /// Dependencies declared in any manifest of the directory
+    pub dependencies: Vec<String>,
+    /// Regexes matched against file contents
+    pub contains: Vec<ContentMatch>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct ContentMatch {
+    pub file: String,
+    pub pattern: String,
+    /// `pattern` compiled once the rule is loaded
+    #[serde(skip)]
+    pub regex: Option<Regex>,
+}
+
+impl DetectionRule {
+    pub fn get_score(&self) -> u32 {
+        self.score.unwrap_or(DEFAULT_RULE_SCORE)
+    }
+
+    /// Framework a matching directory is reported as
+    pub fn get_framework(&self) -> Framework {
+        self.extends.clone().unwrap_or(Framework::Custom)
+    }
+
+    /// Compile the content patterns, or say why the rule cannot match
+    fn compile(&mut self) -> Result<(), String> {
+        if self.framework.trim().is_empty() {
+            return Err("rule without a framework".to_string());
+        }
+        let has_condition = !self.matches.files.is_empty()
+            || !self.matches.dependencies.is_empty()
+            || !self.matches.contains.is_empty();
+        if !has_condition {
+            return Err(format!("rule `{}` has no match conditions", self.framework));
+        }
+
+        for content in &mut self.matches.contains {
+            let regex = Regex::new(&content.pattern).map_err(|e| {
+                format!(
+                    "rule `{}`: invalid pattern `{}`: {}",
+                    self.framework, content.pattern, e
+                )
+            })?;
+            content.regex = Some(regex);
+        }
+        Ok(())
+    }
+}
+
+impl DetectionRules {
+    /// Rules for a project: the repository's own first, then the user's
+    /// global ones. Loaded once per scan and shared by its services.
+    pub fn load(path: &Path) -> Self {
+        let mut loaded = Self::default();
+
+        let config_dir = get_config_dir();
+        let global = ["rules.toml", "rules.yaml", "rules.yml"]
+            .iter()
+            .map(|file| config_dir.join(file))
+            .find(|p| p.exists());
+
+        for file in find_repo_rules(path).into_iter().chain(global) {
+            match Self::parse_file(&file) {
+                Ok(rules) => {
+                    loaded.rules.extend(rules.rules);
+                    loaded.errors.extend(rules.errors);
+                }
+                Err(e) => loaded.errors.push(e),
+            }
+        }
+
+        loaded
+    }
+
+    /// Errors name the file, as they are shown apart from it
+    fn parse_file(path: &Path) -> Result<Self, String> {
+        let file_name = path.to_string_lossy().to_string();
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", file_name, e))?;
+
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        let parsed = if is_yaml {
+            Self::parse_yaml(&content)
+        } else {
+            Self::parse_str(&content)
+        };
+
+        let mut rules = parsed.map_err(|e| format!("{}: {}", file_name, e))?;
+        for error in &mut rules.errors {
+            *error = format!("{}: {}", file_name, error);
+        }
+        Ok(rules)
+    }
+
+    pub fn parse_str(content: &str) -> Result<Self, String> {
+        let rules: Self = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        Ok(rules.compiled())
+    }
+
+    pub fn parse_yaml(content: &str) -> Result<Self, String> {
+        let rules: Self = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+        Ok(rules.compiled())
+    }
+
+    /// Drop rules that cannot match, recording why
+    fn compiled(mut self) -> Self {
+        let mut errors = Vec::new();
+        self.rules.retain_mut(|rule| match rule.compile() {
+            Ok(()) => true,
+            Err(e) => {
+                errors.push(e);
+                false
+            }
+        });
+        self.errors.extend(errors);
+        self
+    }
+}
+
+/// `.warden/rules.toml` (or `.yaml`) in the directory or one of its parents
+fn find_repo_rules(path: &Path) -> Option<PathBuf> {
+    path.ancestors().take(5).find_map(|dir| {
+        ["rules.toml", "rules.yaml", "rules.yml"]
+            .iter()
+            .map(|file| dir.join(".warden").join(file))
+            .find(|p| p.exists())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = DetectionRules::parse_str(
+            r#"
+[[rule]]
+framework = "Acme Gateway"
+extends = "express"
+category = "backend"
+port = 7070
+dev = "acme dev"
+
+[rule.match]
+files = ["acme.config.*"]
+dependencies = ["@acme/gateway"]
+contains = [{ file = "acme.config.ts", pattern = "gateway\\(" }]
+
+[[rule]]
+framework = "Never matches"
+
+[[rule]]
+framework = "Broken pattern"
+
+[rule.match]
+contains = [{ file = "main.go", pattern = "func (" }]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(rules.rules.len(), 1);
+        let rule = &rules.rules[0];
+        assert_eq!(rule.framework, "Acme Gateway");
+        assert_eq!(rule.get_framework(), Framework::Express);
+        assert_eq!(rule.category, Some(ServiceCategory::Backend));
+        assert_eq!(rule.get_score(), 20);
+        assert!(rule.matches.contains[0]
+            .regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match("gateway(")));
+
+        assert_eq!(rules.errors.len(), 2);
+        assert_eq!(
+            rules.errors[0],
+            "rule `Never matches` has no match conditions"
+        );
+        assert!(rules.errors[1].starts_with("rule `Broken pattern`: invalid pattern `func (`"));
+        assert!(DetectionRules::parse_str("[[rule]\n").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+/// Parsed go.mod structure
+#[derive(Debug, Clone, Default)]
+pub struct GoMod {
+    pub module: Option<String>,
+    pub go_version: Option<String>,
+    pub toolchain: Option<String>,
+    pub requires: Vec<GoRequire>,
+}
+
+/// A `require` directive entry
+#[derive(Debug, Clone, Default)]
+pub struct GoRequire {
+    pub path: String,
+    pub indirect: bool,
+}
+
+impl GoMod {
+    /// Parse go.mod from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let go_mod_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            path.join("go.mod")
+        };
+
+        let content = fs::read_to_string(&go_mod_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let mut go_mod = GoMod::default();
+        let mut in_require_block = false;
+
+        for line in content.lines() {
+            let (code, comment) = match line.split_once("//") {
+                Some((code, comment)) => (code.trim(), comment.trim()),
+                None => (line.trim(), ""),
+            };
+
+            if code.is_empty() {
+                continue;
+            }
+
+            if in_require_block {
+                if code == ")" {
+                    in_require_block = false;
+                } else if let Some(require) = parse_require(code, comment) {
+                    go_mod.requires.push(require);
+                }
+                continue;
+            }
+
+            let mut parts = code.splitn(2, char::is_whitespace);
+            let directive = parts.next().unwrap_or_default();
+            let rest = parts.next().unwrap_or_default().trim();
+
+            match directive {
+                "module" => go_mod.module = Some(rest.trim_matches('"').to_string()),
+                "go" => go_mod.go_version = Some(rest.to_string()),
+                "toolchain" => go_mod.toolchain = Some(rest.to_string()),
+                "require" if rest == "(" => in_require_block = true,
+                "require" => {
+                    if let Some(require) = parse_require(rest, comment) {
+                        go_mod.requires.push(require);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        go_mod
+    }
+
+    /// Check if a module is required directly. Matches major-version
+    /// suffixes too: `github.com/labstack/echo` matches `.../echo/v4`.
+    pub fn has_dependency(&self, module_path: &str) -> bool {
+        self.requires.iter().any(|r| {
+            !r.indirect
+                && (r.path == module_path
+                    || r.path
+                        .strip_prefix(module_path)
+                        .is_some_and(is_major_suffix))
+        })
+    }
+}
+
+fn parse_require(spec: &str, comment: &str) -> Option<GoRequire> {
+    let mut parts = spec.split_whitespace();
+    let path = parts.next()?.trim_matches('"').to_string();
+    // A requirement without a version is malformed
+    parts.next()?;
+
+    Some(GoRequire {
+        path,
+        indirect: comment == "indirect",
+    })
+}
+
+/// `/v2`, `/v10` ...
+fn is_major_suffix(rest: &str) -> bool {
+    rest.strip_prefix("/v")
+        .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_go_mod() {
+        let content = r#"
+module github.com/acme/billing
+
+go 1.22.1
+
+toolchain go1.22.3
+
+require github.com/spf13/cobra v1.8.0
+
+require (
+	github.com/labstack/echo/v4 v4.11.4
+	github.com/lib/pq v1.10.9 // indirect
+)
+"#;
+        let go_mod = GoMod::parse_str(content);
+
+        assert_eq!(go_mod.module.as_deref(), Some("github.com/acme/billing"));
+        assert_eq!(go_mod.go_version.as_deref(), Some("1.22.1"));
+        assert_eq!(go_mod.toolchain.as_deref(), Some("go1.22.3"));
+        assert_eq!(go_mod.requires.len(), 3);
+        assert!(go_mod.has_dependency("github.com/labstack/echo"));
+        assert!(go_mod.has_dependency("github.com/spf13/cobra"));
+        assert!(!go_mod.has_dependency("github.com/lib/pq"));
+    }
+}
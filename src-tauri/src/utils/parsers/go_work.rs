@@ -0,0 +1,77 @@
+use std::fs;
+use std::path::Path;
+
+/// Parsed go.work structure
+#[derive(Debug, Clone, Default)]
+pub struct GoWork {
+    #[allow(dead_code)]
+    pub go_version: Option<String>,
+    /// Module directories from `use` directives, as written
+    pub uses: Vec<String>,
+}
+
+impl GoWork {
+    /// Parse go.work from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path.join("go.work")).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let mut go_work = GoWork::default();
+        let mut in_use_block = false;
+
+        for line in content.lines() {
+            let code = line.split("//").next().unwrap_or_default().trim();
+
+            if code.is_empty() {
+                continue;
+            }
+
+            if in_use_block {
+                if code == ")" {
+                    in_use_block = false;
+                } else {
+                    go_work.uses.push(code.trim_matches('"').to_string());
+                }
+                continue;
+            }
+
+            let mut parts = code.splitn(2, char::is_whitespace);
+            let directive = parts.next().unwrap_or_default();
+            let rest = parts.next().unwrap_or_default().trim();
+
+            match directive {
+                "go" => go_work.go_version = Some(rest.to_string()),
+                "use" if rest == "(" => in_use_block = true,
+                "use" => go_work.uses.push(rest.trim_matches('"').to_string()),
+                _ => {}
+            }
+        }
+
+        go_work
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_go_work() {
+        let go_work = GoWork::parse_str(
+            r#"
+go 1.22
+
+use (
+	./api // http server
+	"./tools/cli"
+)
+use ./worker
+"#,
+        );
+
+        assert_eq!(go_work.go_version.as_deref(), Some("1.22"));
+        assert_eq!(go_work.uses, vec!["./api", "./tools/cli", "./worker"]);
+    }
+}
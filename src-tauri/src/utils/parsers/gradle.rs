@@ -0,0 +1,232 @@
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static BLOCK_COMMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)/\*.*?\*/").unwrap());
+
+static LINE_COMMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*//.*$").unwrap());
+
+/// `include("a", ":b")`, `include 'a', 'b'` (possibly spanning lines)
+static INCLUDE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\binclude\s*\(?((?:\s*["'][^"']+["']\s*,?)+)"#).unwrap());
+
+static QUOTED_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
+
+/// `project(":api").projectDir = file("services/api")`
+static PROJECT_DIR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"project\(\s*["']([^"']+)["']\s*\)\.projectDir\s*=\s*file\(\s*["']([^"']+)["']"#)
+        .unwrap()
+});
+
+static PLUGINS_BLOCK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\bplugins\s*\{(.*?)\n\s*\}").unwrap());
+
+static PLUGIN_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bid\s*\(?\s*["']([^"']+)["']"#).unwrap());
+
+static KOTLIN_PLUGIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bkotlin\s*\(\s*["']([^"']+)["']\s*\)"#).unwrap());
+
+static CORE_PLUGIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^`?([a-z][a-z-]*)`?$").unwrap());
+
+static APPLY_PLUGIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"apply\s*\(?\s*plugin\s*[:=]\s*["']([^"']+)["']"#).unwrap());
+
+/// `"group:artifact:version"` dependency notation
+static COORDINATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"["']([\w.-]+):([\w.-]+)(?::[^"']*)?["']"#).unwrap());
+
+/// Parsed settings.gradle / settings.gradle.kts
+#[derive(Debug, Clone, Default)]
+pub struct GradleSettings {
+    /// Included project paths (`:services:api`)
+    pub includes: Vec<String>,
+    /// Explicit `projectDir` overrides, by project path
+    pub project_dirs: Vec<(String, String)>,
+}
+
+/// Parsed build.gradle / build.gradle.kts
+#[derive(Debug, Clone, Default)]
+pub struct GradleBuild {
+    /// Applied plugin ids (`org.springframework.boot`, `application`, ...)
+    pub plugins: Vec<String>,
+    /// Declared dependencies as `group:artifact`
+    pub dependencies: Vec<String>,
+    pub is_kotlin_dsl: bool,
+}
+
+impl GradleSettings {
+    /// Parse settings.gradle(.kts) from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = ["settings.gradle.kts", "settings.gradle"]
+            .iter()
+            .find_map(|file| fs::read_to_string(path.join(file)).ok())?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let content = strip_comments(content);
+
+        let includes = INCLUDE_REGEX
+            .captures_iter(&content)
+            .flat_map(|c| {
+                QUOTED_REGEX
+                    .captures_iter(&c[1])
+                    .map(|q| normalize_project_path(&q[1]))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        GradleSettings {
+            includes,
+            project_dirs: PROJECT_DIR_REGEX
+                .captures_iter(&content)
+                .map(|c| (normalize_project_path(&c[1]), c[2].to_string()))
+                .collect(),
+        }
+    }
+
+    /// Directory of an included project (`:services:api` -> `services/api`)
+    pub fn project_dir(&self, root: &Path, project_path: &str) -> PathBuf {
+        if let Some((_, dir)) = self.project_dirs.iter().find(|(p, _)| p == project_path) {
+            return root.join(dir);
+        }
+
+        project_path
+            .trim_start_matches(':')
+            .split(':')
+            .fold(root.to_path_buf(), |dir, segment| dir.join(segment))
+    }
+}
+
+impl GradleBuild {
+    /// Parse build.gradle(.kts) from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        for file in ["build.gradle.kts", "build.gradle"] {
+            if let Ok(content) = fs::read_to_string(path.join(file)) {
+                let mut build = Self::parse_str(&content);
+                build.is_kotlin_dsl = file.ends_with(".kts");
+                return Some(build);
+            }
+        }
+
+        None
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let content = strip_comments(content);
+        let mut plugins: Vec<String> = Vec::new();
+
+        if let Some(block) = PLUGINS_BLOCK_REGEX.captures(&content) {
+            for line in block[1].lines() {
+                let line = line.trim();
+                if let Some(c) = PLUGIN_ID_REGEX.captures(line) {
+                    plugins.push(c[1].to_string());
+                } else if let Some(c) = KOTLIN_PLUGIN_REGEX.captures(line) {
+                    plugins.push(format!("org.jetbrains.kotlin.{}", &c[1]));
+                } else if let Some(c) = CORE_PLUGIN_REGEX.captures(line) {
+                    plugins.push(c[1].to_string());
+                }
+            }
+        }
+
+        plugins.extend(
+            APPLY_PLUGIN_REGEX
+                .captures_iter(&content)
+                .map(|c| c[1].to_string()),
+        );
+
+        GradleBuild {
+            plugins,
+            dependencies: COORDINATE_REGEX
+                .captures_iter(&content)
+                .map(|c| format!("{}:{}", &c[1], &c[2]))
+                .collect(),
+            is_kotlin_dsl: false,
+        }
+    }
+
+    /// Plugin ids match exactly or as a prefix (`io.micronaut` matches
+    /// `io.micronaut.application`)
+    pub fn has_plugin(&self, id: &str) -> bool {
+        self.plugins
+            .iter()
+            .any(|p| p == id || p.starts_with(&format!("{}.", id)))
+    }
+
+    /// Check whether any dependency belongs to a group
+    pub fn has_group(&self, group: &str) -> bool {
+        self.dependencies.iter().any(|d| {
+            d.split(':')
+                .next()
+                .is_some_and(|g| g == group || g.starts_with(&format!("{}.", group)))
+        })
+    }
+}
+
+fn strip_comments(content: &str) -> String {
+    let content = BLOCK_COMMENT_REGEX.replace_all(content, "");
+    LINE_COMMENT_REGEX.replace_all(&content, "").to_string()
+}
+
+/// `api` and `:api` are the same project
+fn normalize_project_path(path: &str) -> String {
+    if path.starts_with(':') {
+        path.to_string()
+    } else {
+        format!(":{}", path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings_and_build() {
+        let settings = GradleSettings::parse_str(
+            r#"
+rootProject.name = "shop"
+include(
+    ":services:api",
+    "common",
+)
+// include(":legacy")
+project(":common").projectDir = file("libs/common")
+"#,
+        );
+
+        assert_eq!(settings.includes, vec![":services:api", ":common"]);
+        assert_eq!(
+            settings.project_dir(Path::new("/repo"), ":common"),
+            PathBuf::from("/repo/libs/common")
+        );
+        assert_eq!(
+            settings.project_dir(Path::new("/repo"), ":services:api"),
+            PathBuf::from("/repo/services/api")
+        );
+
+        let build = GradleBuild::parse_str(
+            r#"
+plugins {
+    id("org.springframework.boot") version "3.2.0"
+    kotlin("jvm")
+    application
+}
+
+dependencies {
+    implementation("org.springframework.boot:spring-boot-starter-web")
+}
+"#,
+        );
+
+        assert!(build.has_plugin("org.springframework.boot"));
+        assert!(build.has_plugin("org.jetbrains.kotlin"));
+        assert!(build.has_plugin("application"));
+        assert!(build.has_group("org.springframework.boot"));
+    }
+}
@@ -0,0 +1,211 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static JUST_RECIPE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^@?([A-Za-z_][A-Za-z0-9_-]*)((?:\s+[^\s:"'=]+(?:=(?:"[^"]*"|'[^']*'|[^\s:]+))?)*)\s*:([^=].*)?$"#,
+    )
+    .unwrap()
+});
+
+static JUST_DOC_ATTRIBUTE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"doc\(\s*["'](.+?)["']\s*\)"#).unwrap());
+
+/// Parsed justfile
+#[derive(Debug, Clone, Default)]
+pub struct Justfile {
+    pub recipes: Vec<JustRecipe>,
+}
+
+/// A public recipe in a justfile
+#[derive(Debug, Clone, Default)]
+pub struct JustRecipe {
+    pub name: String,
+    pub description: Option<String>,
+    pub commands: Vec<String>,
+}
+
+impl Justfile {
+    /// Parse a justfile from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let justfile_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            ["justfile", "Justfile", ".justfile"]
+                .iter()
+                .map(|file| path.join(file))
+                .find(|p| p.exists())?
+        };
+
+        let content = fs::read_to_string(&justfile_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let mut recipes: Vec<JustRecipe> = Vec::new();
+        let mut pending_comment: Option<String> = None;
+        let mut pending_private = false;
+        let mut current: Option<usize> = None;
+
+        for line in content.lines() {
+            // Recipe bodies are indented (spaces or tabs)
+            if line.starts_with(' ') || line.starts_with('\t') {
+                if let Some(recipe) = current.and_then(|i| recipes.get_mut(i)) {
+                    let body = line.trim();
+                    if !body.is_empty() && !body.starts_with('#') {
+                        recipe.commands.push(body.to_string());
+                    }
+                }
+                continue;
+            }
+
+            let trimmed = line.trim();
+
+            // Recipe bodies may contain blank lines
+            if trimmed.is_empty() {
+                pending_comment = None;
+                pending_private = false;
+                continue;
+            }
+            current = None;
+
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                // Shebang lines only appear inside recipe bodies, but be safe
+                if !comment.starts_with('!') {
+                    pending_comment = Some(comment.trim().to_string());
+                }
+                continue;
+            }
+
+            // Attributes: `[private]`, `[group('dev')]`, `[doc("...")]`
+            if trimmed.starts_with('[') {
+                if attribute_names(trimmed).any(|name| name == "private") {
+                    pending_private = true;
+                }
+                if let Some(captures) = JUST_DOC_ATTRIBUTE_REGEX.captures(trimmed) {
+                    pending_comment = Some(captures[1].to_string());
+                }
+                continue;
+            }
+
+            let is_directive = ["set ", "alias ", "export ", "import ", "mod "]
+                .iter()
+                .any(|prefix| trimmed.starts_with(prefix));
+
+            let captures = match JUST_RECIPE_REGEX.captures(line) {
+                Some(captures) if !is_directive => captures,
+                _ => {
+                    pending_comment = None;
+                    pending_private = false;
+                    continue;
+                }
+            };
+
+            let name = captures[1].to_string();
+            let is_private = pending_private || name.starts_with('_');
+            let description = pending_comment.take().filter(|c| !c.is_empty());
+            pending_private = false;
+
+            if is_private {
+                continue;
+            }
+
+            recipes.push(JustRecipe {
+                name,
+                description,
+                commands: Vec::new(),
+            });
+            current = Some(recipes.len() - 1);
+        }
+
+        Self { recipes }
+    }
+}
+
+/// Names of the attributes in a `[a, b('x'), c: 'y']` line
+fn attribute_names(line: &str) -> impl Iterator<Item = &str> {
+    let inner = line.trim().trim_start_matches('[').trim_end_matches(']');
+
+    // Split on commas outside of arguments and quotes
+    let mut attributes = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                attributes.push(&inner[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    attributes.push(&inner[start..]);
+
+    attributes.into_iter().map(|attribute| {
+        attribute
+            .split(['(', ':'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recipes() {
+        let content = r#"
+set dotenv-load
+port := "8000"
+
+# Start the dev server
+dev port="8000": install
+    uv run alembic upgrade head
+
+    uvicorn app.main:app --reload --port {{port}}
+
+[private]
+install:
+    uv sync
+
+[group('private-tools'), doc("not private")]
+lint:
+    ruff check
+
+[unix, private]
+clean:
+    rm -rf .venv
+
+_helper:
+    echo hidden
+
+[doc("Run the test suite")]
+test *args:
+    pytest {{args}}
+"#;
+        let justfile = Justfile::parse_str(content);
+        let names: Vec<&str> = justfile.recipes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "lint", "test"]);
+
+        let dev = &justfile.recipes[0];
+        assert_eq!(dev.description.as_deref(), Some("Start the dev server"));
+        assert_eq!(dev.commands.len(), 2);
+        assert_eq!(
+            justfile.recipes[1].description.as_deref(),
+            Some("not private")
+        );
+
+        let test = &justfile.recipes[2];
+        assert_eq!(test.description.as_deref(), Some("Run the test suite"));
+    }
+}
@@ -0,0 +1,85 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Parsed `Properties/launchSettings.json`
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LaunchSettings {
+    /// In file order: `dotnet run` picks the first `Project` profile
+    pub profiles: IndexMap<String, LaunchProfile>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub command_name: Option<String>,
+    pub application_url: Option<String>,
+    pub launch_url: Option<String>,
+}
+
+impl LaunchSettings {
+    /// Parse launchSettings.json from a project directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content =
+            fs::read_to_string(path.join("Properties").join("launchSettings.json")).ok()?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        serde_json::from_str(content).ok()
+    }
+
+    /// Profiles that run the project itself (not IIS Express or Docker)
+    pub fn project_profiles(&self) -> Vec<(&String, &LaunchProfile)> {
+        self.profiles
+            .iter()
+            .filter(|(_, p)| p.command_name.as_deref() == Some("Project"))
+            .collect()
+    }
+
+    /// Port of the default profile's `applicationUrl`, preferring plain HTTP
+    pub fn get_port(&self) -> Option<u16> {
+        self.project_profiles()
+            .first()
+            .and_then(|(_, profile)| profile.application_url.as_deref())
+            .and_then(|urls| {
+                let urls: Vec<&str> = urls.split(';').map(str::trim).collect();
+                urls.iter()
+                    .find(|u| u.starts_with("http://"))
+                    .or_else(|| urls.first())
+                    .and_then(|u| url_port(u))
+            })
+    }
+}
+
+/// `https://localhost:7001/` -> 7001
+fn url_port(url: &str) -> Option<u16> {
+    let host = url.split("://").nth(1)?.split('/').next()?;
+    host.rsplit_once(':')?.1.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_profile_port() {
+        let settings = LaunchSettings::parse_str(
+            r#"{
+  "iisSettings": { "comment": "prefer \"http\"" },
+  "profiles": {
+    "https": { "commandName": "Project", "applicationUrl": "https://localhost:7123;http://localhost:5123" },
+    "http": { "commandName": "Project", "applicationUrl": "http://localhost:5124" },
+    "IIS Express": { "commandName": "IISExpress" }
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.get_port(), Some(5123));
+        assert_eq!(settings.project_profiles().len(), 2);
+        assert_eq!(settings.project_profiles()[0].0, "https");
+    }
+}
@@ -0,0 +1,166 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static MAKE_TARGET_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z0-9_][A-Za-z0-9_.\-/]*)\s*:([^=].*)?$").unwrap());
+
+static MAKE_INLINE_DOC_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"##\s*(.+)$").unwrap());
+
+/// Parsed Makefile (only the parts Warden cares about: explicit targets)
+#[derive(Debug, Clone, Default)]
+pub struct Makefile {
+    pub targets: Vec<MakeTarget>,
+}
+
+/// A single explicit target in a Makefile
+#[derive(Debug, Clone, Default)]
+pub struct MakeTarget {
+    pub name: String,
+    pub description: Option<String>,
+    pub commands: Vec<String>,
+}
+
+impl Makefile {
+    /// Parse a Makefile from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let makefile_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            ["GNUmakefile", "Makefile", "makefile"]
+                .iter()
+                .map(|file| path.join(file))
+                .find(|p| p.exists())?
+        };
+
+        let content = fs::read_to_string(&makefile_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let mut targets: Vec<MakeTarget> = Vec::new();
+        let mut pending_comment: Option<String> = None;
+        // Index of the target whose recipe lines are currently being read
+        let mut current: Option<usize> = None;
+
+        for line in content.lines() {
+            // Recipe lines are tab-indented and belong to the current target
+            if let Some(recipe) = line.strip_prefix('\t') {
+                if let Some(target) = current.and_then(|i| targets.get_mut(i)) {
+                    let recipe = recipe.trim();
+                    if !recipe.is_empty() && !recipe.starts_with('#') {
+                        target.commands.push(recipe.to_string());
+                    }
+                }
+                continue;
+            }
+
+            let trimmed = line.trim();
+
+            // Blank lines may separate recipe lines; only a new rule or
+            // variable ends the recipe
+            if trimmed.is_empty() {
+                pending_comment = None;
+                continue;
+            }
+
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                let comment = comment.trim_start_matches('#').trim();
+                if !comment.is_empty() {
+                    pending_comment = Some(comment.to_string());
+                }
+                continue;
+            }
+
+            current = None;
+
+            let Some(captures) = MAKE_TARGET_REGEX.captures(line) else {
+                pending_comment = None;
+                continue;
+            };
+
+            let rest = captures.get(2).map(|m| m.as_str()).unwrap_or_default();
+            let description = MAKE_INLINE_DOC_REGEX
+                .captures(rest)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().trim().to_string())
+                .or_else(|| pending_comment.take());
+            pending_comment = None;
+
+            // Special targets like `.PHONY` never match the regex (they start
+            // with a dot); pattern rules are skipped here.
+            let name = captures[1].to_string();
+            if name.contains('%') {
+                continue;
+            }
+
+            // Double-colon or re-declared targets: keep the first description
+            // and append commands from the later block.
+            if let Some(index) = targets.iter().position(|t| t.name == name) {
+                if targets[index].description.is_none() {
+                    targets[index].description = description;
+                }
+                current = Some(index);
+                continue;
+            }
+
+            targets.push(MakeTarget {
+                name,
+                description,
+                commands: Vec::new(),
+            });
+            current = Some(targets.len() - 1);
+        }
+
+        // Drop targets that only aggregate prerequisites and carry no recipe
+        // or description; they are not useful as runnable tasks.
+        targets.retain(|t| !t.commands.is_empty() || t.description.is_some());
+
+        Self { targets }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets_with_descriptions() {
+        let content = "\
+.PHONY: dev build
+
+APP := server
+
+# Run the API with hot reload
+dev:
+\tair -c .air.toml
+
+build: ## Compile the binary
+\tgo build -o bin/$(APP) ./cmd/$(APP)
+
+\t@echo done
+
+%.o: %.c
+\tcc -c $<
+";
+        let makefile = Makefile::parse_str(content);
+        let names: Vec<&str> = makefile.targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "build"]);
+
+        let dev = &makefile.targets[0];
+        assert_eq!(
+            dev.description.as_deref(),
+            Some("Run the API with hot reload")
+        );
+        assert_eq!(dev.commands, vec!["air -c .air.toml"]);
+
+        let build = &makefile.targets[1];
+        assert_eq!(build.description.as_deref(), Some("Compile the binary"));
+        assert_eq!(
+            build.commands,
+            vec!["go build -o bin/$(APP) ./cmd/$(APP)", "@echo done"]
+        );
+    }
+}
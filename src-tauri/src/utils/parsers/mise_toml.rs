@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Config files mise reads from a directory, highest priority first
+const MISE_CONFIG_FILES: [&str; 3] = ["mise.toml", ".mise.toml", ".config/mise.toml"];
+
+/// Parsed mise.toml structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct MiseToml {
+    pub tools: HashMap<String, MiseTool>,
+}
+
+/// `node = "20"`, `python = ["3.12", "3.11"]` or `rust = { version = "1.75" }`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MiseTool {
+    Version(String),
+    Versions(Vec<String>),
+    Detailed { version: String },
+}
+
+impl MiseTool {
+    /// The version mise activates
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            MiseTool::Version(version) => Some(version),
+            MiseTool::Versions(versions) => versions.first().map(String::as_str),
+            MiseTool::Detailed { version } => Some(version),
+        }
+    }
+}
+
+impl MiseToml {
+    /// Parse the mise config of a directory, with the file it came from
+    pub fn parse(path: &Path) -> Option<(Self, &'static str)> {
+        MISE_CONFIG_FILES.iter().find_map(|file| {
+            let content = fs::read_to_string(path.join(file)).ok()?;
+            Some((Self::parse_str(&content)?, *file))
+        })
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        toml::from_str(content).ok()
+    }
+
+    pub fn get(&self, tool: &str) -> Option<&str> {
+        self.tools.get(tool).and_then(MiseTool::version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mise_toml() {
+        let mise = MiseToml::parse_str(
+            r#"
+[env]
+NODE_ENV = "development"
+
+[tools]
+node = "20"
+python = ["3.12", "3.11"]
+rust = { version = "1.77", profile = "minimal" }
+"npm:prettier" = "latest"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(mise.get("node"), Some("20"));
+        assert_eq!(mise.get("python"), Some("3.12"));
+        assert_eq!(mise.get("rust"), Some("1.77"));
+        assert_eq!(mise.get("go"), None);
+    }
+}
@@ -1,9 +1,61 @@
+pub mod air_toml;
+pub mod application_config;
 pub mod cargo_toml;
+pub mod csproj;
+pub mod deno_json;
+pub mod detection_rules;
 pub mod docker_compose;
+pub mod go_mod;
+pub mod go_work;
+pub mod gradle;
+pub mod justfile;
+pub mod launch_settings;
+pub mod makefile;
+pub mod mise_toml;
+pub mod nx_project;
 pub mod package_json;
+pub mod pipfile;
+pub mod pom_xml;
+pub mod procfile;
+pub mod pyproject;
+pub mod requirements;
+pub mod rush_json;
+pub mod rust_toolchain;
+pub mod scaffold_templates;
+pub mod sln;
+pub mod taskfile;
 pub mod tauri_conf;
+pub mod tool_versions;
+pub mod turbo_json;
+pub mod warden_manifest;
 
+pub use air_toml::*;
+pub use application_config::*;
 pub use cargo_toml::*;
+pub use csproj::*;
+pub use deno_json::*;
+pub use detection_rules::*;
 pub use docker_compose::*;
+pub use go_mod::*;
+pub use go_work::*;
+pub use gradle::*;
+pub use justfile::*;
+pub use launch_settings::*;
+pub use makefile::*;
+pub use mise_toml::*;
+pub use nx_project::*;
 pub use package_json::*;
+pub use pipfile::*;
+pub use pom_xml::*;
+pub use procfile::*;
+pub use pyproject::*;
+pub use requirements::*;
+pub use rush_json::*;
+pub use rust_toolchain::*;
+pub use scaffold_templates::*;
+pub use sln::*;
+pub use taskfile::*;
 pub use tauri_conf::*;
+pub use tool_versions::*;
+pub use turbo_json::*;
+pub use warden_manifest::*;
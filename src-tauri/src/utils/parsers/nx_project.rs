@@ -0,0 +1,72 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Parsed Nx project.json structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NxProject {
+    pub name: Option<String>,
+    #[allow(dead_code)]
+    pub project_type: Option<String>,
+    pub targets: HashMap<String, NxTarget>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct NxTarget {
+    /// `@nx/vite:dev-server`, `nx:run-commands`...
+    #[allow(dead_code)]
+    pub executor: Option<String>,
+}
+
+impl NxProject {
+    /// Parse project.json from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path.join("project.json")).ok()?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        serde_json::from_str(content).ok()
+    }
+
+    /// Project name, which defaults to the directory name
+    pub fn get_name(&self, path: &Path) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nx_project() {
+        let project = NxProject::parse_str(
+            r#"{
+  "name": "storefront",
+  "projectType": "application",
+  "targets": {
+    "serve": { "executor": "@nx/vite:dev-server", "options": { "port": 4200 } },
+    "build": { "executor": "@nx/vite:build" },
+    "test": { "executor": "@nx/vite:test" }
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(project.get_name(Path::new("apps/shop")), "storefront");
+        assert_eq!(project.project_type.as_deref(), Some("application"));
+        assert_eq!(project.targets.len(), 3);
+        assert_eq!(
+            project.targets["serve"].executor.as_deref(),
+            Some("@nx/vite:dev-server")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::detected_service::PackageManager;
 use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -32,6 +33,9 @@ pub struct PackageJson {
     pub workspaces: Option<WorkspacesConfig>,
     #[serde(rename = "packageManager")]
     pub package_manager: Option<String>,
+    /// Supported runtime versions (`"node": ">=20"`); kept loose since
+    /// old packages wrote it as an array
+    pub engines: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -128,10 +132,6 @@ impl PackageJson {
         None
     }
 
-    pub fn is_monorepo(&self) -> bool {
-        self.workspaces.is_some()
-    }
-
     pub fn get_workspace_patterns(&self) -> Vec<String> {
         self.workspaces
             .as_ref()
@@ -139,11 +139,29 @@ impl PackageJson {
             .unwrap_or_default()
     }
 
-    #[allow(dead_code)]
-    pub fn get_declared_package_manager(&self) -> Option<String> {
-        self.package_manager
-            .as_ref()
-            .map(|pm| pm.split('@').next().unwrap_or(pm).to_string())
+    /// Package manager pinned through Corepack (`"packageManager": "yarn@4.1.0"`).
+    /// Yarn 2 and later is Yarn Berry.
+    pub fn get_declared_package_manager(&self) -> Option<PackageManager> {
+        let spec = self.package_manager.as_ref()?;
+        let (name, version) = spec.split_once('@').unwrap_or((spec, ""));
+        let major = version
+            .split(['.', '+'])
+            .next()
+            .and_then(|major| major.parse::<u32>().ok());
+
+        match name {
+            "npm" => Some(PackageManager::Npm),
+            "pnpm" => Some(PackageManager::Pnpm),
+            "bun" => Some(PackageManager::Bun),
+            "yarn" if major.is_some_and(|major| major >= 2) => Some(PackageManager::YarnBerry),
+            "yarn" => Some(PackageManager::Yarn),
+            _ => None,
+        }
+    }
+
+    /// Version range an `engines` entry allows
+    pub fn get_engine(&self, name: &str) -> Option<&str> {
+        self.engines.get(name)?.as_str()
     }
 
     pub fn is_frontend(&self) -> bool {
@@ -238,4 +256,24 @@ mod tests {
             .insert("dev".to_string(), "next dev -p 3002".to_string());
         assert_eq!(pkg2.extract_port_from_scripts(), Some(3002));
     }
+
+    #[test]
+    fn test_declared_package_manager() {
+        let declared = |spec: &str| {
+            PackageJson {
+                package_manager: Some(spec.to_string()),
+                ..PackageJson::default()
+            }
+            .get_declared_package_manager()
+        };
+
+        assert_eq!(declared("yarn@1.22.19"), Some(PackageManager::Yarn));
+        assert_eq!(
+            declared("yarn@4.1.0+sha512.5b7bc05"),
+            Some(PackageManager::YarnBerry)
+        );
+        assert_eq!(declared("pnpm@9.1.0"), Some(PackageManager::Pnpm));
+        assert_eq!(declared("bun@1.1.8"), Some(PackageManager::Bun));
+        assert_eq!(declared("cnpm@1.0.0"), None);
+    }
 }
@@ -0,0 +1,45 @@
+use crate::utils::parsers::pyproject::normalize_package_name;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Parsed Pipfile structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct Pipfile {
+    pub packages: HashMap<String, toml::Value>,
+    #[serde(rename = "dev-packages")]
+    pub dev_packages: HashMap<String, toml::Value>,
+    pub scripts: HashMap<String, String>,
+    pub requires: Option<PipfileRequires>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PipfileRequires {
+    pub python_version: Option<String>,
+    pub python_full_version: Option<String>,
+}
+
+impl Pipfile {
+    /// Parse a Pipfile from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let pipfile_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            path.join("Pipfile")
+        };
+
+        let content = fs::read_to_string(&pipfile_path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    pub fn has_dependency(&self, name: &str) -> bool {
+        let name = normalize_package_name(name);
+        self.packages
+            .keys()
+            .chain(self.dev_packages.keys())
+            .any(|pkg| normalize_package_name(pkg) == name)
+    }
+}
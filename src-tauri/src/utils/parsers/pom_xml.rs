@@ -0,0 +1,191 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static XML_COMMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+
+static MODULE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<module>\s*([^<]+?)\s*</module>").unwrap());
+
+static DEPENDENCY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<dependency>(.*?)</dependency>").unwrap());
+
+static PLUGIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<plugin>(.*?)</plugin>").unwrap());
+
+/// Sections whose `<artifactId>` / `<packaging>` don't describe the project itself
+const NESTED_SECTIONS: [&str; 7] = [
+    "parent",
+    "dependencies",
+    "dependencyManagement",
+    "build",
+    "profiles",
+    "reporting",
+    "distributionManagement",
+];
+
+/// Parsed pom.xml structure (only what Warden needs, read with regexes)
+#[derive(Debug, Clone, Default)]
+pub struct PomXml {
+    pub packaging: Option<String>,
+    pub parent: Option<MavenCoordinate>,
+    pub modules: Vec<String>,
+    pub dependencies: Vec<MavenCoordinate>,
+    /// Build plugins (artifactIds), including those in `<pluginManagement>`
+    pub plugins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MavenCoordinate {
+    pub group_id: String,
+    pub artifact_id: String,
+}
+
+impl PomXml {
+    /// Parse pom.xml from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let pom_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            path.join("pom.xml")
+        };
+
+        let content = fs::read_to_string(&pom_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let content = XML_COMMENT_REGEX.replace_all(content, "");
+
+        let mut own = content.to_string();
+        for section in NESTED_SECTIONS {
+            own = remove_section(&own, section);
+        }
+
+        let parent = section_body(&content, "parent").map(|body| MavenCoordinate {
+            group_id: tag_text(body, "groupId").unwrap_or_default(),
+            artifact_id: tag_text(body, "artifactId").unwrap_or_default(),
+        });
+
+        let dependencies = DEPENDENCY_REGEX
+            .captures_iter(&content)
+            .map(|c| MavenCoordinate {
+                group_id: tag_text(&c[1], "groupId").unwrap_or_default(),
+                artifact_id: tag_text(&c[1], "artifactId").unwrap_or_default(),
+            })
+            .collect();
+
+        // Plugin dependencies are nested in <plugin>, strip them first
+        let plugins = PLUGIN_REGEX
+            .captures_iter(&content)
+            .filter_map(|c| tag_text(&remove_section(&c[1], "dependencies"), "artifactId"))
+            .collect();
+
+        PomXml {
+            packaging: tag_text(&own, "packaging"),
+            parent,
+            modules: MODULE_REGEX
+                .captures_iter(&own)
+                .map(|c| c[1].to_string())
+                .collect(),
+            dependencies,
+            plugins,
+        }
+    }
+
+    /// Packaging type, defaulting to `jar` like Maven does
+    pub fn get_packaging(&self) -> &str {
+        self.packaging.as_deref().unwrap_or("jar")
+    }
+
+    /// Check whether any dependency (or the parent) belongs to a groupId
+    pub fn has_group(&self, group_id: &str) -> bool {
+        self.dependencies
+            .iter()
+            .chain(self.parent.iter())
+            .any(|d| d.group_id == group_id || d.group_id.starts_with(&format!("{}.", group_id)))
+    }
+
+    pub fn has_plugin(&self, artifact_id: &str) -> bool {
+        self.plugins.iter().any(|p| p == artifact_id)
+    }
+}
+
+/// Text of the first `<tag>` in `content`
+fn tag_text(content: &str, tag: &str) -> Option<String> {
+    section_body(content, tag)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Body of the first `<tag>...</tag>` in `content`
+fn section_body<'a>(content: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = content.find(&open)? + open.len();
+    let end = content[start..].find(&close)? + start;
+    Some(&content[start..end])
+}
+
+/// Remove every `<tag>...</tag>` block from `content`
+fn remove_section(content: &str, tag: &str) -> String {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut result = content.to_string();
+
+    while let Some(start) = result.find(&open) {
+        match result[start..].find(&close) {
+            Some(end) => result.replace_range(start..start + end + close.len(), ""),
+            None => break,
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pom() {
+        let content = r#"
+<project>
+  <parent>
+    <groupId>org.springframework.boot</groupId>
+    <artifactId>spring-boot-starter-parent</artifactId>
+  </parent>
+  <groupId>com.acme</groupId>
+  <artifactId>shop</artifactId>
+  <packaging>pom</packaging>
+  <modules>
+    <module>api</module>
+    <!-- <module>legacy</module> -->
+    <module>common</module>
+  </modules>
+  <dependencies>
+    <dependency>
+      <groupId>org.springframework.boot</groupId>
+      <artifactId>spring-boot-starter-web</artifactId>
+    </dependency>
+  </dependencies>
+  <build>
+    <plugins>
+      <plugin>
+        <groupId>org.springframework.boot</groupId>
+        <artifactId>spring-boot-maven-plugin</artifactId>
+      </plugin>
+    </plugins>
+  </build>
+</project>
+"#;
+        let pom = PomXml::parse_str(content);
+
+        assert_eq!(pom.get_packaging(), "pom");
+        assert_eq!(pom.modules, vec!["api", "common"]);
+        assert!(pom.has_group("org.springframework.boot"));
+        assert!(pom.has_plugin("spring-boot-maven-plugin"));
+    }
+}
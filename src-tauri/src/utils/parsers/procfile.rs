@@ -0,0 +1,92 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static PROCFILE_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z0-9_-]+)\s*:\s*(.+)$").unwrap());
+
+/// Parsed Procfile (Heroku / foreman / overmind format)
+#[derive(Debug, Clone, Default)]
+pub struct Procfile {
+    pub processes: Vec<ProcfileProcess>,
+}
+
+/// One process type declared in a Procfile
+#[derive(Debug, Clone, Default)]
+pub struct ProcfileProcess {
+    pub name: String,
+    pub command: String,
+}
+
+impl Procfile {
+    /// Parse a Procfile from a path.
+    ///
+    /// `Procfile.dev` (used by `bin/dev`, foreman and overmind for local
+    /// development) takes precedence over the production `Procfile`.
+    pub fn parse(path: &Path) -> Option<Self> {
+        let procfile_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            ["Procfile.dev", "Procfile"]
+                .iter()
+                .map(|file| path.join(file))
+                .find(|p| p.exists())?
+        };
+
+        let content = fs::read_to_string(&procfile_path).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let processes = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| PROCFILE_LINE_REGEX.captures(line))
+            .map(|captures| ProcfileProcess {
+                name: captures[1].to_string(),
+                command: captures[2].trim().to_string(),
+            })
+            .collect();
+
+        Self { processes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_parse_procfile() {
+        let fixture = Fixture::new(&[
+            ("Procfile", "web: bundle exec puma -C config/puma.rb\n"),
+            (
+                "Procfile.dev",
+                "# local processes\nweb: bin/rails server -p 3000\ncss:   bin/rails tailwindcss:watch \n\nnot a process\n",
+            ),
+        ]);
+
+        let procfile = Procfile::parse(fixture.path()).unwrap();
+        let processes: Vec<(&str, &str)> = procfile
+            .processes
+            .iter()
+            .map(|p| (p.name.as_str(), p.command.as_str()))
+            .collect();
+        assert_eq!(
+            processes,
+            vec![
+                ("web", "bin/rails server -p 3000"),
+                ("css", "bin/rails tailwindcss:watch"),
+            ]
+        );
+
+        let production = Procfile::parse(&fixture.join("Procfile")).unwrap();
+        assert_eq!(
+            production.processes[0].command,
+            "bundle exec puma -C config/puma.rb"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static REQUIREMENT_NAME_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)").unwrap());
+
+/// Parsed pyproject.toml structure (PEP 621 plus the tool sections Warden uses)
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PyProject {
+    pub project: Option<PyProjectMeta>,
+    #[serde(rename = "dependency-groups")]
+    pub dependency_groups: HashMap<String, Vec<toml::Value>>,
+    pub tool: PyProjectTool,
+    /// Normalized names of every dependency declared in any section,
+    /// collected once parsed
+    #[serde(skip)]
+    pub dependency_names: HashSet<String>,
+}
+
+/// PEP 621 `[project]` table
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PyProjectMeta {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    #[serde(rename = "requires-python")]
+    pub requires_python: Option<String>,
+    pub dependencies: Vec<String>,
+    #[serde(rename = "optional-dependencies")]
+    pub optional_dependencies: HashMap<String, Vec<String>>,
+    pub scripts: HashMap<String, String>,
+    #[serde(rename = "gui-scripts")]
+    pub gui_scripts: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PyProjectTool {
+    pub poetry: Option<PoetryConfig>,
+    pub uv: Option<UvConfig>,
+    pub hatch: Option<HatchConfig>,
+    pub pdm: Option<PdmConfig>,
+}
+
+/// `[tool.poetry]`
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PoetryConfig {
+    pub name: Option<String>,
+    pub dependencies: HashMap<String, toml::Value>,
+    #[serde(rename = "dev-dependencies")]
+    pub dev_dependencies: HashMap<String, toml::Value>,
+    pub group: HashMap<String, PoetryGroup>,
+    pub scripts: HashMap<String, toml::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PoetryGroup {
+    pub dependencies: HashMap<String, toml::Value>,
+}
+
+/// `[tool.uv]`
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct UvConfig {
+    #[serde(rename = "dev-dependencies")]
+    pub dev_dependencies: Vec<String>,
+    pub package: Option<bool>,
+}
+
+/// `[tool.hatch]`
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct HatchConfig {
+    pub envs: HashMap<String, HatchEnv>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct HatchEnv {
+    pub dependencies: Vec<String>,
+    pub scripts: HashMap<String, toml::Value>,
+}
+
+/// `[tool.pdm]`
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PdmConfig {
+    #[serde(rename = "dev-dependencies")]
+    pub dev_dependencies: HashMap<String, Vec<String>>,
+    pub scripts: HashMap<String, toml::Value>,
+}
+
+impl PyProject {
+    /// Parse pyproject.toml from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let pyproject_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            path.join("pyproject.toml")
+        };
+
+        let content = fs::read_to_string(&pyproject_path).ok()?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        let mut pyproject: Self = toml::from_str(content).ok()?;
+        pyproject.dependency_names = pyproject.collect_dependency_names();
+        Some(pyproject)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_name(&self) -> Option<String> {
+        self.project
+            .as_ref()
+            .and_then(|p| p.name.clone())
+            .or_else(|| self.tool.poetry.as_ref().and_then(|p| p.name.clone()))
+    }
+
+    fn collect_dependency_names(&self) -> HashSet<String> {
+        let mut specs: Vec<String> = Vec::new();
+
+        if let Some(project) = &self.project {
+            specs.extend(project.dependencies.iter().cloned());
+            specs.extend(project.optional_dependencies.values().flatten().cloned());
+        }
+
+        specs.extend(
+            self.dependency_groups
+                .values()
+                .flatten()
+                .filter_map(|v| v.as_str().map(String::from)),
+        );
+
+        if let Some(poetry) = &self.tool.poetry {
+            specs.extend(poetry.dependencies.keys().cloned());
+            specs.extend(poetry.dev_dependencies.keys().cloned());
+            specs.extend(
+                poetry
+                    .group
+                    .values()
+                    .flat_map(|g| g.dependencies.keys().cloned()),
+            );
+        }
+
+        if let Some(uv) = &self.tool.uv {
+            specs.extend(uv.dev_dependencies.iter().cloned());
+        }
+
+        if let Some(hatch) = &self.tool.hatch {
+            specs.extend(
+                hatch
+                    .envs
+                    .values()
+                    .flat_map(|e| e.dependencies.iter().cloned()),
+            );
+        }
+
+        if let Some(pdm) = &self.tool.pdm {
+            specs.extend(pdm.dev_dependencies.values().flatten().cloned());
+        }
+
+        specs
+            .iter()
+            .filter_map(|spec| requirement_name(spec))
+            .filter(|name| name != "python")
+            .collect()
+    }
+
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.dependency_names
+            .contains(&normalize_package_name(name))
+    }
+
+    /// Console scripts declared in `[project.scripts]` or `[tool.poetry.scripts]`
+    pub fn get_console_scripts(&self) -> Vec<String> {
+        let mut scripts: Vec<String> = Vec::new();
+
+        if let Some(project) = &self.project {
+            scripts.extend(project.scripts.keys().cloned());
+        }
+
+        if let Some(poetry) = &self.tool.poetry {
+            scripts.extend(poetry.scripts.keys().cloned());
+        }
+
+        scripts.sort();
+        scripts.dedup();
+        scripts
+    }
+
+    /// Scripts declared in `[tool.pdm.scripts]`, with their command text
+    pub fn get_pdm_scripts(&self) -> Vec<(String, Option<String>)> {
+        let mut scripts: Vec<(String, Option<String>)> = self
+            .tool
+            .pdm
+            .as_ref()
+            .map(|pdm| {
+                pdm.scripts
+                    .iter()
+                    // `_` holds shared settings, not a script
+                    .filter(|(name, _)| name.as_str() != "_")
+                    .map(|(name, value)| (name.clone(), script_command(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        scripts.sort();
+        scripts
+    }
+
+    /// Scripts declared in `[tool.hatch.envs.<env>.scripts]`, qualified with
+    /// the environment name unless it is the default one.
+    pub fn get_hatch_scripts(&self) -> Vec<(String, Option<String>)> {
+        let mut scripts: Vec<(String, Option<String>)> = self
+            .tool
+            .hatch
+            .as_ref()
+            .map(|hatch| {
+                hatch
+                    .envs
+                    .iter()
+                    .flat_map(|(env, config)| {
+                        config.scripts.iter().map(move |(name, value)| {
+                            let qualified = if env == "default" {
+                                name.clone()
+                            } else {
+                                format!("{}:{}", env, name)
+                            };
+                            (qualified, script_command(value))
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        scripts.sort();
+        scripts
+    }
+
+    pub fn is_poetry(&self) -> bool {
+        self.tool.poetry.is_some()
+    }
+
+    pub fn is_uv(&self) -> bool {
+        self.tool.uv.is_some()
+    }
+
+    pub fn is_pdm(&self) -> bool {
+        self.tool.pdm.is_some()
+    }
+
+    /// Hatch is only a runner when it defines environments; many projects
+    /// just use it as a build backend.
+    pub fn is_hatch(&self) -> bool {
+        self.tool
+            .hatch
+            .as_ref()
+            .is_some_and(|hatch| !hatch.envs.is_empty())
+    }
+}
+
+/// Script values are either a command string, an array of commands, or a
+/// table like `{ cmd = "..." }` / `{ shell = "..." }` / `{ call = "..." }`.
+fn script_command(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(cmd) => Some(cmd.clone()),
+        toml::Value::Array(parts) => {
+            let cmds: Vec<&str> = parts.iter().filter_map(|p| p.as_str()).collect();
+            (!cmds.is_empty()).then(|| cmds.join(" && "))
+        }
+        toml::Value::Table(table) => ["cmd", "shell", "call", "script"]
+            .iter()
+            .find_map(|key| table.get(*key))
+            .and_then(|v| match v {
+                toml::Value::String(cmd) => Some(cmd.clone()),
+                toml::Value::Array(parts) => Some(
+                    parts
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+/// Extract the normalized distribution name from a PEP 508 requirement
+/// (`FastAPI[all]>=0.110` -> `fastapi`).
+pub fn requirement_name(spec: &str) -> Option<String> {
+    REQUIREMENT_NAME_REGEX
+        .captures(spec)
+        .and_then(|c| c.get(1))
+        .map(|m| normalize_package_name(m.as_str()))
+}
+
+/// PEP 503 name normalization
+pub fn normalize_package_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependencies_and_scripts() {
+        let content = r#"
+[project]
+name = "billing"
+dependencies = ["FastAPI[standard]>=0.110", "SQLAlchemy~=2.0", "uvicorn"]
+
+[project.scripts]
+billing-worker = "billing.worker:main"
+
+[tool.uv]
+dev-dependencies = ["pytest>=8"]
+
+[tool.pdm.scripts]
+_ = { env_file = ".env" }
+dev = "uvicorn billing.main:app --reload"
+"#;
+        let pyproject = PyProject::parse_str(content).unwrap();
+
+        assert!(pyproject.has_dependency("fastapi"));
+        assert!(pyproject.has_dependency("sqlalchemy"));
+        assert!(pyproject.has_dependency("pytest"));
+        assert!(!pyproject.has_dependency("python"));
+        assert!(pyproject.is_uv());
+        assert_eq!(pyproject.get_console_scripts(), vec!["billing-worker"]);
+        assert_eq!(
+            pyproject.get_pdm_scripts(),
+            vec![(
+                "dev".to_string(),
+                Some("uvicorn billing.main:app --reload".to_string())
+            )]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::utils::parsers::pyproject::{normalize_package_name, requirement_name};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Requirements collected from `requirements*.txt` files
+#[derive(Debug, Clone, Default)]
+pub struct Requirements {
+    pub packages: Vec<String>,
+}
+
+impl Requirements {
+    /// Read `requirements.txt`, `requirements-*.txt` / `requirements_*.txt`
+    /// and `requirements/*.txt` in a directory. Returns `None` when there are
+    /// no requirement files at all.
+    pub fn parse(path: &Path) -> Option<Self> {
+        let mut files: Vec<PathBuf> = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with("requirements") && name.ends_with(".txt") {
+                    files.push(entry.path());
+                }
+            }
+        }
+
+        if let Ok(entries) = fs::read_dir(path.join("requirements")) {
+            for entry in entries.flatten() {
+                if entry.path().extension().is_some_and(|ext| ext == "txt") {
+                    files.push(entry.path());
+                }
+            }
+        }
+
+        if files.is_empty() {
+            return None;
+        }
+
+        files.sort();
+
+        let mut packages: Vec<String> = files
+            .iter()
+            .filter_map(|file| fs::read_to_string(file).ok())
+            .flat_map(|content| Self::parse_str(&content))
+            .collect();
+        packages.sort();
+        packages.dedup();
+
+        Some(Self { packages })
+    }
+
+    /// Extract package names, skipping comments, options (`-r`, `-e`,
+    /// `--index-url`) and direct URL references.
+    pub fn parse_str(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('-'))
+            .filter(|line| !line.contains("://") || line.contains(" @ "))
+            .filter_map(requirement_name)
+            .collect()
+    }
+
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.packages.contains(&normalize_package_name(name))
+    }
+}
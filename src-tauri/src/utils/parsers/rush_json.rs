@@ -0,0 +1,112 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Parsed rush.json structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RushJson {
+    #[allow(dead_code)]
+    pub rush_version: Option<String>,
+    pub projects: Vec<RushProject>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RushProject {
+    pub package_name: String,
+    /// Directory relative to rush.json
+    pub project_folder: String,
+}
+
+impl RushJson {
+    /// Parse rush.json from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path.join("rush.json")).ok()?;
+        Self::parse_str(&content)
+    }
+
+    /// rush.json is JSON with comments
+    pub fn parse_str(content: &str) -> Option<Self> {
+        serde_json::from_str(&strip_json_comments(content)).ok()
+    }
+}
+
+/// Remove `//` and `/* */` comments outside of strings
+pub fn strip_json_comments(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        output.push('\n');
+                    }
+                    previous = c;
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rush_json() {
+        let rush = RushJson::parse_str(
+            r#"/**
+ * This is the main configuration file for Rush.
+ */
+{
+  "$schema": "https://developer.microsoft.com/json-schemas/rush/v5/rush.schema.json",
+  "rushVersion": "5.112.0", // pinned
+  "projects": [
+    { "packageName": "@acme/web", "projectFolder": "apps/web" },
+    /* { "packageName": "@acme/old", "projectFolder": "apps/old" }, */
+    { "packageName": "@acme/ui", "projectFolder": "libs/ui" }
+  ]
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(rush.rush_version.as_deref(), Some("5.112.0"));
+        assert_eq!(rush.projects.len(), 2);
+        assert_eq!(rush.projects[0].package_name, "@acme/web");
+        assert_eq!(rush.projects[1].project_folder, "libs/ui");
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Parsed rust-toolchain.toml, or the legacy one-line rust-toolchain file
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct RustToolchain {
+    pub toolchain: ToolchainSection,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct ToolchainSection {
+    /// `stable`, `1.77.2`, `nightly-2024-03-01`...
+    pub channel: Option<String>,
+    #[allow(dead_code)]
+    pub components: Vec<String>,
+}
+
+impl RustToolchain {
+    /// Parse rust-toolchain.toml or rust-toolchain from a directory, with
+    /// the file it came from
+    pub fn parse(path: &Path) -> Option<(Self, &'static str)> {
+        if let Ok(content) = fs::read_to_string(path.join("rust-toolchain.toml")) {
+            return Some((Self::parse_str(&content)?, "rust-toolchain.toml"));
+        }
+        let content = fs::read_to_string(path.join("rust-toolchain")).ok()?;
+        Some((Self::parse_str(&content)?, "rust-toolchain"))
+    }
+
+    /// The legacy file holds either TOML or just the channel name
+    pub fn parse_str(content: &str) -> Option<Self> {
+        if let Ok(toolchain) = toml::from_str::<Self>(content) {
+            return Some(toolchain);
+        }
+        let channel = content.trim();
+        (!channel.is_empty() && !channel.contains(char::is_whitespace)).then(|| Self {
+            toolchain: ToolchainSection {
+                channel: Some(channel.to_string()),
+                components: Vec::new(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rust_toolchain() {
+        let toolchain = RustToolchain::parse_str(
+            "[toolchain]\nchannel = \"1.77.2\"\ncomponents = [\"rustfmt\", \"clippy\"]\n",
+        )
+        .unwrap();
+        assert_eq!(toolchain.toolchain.channel.as_deref(), Some("1.77.2"));
+        assert_eq!(toolchain.toolchain.components, ["rustfmt", "clippy"]);
+
+        let legacy = RustToolchain::parse_str("nightly-2024-03-01\n").unwrap();
+        assert_eq!(
+            legacy.toolchain.channel.as_deref(),
+            Some("nightly-2024-03-01")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::config_store::config::get_config_dir;
+use crate::models::scaffold::{ScaffoldTemplate, TemplateSource};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Templates shipped with Warden
+const BUILTIN_TEMPLATES: &str = include_str!("../../../resources/scaffold-templates.json");
+
+/// User template files in the Warden config dir, first found wins
+const USER_TEMPLATE_FILES: [&str; 4] = [
+    "scaffold-templates.toml",
+    "scaffold-templates.yaml",
+    "scaffold-templates.yml",
+    "scaffold-templates.json",
+];
+
+/// Scaffold template registry: the built-ins plus the user's own, which
+/// replace built-ins with the same id
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ScaffoldTemplates {
+    #[serde(alias = "template")]
+    pub templates: Vec<ScaffoldTemplate>,
+    /// Why the user file or some of its templates were left out
+    #[serde(skip_deserializing)]
+    pub errors: Vec<String>,
+}
+
+impl ScaffoldTemplates {
+    pub fn load() -> Self {
+        let mut registry = Self::builtin();
+
+        let config_dir = get_config_dir();
+        if let Some(user_file) = USER_TEMPLATE_FILES
+            .iter()
+            .map(|file| config_dir.join(file))
+            .find(|p| p.exists())
+        {
+            match Self::parse_file(&user_file) {
+                Ok(user) => {
+                    registry.errors.extend(user.errors);
+                    for mut template in user.templates {
+                        template.source = TemplateSource::User;
+                        registry.templates.retain(|t| t.id != template.id);
+                        registry.templates.push(template);
+                    }
+                }
+                Err(e) => registry.errors.push(e),
+            }
+        }
+
+        registry
+    }
+
+    pub fn builtin() -> Self {
+        Self::parse_json(BUILTIN_TEMPLATES).unwrap_or_default()
+    }
+
+    /// Errors name the file, as they are shown apart from it
+    fn parse_file(path: &Path) -> Result<Self, String> {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", file_name, e))?;
+
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => Self::parse_yaml(&content),
+            Some("json") => Self::parse_json(&content),
+            _ => Self::parse_str(&content),
+        };
+
+        let mut templates = parsed.map_err(|e| format!("{}: {}", file_name, e))?;
+        for error in &mut templates.errors {
+            *error = format!("{}: {}", file_name, error);
+        }
+        Ok(templates)
+    }
+
+    pub fn parse_str(content: &str) -> Result<Self, String> {
+        let templates: Self = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        Ok(templates.valid())
+    }
+
+    pub fn parse_yaml(content: &str) -> Result<Self, String> {
+        let templates: Self = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+        Ok(templates.valid())
+    }
+
+    pub fn parse_json(content: &str) -> Result<Self, String> {
+        let templates: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        Ok(templates.valid())
+    }
+
+    /// Drop templates that cannot run, recording why
+    fn valid(mut self) -> Self {
+        let mut errors = Vec::new();
+        self.templates.retain(|template| {
+            let problem = if template.id.trim().is_empty() {
+                format!("template `{}` has no id", template.name)
+            } else if template.steps.is_empty() {
+                format!("template `{}` has no steps", template.id)
+            } else if !template.steps.iter().all(|step| {
+                step.first()
+                    .is_some_and(|program| !program.trim().is_empty())
+            }) {
+                format!("template `{}` has a step without a program", template.id)
+            } else {
+                return true;
+            };
+            errors.push(problem);
+            false
+        });
+        self.errors.extend(errors);
+        self
+    }
+
+    pub fn find(&self, id: &str) -> Option<&ScaffoldTemplate> {
+        self.templates.iter().find(|template| template.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::detected_service::PackageManager;
+
+    #[test]
+    fn test_parse_scaffold_templates() {
+        let builtin = ScaffoldTemplates::builtin();
+        assert!(builtin.templates.len() >= 20);
+        assert_eq!(
+            builtin.find("gin").unwrap().package_manager,
+            PackageManager::GoMod
+        );
+
+        let user = ScaffoldTemplates::parse_str(
+            r#"
+[[template]]
+id = "hono"
+name = "Hono"
+language = "typescript"
+packageManager = "pnpm"
+category = "api"
+steps = [["pnpm", "create", "hono@latest", "{name}", "--template", "nodejs"]]
+defaultPort = 3000
+
+[[template]]
+id = "broken"
+name = "No steps"
+language = "go"
+packageManager = "goMod"
+category = "backend"
+steps = []
+defaultPort = 8080
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(user.templates.len(), 1);
+        assert_eq!(user.templates[0].steps[0][2], "hono@latest");
+        assert_eq!(user.templates[0].source, TemplateSource::Builtin);
+        assert_eq!(user.errors, ["template `broken` has no steps"]);
+
+        let broken = ScaffoldTemplates::parse_json(r#"{ "templates": [{ "id": "x" }] }"#);
+        assert!(broken.is_err());
+        assert!(builtin.errors.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `Project("{type-guid}") = "Name", "src\Name\Name.csproj", "{guid}"`
+static SLN_PROJECT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^Project\("\{[^}]+\}"\)\s*=\s*"([^"]+)"\s*,\s*"([^"]+)""#).unwrap()
+});
+
+/// `<Project Path="src/Name/Name.csproj" />` in `.slnx` files
+static SLNX_PROJECT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<Project\s+Path="([^"]+)""#).unwrap());
+
+/// Project file extensions Warden understands
+const PROJECT_EXTENSIONS: [&str; 3] = [".csproj", ".fsproj", ".vbproj"];
+
+/// Parsed Visual Studio solution (`.sln` or `.slnx`)
+#[derive(Debug, Clone, Default)]
+pub struct Solution {
+    /// Solution file name
+    pub file_name: String,
+    pub projects: Vec<SolutionProject>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolutionProject {
+    pub name: String,
+    /// Project file path relative to the solution, with forward slashes
+    pub path: String,
+}
+
+impl Solution {
+    /// Parse the first solution file found in a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let mut solution_files: Vec<String> = fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| is_solution_file(name))
+            .collect();
+        solution_files.sort();
+
+        let file_name = solution_files.into_iter().next()?;
+        let content = fs::read_to_string(path.join(&file_name)).ok()?;
+
+        let mut solution = if file_name.ends_with(".slnx") {
+            Self::parse_slnx(&content)
+        } else {
+            Self::parse_sln(&content)
+        };
+        solution.file_name = file_name;
+        Some(solution)
+    }
+
+    pub fn parse_sln(content: &str) -> Self {
+        let projects = SLN_PROJECT_REGEX
+            .captures_iter(content)
+            .map(|c| SolutionProject {
+                name: c[1].to_string(),
+                path: c[2].replace('\\', "/"),
+            })
+            // Solution folders are listed as projects too
+            .filter(|p| is_project_file(&p.path))
+            .collect();
+
+        Solution {
+            file_name: String::new(),
+            projects,
+        }
+    }
+
+    pub fn parse_slnx(content: &str) -> Self {
+        let projects = SLNX_PROJECT_REGEX
+            .captures_iter(content)
+            .map(|c| c[1].replace('\\', "/"))
+            .filter(|p| is_project_file(p))
+            .map(|path| SolutionProject {
+                name: project_name(&path),
+                path,
+            })
+            .collect();
+
+        Solution {
+            file_name: String::new(),
+            projects,
+        }
+    }
+}
+
+pub fn is_solution_file(path: &str) -> bool {
+    path.ends_with(".sln") || path.ends_with(".slnx")
+}
+
+pub fn is_project_file(path: &str) -> bool {
+    PROJECT_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+/// `src/Api/Api.csproj` -> `Api`
+pub fn project_name(path: &str) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    PROJECT_EXTENSIONS
+        .iter()
+        .find_map(|ext| file.strip_suffix(ext))
+        .unwrap_or(file)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sln() {
+        let content = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "src", "src", "{11111111-1111-1111-1111-111111111111}"
+EndProject
+Project("{9A19103F-16F7-4668-BE54-9A1E7A4F7556}") = "Shop.Api", "src\Shop.Api\Shop.Api.csproj", "{22222222-2222-2222-2222-222222222222}"
+EndProject
+"#;
+        let solution = Solution::parse_sln(content);
+
+        assert_eq!(
+            solution.projects,
+            vec![SolutionProject {
+                name: "Shop.Api".to_string(),
+                path: "src/Shop.Api/Shop.Api.csproj".to_string(),
+            }]
+        );
+
+        let slnx = Solution::parse_slnx(
+            r#"<Solution><Folder Name="/src/"><Project Path="src/Shop.Worker/Shop.Worker.csproj" /></Folder></Solution>"#,
+        );
+        assert_eq!(slnx.projects[0].name, "Shop.Worker");
+    }
+}
@@ -0,0 +1,165 @@
+use serde_yaml::Value;
+use std::fs;
+use std::path::Path;
+
+/// Parsed Taskfile.yml (go-task)
+#[derive(Debug, Clone, Default)]
+pub struct Taskfile {
+    pub tasks: Vec<TaskfileTask>,
+}
+
+/// A non-internal task declared in a Taskfile
+#[derive(Debug, Clone, Default)]
+pub struct TaskfileTask {
+    pub name: String,
+    pub description: Option<String>,
+    pub commands: Vec<String>,
+}
+
+impl Taskfile {
+    /// Parse a Taskfile from a path
+    pub fn parse(path: &Path) -> Option<Self> {
+        let taskfile_path = if path.is_file() {
+            path.to_path_buf()
+        } else {
+            [
+                "Taskfile.yml",
+                "Taskfile.yaml",
+                "taskfile.yml",
+                "taskfile.yaml",
+                "Taskfile.dist.yml",
+                "Taskfile.dist.yaml",
+            ]
+            .iter()
+            .map(|file| path.join(file))
+            .find(|p| p.exists())?
+        };
+
+        let content = fs::read_to_string(&taskfile_path).ok()?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        let yaml: Value = serde_yaml::from_str(content).ok()?;
+        let tasks_map = yaml.get("tasks").and_then(|t| t.as_mapping())?;
+
+        let mut tasks = Vec::new();
+
+        for (key, value) in tasks_map {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+
+            let task = match value {
+                // Short syntax: `build: go build ./...`
+                Value::String(cmd) => TaskfileTask {
+                    name: name.to_string(),
+                    description: None,
+                    commands: vec![cmd.clone()],
+                },
+                // Short syntax: list of commands
+                Value::Sequence(cmds) => TaskfileTask {
+                    name: name.to_string(),
+                    description: None,
+                    commands: cmds.iter().filter_map(command_from_value).collect(),
+                },
+                Value::Mapping(_) => {
+                    if value.get("internal").and_then(|v| v.as_bool()) == Some(true) {
+                        continue;
+                    }
+
+                    let description = value
+                        .get("desc")
+                        .or_else(|| value.get("summary"))
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.trim().to_string());
+
+                    let mut commands: Vec<String> = value
+                        .get("cmds")
+                        .and_then(|c| c.as_sequence())
+                        .map(|seq| seq.iter().filter_map(command_from_value).collect())
+                        .unwrap_or_default();
+
+                    if let Some(cmd) = value.get("cmd").and_then(|c| c.as_str()) {
+                        commands.push(cmd.to_string());
+                    }
+
+                    TaskfileTask {
+                        name: name.to_string(),
+                        description,
+                        commands,
+                    }
+                }
+                _ => continue,
+            };
+
+            tasks.push(task);
+        }
+
+        Some(Self { tasks })
+    }
+}
+
+/// A `cmds` entry is either a plain string, `{ cmd: ... }` or `{ task: ... }`
+fn command_from_value(value: &Value) -> Option<String> {
+    if let Some(cmd) = value.as_str() {
+        return Some(cmd.to_string());
+    }
+
+    if let Some(cmd) = value.get("cmd").and_then(|c| c.as_str()) {
+        return Some(cmd.to_string());
+    }
+
+    value
+        .get("task")
+        .and_then(|t| t.as_str())
+        .map(|task| format!("task {}", task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_taskfile() {
+        let content = r#"
+version: "3"
+
+tasks:
+  build: go build ./...
+  lint:
+    - gofmt -l .
+    - go vet ./...
+  dev:
+    desc: Run the API with live reload
+    cmds:
+      - task: build
+      - cmd: air
+  migrate:
+    summary: Apply migrations
+    cmd: goose up
+  setup:
+    internal: true
+    cmds:
+      - go mod download
+"#;
+        let taskfile = Taskfile::parse_str(content).unwrap();
+        let names: Vec<&str> = taskfile.tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "lint", "dev", "migrate"]);
+
+        assert_eq!(
+            taskfile.tasks[1].commands,
+            vec!["gofmt -l .", "go vet ./..."]
+        );
+
+        let dev = &taskfile.tasks[2];
+        assert_eq!(
+            dev.description.as_deref(),
+            Some("Run the API with live reload")
+        );
+        assert_eq!(dev.commands, vec!["task build", "air"]);
+
+        assert_eq!(taskfile.tasks[3].commands, vec!["goose up"]);
+        assert!(Taskfile::parse_str("version: \"3\"\n").is_none());
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::Path;
+
+/// Parsed asdf `.tool-versions` file
+#[derive(Debug, Clone, Default)]
+pub struct ToolVersions {
+    /// Tool name and the version asdf selects (the first one listed)
+    pub tools: Vec<(String, String)>,
+}
+
+impl ToolVersions {
+    /// Parse .tool-versions from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path.join(".tool-versions")).ok()?;
+        Some(Self::parse_str(&content))
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        let tools = content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let tool = parts.next()?;
+                let version = parts.next()?;
+                Some((tool.to_string(), version.to_string()))
+            })
+            .collect();
+        Self { tools }
+    }
+
+    pub fn get(&self, tool: &str) -> Option<&str> {
+        self.tools
+            .iter()
+            .find(|(name, _)| name == tool)
+            .map(|(_, version)| version.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tool_versions() {
+        let tools = ToolVersions::parse_str(
+            "# runtimes\nnodejs 20.11.0 18.19.0\npython   3.12.1 # default\n\ngolang 1.22.0\n",
+        );
+
+        assert_eq!(tools.tools.len(), 3);
+        assert_eq!(tools.get("nodejs"), Some("20.11.0"));
+        assert_eq!(tools.get("python"), Some("3.12.1"));
+        assert_eq!(tools.get("golang"), Some("1.22.0"));
+        assert_eq!(tools.get("rust"), None);
+    }
+}
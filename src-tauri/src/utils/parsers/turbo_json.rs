@@ -0,0 +1,71 @@
+use crate::utils::parsers::strip_json_comments;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Parsed turbo.json structure
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct TurboJson {
+    /// `tasks` since Turborepo 2, `pipeline` before. Only the task names
+    /// matter: Turborepo runs the package script of the same name.
+    #[serde(alias = "pipeline")]
+    pub tasks: HashMap<String, IgnoredAny>,
+}
+
+impl TurboJson {
+    /// Parse turbo.json from a directory
+    pub fn parse(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path.join("turbo.json")).ok()?;
+        Self::parse_str(&content)
+    }
+
+    pub fn parse_str(content: &str) -> Option<Self> {
+        serde_json::from_str(&strip_json_comments(content)).ok()
+    }
+
+    /// Names of the tasks defined for every package, plus those defined for
+    /// this one
+    pub fn task_names(&self, package: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tasks
+            .keys()
+            .filter_map(|key| match key.split_once('#') {
+                Some((owner, name)) if owner == package => Some(name.to_string()),
+                Some(_) => None,
+                None => Some(key.clone()),
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_turbo_json() {
+        let turbo = TurboJson::parse_str(
+            r#"{
+  // Turborepo 1.x
+  "pipeline": {
+    "build": { "dependsOn": ["^build"], "outputs": ["dist/**"] },
+    "dev": { "cache": false, "persistent": true },
+    "web#start": { "dependsOn": ["build"] }
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(turbo.task_names("web"), vec!["build", "dev", "start"]);
+        assert_eq!(turbo.task_names("api"), vec!["build", "dev"]);
+
+        let turbo = TurboJson::parse_str(r#"{ "tasks": { "lint": {} } }"#).unwrap();
+        assert_eq!(turbo.task_names("web"), vec!["lint"]);
+    }
+}
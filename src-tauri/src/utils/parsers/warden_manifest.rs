@@ -0,0 +1,477 @@
+use crate::models::detected_service::{Framework, HealthCheck, ManifestError, ServiceCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path};
+
+/// Checked-in `.warden.toml` / `warden.yaml` declaring a repository's
+/// services explicitly.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct WardenManifest {
+    /// Project name used on import
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Keep heuristically detected services that are not declared
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub extend_detection: bool,
+    #[serde(alias = "service")]
+    pub services: Vec<ManifestService>,
+    #[serde(skip)]
+    pub file_name: String,
+    /// Syntax and schema problems; invalid services are left out
+    #[serde(skip)]
+    pub errors: Vec<ManifestError>,
+}
+
+/// A service declared in the manifest. Unset fields fall back to detection.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ManifestService {
+    pub name: String,
+    /// Directory relative to the manifest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub framework: Option<Framework>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<ServiceCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// Local URL the service is reached at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub env_files: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    /// 1-based line the service is declared at in the manifest file
+    #[serde(skip)]
+    pub line: usize,
+}
+
+/// The manifest with services left unparsed, so a typo in one service
+/// leaves out that service rather than the whole manifest
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawManifest {
+    name: Option<String>,
+    extend_detection: bool,
+    #[serde(alias = "service")]
+    services: Vec<serde_json::Value>,
+}
+
+impl ManifestService {
+    pub fn get_path(&self) -> &str {
+        self.path.as_deref().unwrap_or(".")
+    }
+}
+
+impl WardenManifest {
+    /// Find and parse the manifest of a repository root
+    pub fn parse(path: &Path) -> Option<Self> {
+        let file_name = [
+            ".warden.toml",
+            "warden.toml",
+            "warden.yaml",
+            "warden.yml",
+            "warden.json",
+        ]
+        .into_iter()
+        .find(|file| path.join(file).is_file())?;
+        Self::parse_file(&path.join(file_name))
+    }
+
+    /// Parse a manifest file; service paths resolve against its directory
+    pub fn parse_file(file: &Path) -> Option<Self> {
+        let content = fs::read_to_string(file).ok()?;
+        let file_name = file.file_name()?.to_string_lossy().to_string();
+
+        let mut manifest = if file_name.ends_with(".toml") {
+            Self::parse_str(&content)
+        } else if file_name.ends_with(".json") {
+            Self::parse_json(&content)
+        } else {
+            Self::parse_yaml(&content)
+        };
+        manifest.file_name = file_name;
+        manifest.validate(file.parent()?);
+        Some(manifest)
+    }
+
+    pub fn parse_str(content: &str) -> Self {
+        match toml::from_str(content) {
+            Ok(raw) => Self::from_raw(raw, toml_service_lines(content)),
+            Err(e) => {
+                let line = e
+                    .span()
+                    .map(|span| line_at(content, span.start))
+                    .unwrap_or(1);
+                Self::invalid(line, e.message())
+            }
+        }
+    }
+
+    pub fn parse_yaml(content: &str) -> Self {
+        match serde_yaml::from_str(content) {
+            Ok(raw) => Self::from_raw(raw, yaml_service_lines(content)),
+            Err(e) => {
+                let line = e.location().map(|l| l.line()).unwrap_or(1);
+                // serde_yaml appends " at line X column Y"; the line is reported apart
+                Self::invalid(line, without_location(&e.to_string()))
+            }
+        }
+    }
+
+    pub fn parse_json(content: &str) -> Self {
+        match serde_json::from_str(content) {
+            Ok(raw) => Self::from_raw(raw, json_service_lines(content)),
+            Err(e) => Self::invalid(e.line().max(1), without_location(&e.to_string())),
+        }
+    }
+
+    /// Parse each service on its own, recording the ones that don't fit.
+    /// `lines` holds the line each service entry starts at, in order.
+    fn from_raw(raw: RawManifest, lines: Vec<usize>) -> Self {
+        let mut manifest = Self {
+            name: raw.name,
+            extend_detection: raw.extend_detection,
+            ..Self::default()
+        };
+
+        for (index, value) in raw.services.into_iter().enumerate() {
+            let line = lines.get(index).copied().unwrap_or(1);
+            match serde_json::from_value::<ManifestService>(value.clone()) {
+                Ok(service) => manifest.services.push(ManifestService { line, ..service }),
+                Err(e) => {
+                    let name = value
+                        .get("name")
+                        .and_then(|name| name.as_str())
+                        .unwrap_or_default();
+                    manifest.errors.push(ManifestError {
+                        line,
+                        message: format!(
+                            "service `{}`: {}",
+                            name,
+                            without_location(&e.to_string())
+                        ),
+                    });
+                }
+            }
+        }
+        manifest
+    }
+
+    fn invalid(line: usize, message: &str) -> Self {
+        Self {
+            errors: vec![ManifestError {
+                line,
+                message: message.to_string(),
+            }],
+            ..Self::default()
+        }
+    }
+
+    /// Drop services that cannot be honoured, recording why
+    fn validate(&mut self, root: &Path) {
+        let mut seen = HashSet::new();
+        let mut errors = Vec::new();
+
+        let names: HashSet<String> = self.services.iter().map(|s| s.name.clone()).collect();
+
+        self.services.retain(|service| {
+            let line = service.line;
+            let mut error = |message: String| errors.push(ManifestError { line, message });
+
+            if service.name.trim().is_empty() {
+                error("service is missing a name".to_string());
+                return false;
+            }
+            if !seen.insert(service.name.clone()) {
+                error(format!("service `{}` is declared twice", service.name));
+                return false;
+            }
+            let inside = Path::new(service.get_path())
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+            if !inside {
+                error(format!(
+                    "path `{}` of service `{}` must be relative and inside the repository",
+                    service.get_path(),
+                    service.name
+                ));
+                return false;
+            }
+            if !root.join(service.get_path()).is_dir() {
+                error(format!(
+                    "path `{}` of service `{}` is not a directory",
+                    service.get_path(),
+                    service.name
+                ));
+                return false;
+            }
+            if service.port == Some(0) {
+                error(format!("service `{}` has port 0", service.name));
+                return false;
+            }
+            for dependency in &service.depends_on {
+                if !names.contains(dependency) {
+                    error(format!(
+                        "service `{}` depends on unknown service `{}`",
+                        service.name, dependency
+                    ));
+                }
+            }
+            true
+        });
+
+        self.errors.extend(errors);
+        self.errors.sort_by_key(|e| e.line);
+    }
+}
+
+/// An error message without the " at line X column Y" serde appends
+fn without_location(message: &str) -> &str {
+    message.split(" at line ").next().unwrap_or(message)
+}
+
+/// 1-based line holding a byte offset
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Lines of the `[[services]]` tables of a TOML manifest
+fn toml_service_lines(content: &str) -> Vec<usize> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim_start();
+            line.starts_with("[[services]]") || line.starts_with("[[service]]")
+        })
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+/// Lines of the `- ` items of the top-level `services:` list of a YAML manifest
+fn yaml_service_lines(content: &str) -> Vec<usize> {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut lines = Vec::new();
+    let mut in_services = false;
+    let mut item_indent = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if indent(line) == 0 && !trimmed.starts_with('-') {
+            in_services = ["services:", "service:"]
+                .iter()
+                .any(|key| trimmed.starts_with(key));
+            continue;
+        }
+        if !in_services || !(trimmed == "-" || trimmed.starts_with("- ")) {
+            continue;
+        }
+
+        // Items are the dashes at the indentation of the first one
+        if *item_indent.get_or_insert(indent(line)) == indent(line) {
+            lines.push(index + 1);
+        }
+    }
+
+    lines
+}
+
+/// Lines of the objects in the top-level `"services"` array of a JSON manifest
+fn json_service_lines(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 1;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string = String::new();
+    let mut last_key = String::new();
+    // Depth of the `services` array once entered
+    let mut services_depth = None;
+
+    for c in content.chars() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => string.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                string.clear();
+            }
+            ':' => last_key = string.clone(),
+            '[' | '{' => {
+                if services_depth == Some(depth) && c == '{' {
+                    lines.push(line);
+                }
+                if services_depth.is_none()
+                    && c == '['
+                    && depth == 1
+                    && (last_key == "services" || last_key == "service")
+                {
+                    services_depth = Some(depth + 1);
+                }
+                depth += 1;
+            }
+            ']' | '}' => {
+                depth -= 1;
+                if services_depth == Some(depth + 1) && c == ']' {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_manifest_errors_have_lines() {
+        let manifest = WardenManifest::parse_str(
+            r#"
+[[services]]
+name = "api"
+port = 8080
+dev = "make dev"
+depends_on = ["db"]
+health_check = { url = "http://localhost:8080/health" }
+
+[[services]]
+name = "web"
+prot = 3000
+"#,
+        );
+        // The typo only costs its own service
+        assert_eq!(manifest.errors.len(), 1);
+        assert_eq!(manifest.errors[0].line, 9);
+        assert!(manifest.errors[0].message.contains("prot"));
+        assert_eq!(manifest.services.len(), 1);
+        assert_eq!(manifest.services[0].name, "api");
+
+        let manifest = WardenManifest::parse_str("projct = \"shop\"\n");
+        assert_eq!(manifest.errors.len(), 1);
+        assert!(manifest.services.is_empty());
+
+        let mut manifest = WardenManifest::parse_str(
+            r#"
+[[services]]
+name = "api"
+depends_on = ["db"]
+"#,
+        );
+        manifest.validate(Path::new("."));
+        assert_eq!(
+            manifest.errors,
+            vec![ManifestError {
+                line: 2,
+                message: "service `api` depends on unknown service `db`".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_services_are_dropped() {
+        let fixture = Fixture::new(&[(
+            "repo/.warden.toml",
+            r#"
+[[services]]
+name = "web"
+path = "apps/web"
+
+[[services]]
+path = "apps/web"
+
+[[services]]
+name = "web"
+path = "."
+
+[[services]]
+name = "outside"
+path = "../other"
+
+[[services]]
+name = "absolute"
+path = "/etc"
+
+[[services]]
+name = "missing"
+path = "apps/api"
+
+[[services]]
+name = "portless"
+port = 0
+"#,
+        )]);
+        fixture.mkdir("repo/apps/web");
+        fixture.mkdir("other");
+
+        let manifest = WardenManifest::parse(&fixture.join("repo")).unwrap();
+        let names: Vec<&str> = manifest.services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["web"]);
+
+        let messages: Vec<&str> = manifest.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "service is missing a name",
+                "service `web` is declared twice",
+                "path `../other` of service `outside` must be relative and inside the repository",
+                "path `/etc` of service `absolute` must be relative and inside the repository",
+                "path `apps/api` of service `missing` is not a directory",
+                "service `portless` has port 0",
+            ]
+        );
+        // Each error points at its own `[[services]]` table
+        let lines: Vec<usize> = manifest.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [6, 9, 13, 17, 21, 25]);
+    }
+
+    #[test]
+    fn test_service_lines_in_yaml_and_json() {
+        let yaml =
+            "name: shop\nservices:\n  - name: api\n    depends_on:\n      - db\n\n  - name: api\n";
+        let manifest = WardenManifest::parse_yaml(yaml);
+        let lines: Vec<usize> = manifest.services.iter().map(|s| s.line).collect();
+        assert_eq!(lines, [3, 7]);
+
+        let json = "{\n  \"services\": [\n    { \"name\": \"api\", \"health_check\": { \"url\": \"/{\" } },\n    {\n      \"name\": \"web\"\n    }\n  ]\n}\n";
+        let manifest = WardenManifest::parse_json(json);
+        let lines: Vec<usize> = manifest.services.iter().map(|s| s.line).collect();
+        assert_eq!(lines, [3, 4]);
+    }
+}
@@ -1,43 +1,32 @@
+use crate::config_store::config::WorkspaceRoot;
 use crate::models::detected_service::{
-    DetectedProject, DetectedService, Framework, PackageManager, ServiceCategory,
+    DetectedProject, DetectedService, DetectedTask, Framework, PackageManager, ServiceCategory,
+    WorkspaceSystem,
 };
 use crate::utils::detectors::{
-    detect_commands, detect_docker_services, detect_framework, detect_monorepo,
-    detect_package_manager, detect_port, detect_service_category, get_tauri_backend_commands,
-    get_tauri_frontend_commands, get_workspace_projects, has_docker, has_docker_compose,
+    apply_rule, detect_cargo_services, detect_commands, detect_docker_services,
+    detect_dotnet_services, detect_framework_explained, detect_go_services, detect_install_status,
+    detect_package_manager, detect_port, detect_procfile_services, detect_service_category,
+    detect_tasks, detect_toolchains, detect_workspace_systems, find_build_task, find_dev_task,
+    get_tauri_backend_commands, get_tauri_frontend_commands, get_workspace_projects, has_docker,
+    has_docker_compose, workspace_tasks,
 };
-use crate::utils::parsers::{is_tauri_project, CargoToml, PackageJson, TauriConf};
+use crate::utils::parsers::{
+    is_project_file, is_solution_file, is_tauri_project, CargoToml, DetectionRules,
+    ManifestService, PackageJson, TauriConf, WardenManifest,
+};
+use crate::utils::scan_ignore::ScanIgnore;
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 fn is_valid_project(path: &Path) -> bool {
     if !path.is_dir() {
         return false;
     }
 
-    let folder_name = match path.file_name() {
-        Some(name) => name.to_string_lossy(),
-        None => return false,
-    };
-
-    if folder_name.starts_with('.') {
-        return false;
-    }
-
-    let ignored = [
-        "node_modules",
-        "target",
-        "vendor",
-        "__pycache__",
-        ".git",
-        "dist",
-        "build",
-    ];
-    if ignored.contains(&folder_name.as_ref()) {
-        return false;
-    }
-
     let has_project_file = [
         "package.json",
         "Cargo.toml",
@@ -48,15 +37,101 @@ fn is_valid_project(path: &Path) -> bool {
         "go.mod",
         "pom.xml",
         "build.gradle",
+        "build.gradle.kts",
         "mix.exs",
     ]
     .iter()
     .any(|file| path.join(file).exists());
 
+    // .NET solutions and projects are told by name; parsing them here would
+    // read them for every folder scanned
     has_project_file
+        || fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                is_solution_file(&name) || is_project_file(&name)
+            })
+}
+
+/// Scan a project. `ignore_patterns` are the folder name globs from the
+/// settings, passed in so a workspace scan loads them once.
+pub fn scan_project_deep(
+    path: &Path,
+    max_depth: u8,
+    ignore_patterns: &[String],
+) -> DetectedProject {
+    let ignore = ScanIgnore::new(path, ignore_patterns);
+    scan_project(path, max_depth, &ignore, ScanDeadline::default())
+}
+
+/// Ends a project scan once its time budget is spent. Checked between
+/// services, so the one being detected finishes first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanDeadline {
+    at: Option<Instant>,
 }
 
-pub fn scan_project_deep(path: &Path, max_depth: u8) -> DetectedProject {
+impl ScanDeadline {
+    pub fn new(at: Option<Instant>) -> Self {
+        Self { at }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+}
+
+/// A project found under a workspace root, tagged with it. `None` when the
+/// scan is still going at `deadline`: it stops there instead of finishing
+/// unseen in the background.
+pub fn scan_workspace_project(
+    path: &Path,
+    root: &WorkspaceRoot,
+    ignore_patterns: &[String],
+    deadline: Option<Instant>,
+) -> Option<DetectedProject> {
+    let ignore = ScanIgnore::new(path, ignore_patterns);
+    let deadline = ScanDeadline::new(deadline);
+    let mut project = scan_project(path, root.scan_depth, &ignore, deadline);
+    if deadline.is_expired() {
+        return None;
+    }
+
+    project.workspace_root = Some(root.path.clone());
+    project.auto_import = root.auto_import;
+    Some(project)
+}
+
+fn scan_project(
+    path: &Path,
+    max_depth: u8,
+    ignore: &ScanIgnore,
+    deadline: ScanDeadline,
+) -> DetectedProject {
+    let rules = DetectionRules::load(path);
+    let manifest = WardenManifest::parse(path);
+
+    // A manifest declaring every service makes detection of the rest moot
+    let mut project = match &manifest {
+        Some(manifest) if !manifest.services.is_empty() && !manifest.extend_detection => {
+            new_project(path)
+        }
+        _ => detect_project(path, max_depth, ignore, &rules, deadline),
+    };
+
+    if let Some(manifest) = manifest {
+        if !deadline.is_expired() {
+            apply_manifest(path, &mut project, manifest, &rules, deadline);
+        }
+    }
+    project.rule_errors = rules.errors;
+    project
+}
+
+fn new_project(path: &Path) -> DetectedProject {
     let name = path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
@@ -67,32 +142,86 @@ pub fn scan_project_deep(path: &Path, max_depth: u8) -> DetectedProject {
     project.root_package_manager = detect_package_manager(path);
     project.has_docker = has_docker(path);
     project.has_docker_compose = has_docker_compose(path);
+    project
+}
+
+fn detect_project(
+    path: &Path,
+    max_depth: u8,
+    ignore: &ScanIgnore,
+    rules: &DetectionRules,
+    deadline: ScanDeadline,
+) -> DetectedProject {
+    let mut project = new_project(path);
 
     if is_tauri_project(path) {
         project.is_tauri = true;
         project.services = scan_tauri_project(path);
-        return project;
     }
 
-    if let Some(monorepo_info) = detect_monorepo(path) {
-        project.is_monorepo = true;
-        project.monorepo_tool = monorepo_info.tool;
-        project.workspaces = monorepo_info
-            .workspace_paths
-            .iter()
-            .map(|p| p.to_string_lossy().to_string())
-            .collect();
+    let systems = detect_workspace_systems(path, max_depth, ignore);
+    project.is_monorepo = !systems.is_empty();
+    project.workspace_systems = systems
+        .iter()
+        .map(|system| WorkspaceSystem {
+            tool: system.tool.clone(),
+            root: system
+                .root
+                .strip_prefix(path)
+                .map(|p| p.to_string_lossy().to_string())
+                .ok()
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| ".".to_string()),
+        })
+        .collect();
+    for workspace_path in systems.iter().flat_map(|s| &s.workspace_paths) {
+        let workspace_path = workspace_path.to_string_lossy().to_string();
+        if !project.workspaces.contains(&workspace_path) {
+            project.workspaces.push(workspace_path);
+        }
+    }
+
+    // A workspace defined at the root replaces the root service with its
+    // members; one nested below (a Cargo workspace in `server/`) does not
+    if !systems.iter().any(|system| system.root == path) && !project.is_tauri {
+        if let Some(service) = scan_single_service(path, path, rules) {
+            project.services.extend(expand_service(path, service));
+        }
+    }
 
-        for workspace_path in &monorepo_info.workspace_paths {
-            if let Some(service) = scan_single_service(workspace_path, path) {
-                project.services.push(service);
+    if max_depth > 1 || !systems.is_empty() {
+        for project_path in get_workspace_projects(path, max_depth, ignore, &systems) {
+            if deadline.is_expired() {
+                break;
+            }
+            let dir = project_path.to_string_lossy();
+            if project.services.iter().any(|s| s.path == dir) {
+                continue;
+            }
+            if let Some(service) = scan_single_service(&project_path, path, rules) {
+                project
+                    .services
+                    .extend(expand_service(&project_path, service));
             }
         }
-    } else if let Some(service) = scan_single_service(path, path) {
-        project.services.push(service);
     }
 
-    if project.has_docker_compose {
+    // Workspace tools run their members through their own CLI
+    for system in &systems {
+        for member in &system.workspace_paths {
+            let tasks = workspace_tasks(system, member);
+            if tasks.is_empty() {
+                continue;
+            }
+
+            let dir = member.to_string_lossy();
+            for service in project.services.iter_mut().filter(|s| s.path == dir) {
+                apply_workspace_tasks(service, &tasks);
+            }
+        }
+    }
+
+    if project.has_docker_compose && !deadline.is_expired() {
         let docker_services = detect_docker_services(path);
         for docker_service in docker_services {
             let is_duplicate = project.services.iter().any(|s| {
@@ -106,21 +235,144 @@ pub fn scan_project_deep(path: &Path, max_depth: u8) -> DetectedProject {
         }
     }
 
-    if max_depth > 1 && !project.is_monorepo {
-        let additional_projects = get_workspace_projects(path, max_depth);
-        for additional_path in additional_projects {
-            if additional_path != path.to_path_buf() {
-                if let Some(service) = scan_single_service(&additional_path, path) {
-                    let is_duplicate = project.services.iter().any(|s| s.path == service.path);
-                    if !is_duplicate {
-                        project.services.push(service);
-                    }
-                }
-            }
+    project
+}
+
+fn apply_workspace_tasks(service: &mut DetectedService, tasks: &[DetectedTask]) {
+    if let Some(task) = find_dev_task(tasks) {
+        service.dev_command = Some(task.command.clone());
+    }
+    if let Some(task) = find_build_task(tasks) {
+        service.build_command = Some(task.command.clone());
+    }
+    service.tasks.extend(tasks.iter().cloned());
+}
+
+/// Replace detected services with the ones declared in the manifest. Fields
+/// a declaration leaves out are filled from detection of its directory.
+fn apply_manifest(
+    path: &Path,
+    project: &mut DetectedProject,
+    manifest: WardenManifest,
+    rules: &DetectionRules,
+    deadline: ScanDeadline,
+) {
+    project.manifest = Some(manifest.file_name.clone());
+    project.manifest_errors = manifest.errors;
+
+    if manifest.services.is_empty() {
+        return;
+    }
+
+    let declared: Vec<DetectedService> = manifest
+        .services
+        .iter()
+        .take_while(|_| !deadline.is_expired())
+        .map(|declaration| declared_service(path, declaration, rules))
+        .collect();
+
+    if manifest.extend_detection {
+        project.services.retain(|detected| {
+            !declared
+                .iter()
+                .any(|d| d.name == detected.name || d.path == detected.path)
+        });
+        project.services.splice(0..0, declared);
+    } else {
+        project.services = declared;
+    }
+}
+
+/// One service directory as a scan of its project reports it, manifest
+/// declaration included
+pub fn detect_service(path: &Path, root: &Path, rules: &DetectionRules) -> Option<DetectedService> {
+    let declaration = WardenManifest::parse(root).and_then(|manifest| {
+        manifest
+            .services
+            .into_iter()
+            .find(|declaration| root.join(declaration.get_path()) == path)
+    });
+
+    match declaration {
+        Some(declaration) => Some(declared_service(root, &declaration, rules)),
+        None => scan_single_service(path, root, rules),
+    }
+}
+
+/// A manifest declaration completed with what detection finds in its directory
+pub fn declared_service(
+    root: &Path,
+    declaration: &ManifestService,
+    rules: &DetectionRules,
+) -> DetectedService {
+    let dir = root.join(declaration.get_path());
+    let mut service = scan_single_service(&dir, root, rules).unwrap_or_else(|| {
+        DetectedService::new(
+            declaration.name.clone(),
+            dir.to_string_lossy().to_string(),
+            declaration.get_path().to_string(),
+        )
+    });
+
+    service.name = declaration.name.clone();
+    if let Some(framework) = &declaration.framework {
+        service.framework = framework.clone();
+        service.framework_label = None;
+        service.update_stack_from_framework();
+    }
+    if let Some(stack) = &declaration.stack {
+        service.stack = stack.clone();
+    }
+    if let Some(category) = &declaration.category {
+        service.category = category.clone();
+    }
+    if declaration.port.is_some() {
+        service.port = declaration.port;
+    }
+
+    let commands = [
+        (&mut service.dev_command, &declaration.dev),
+        (&mut service.build_command, &declaration.build),
+        (&mut service.start_command, &declaration.start),
+        (&mut service.install_command, &declaration.install),
+    ];
+    for (command, declared) in commands {
+        if declared.is_some() {
+            *command = declared.clone();
         }
     }
 
-    project
+    service.env_files = declaration.env_files.clone();
+    service.depends_on = declaration.depends_on.clone();
+    service.health_check = declaration.health_check.clone();
+    service
+}
+
+/// Split a directory that runs several processes into one service each:
+/// Procfile entries first, then .NET solution projects, Go binaries under
+/// `cmd/` and Cargo binaries.
+fn expand_service(path: &Path, service: DetectedService) -> Vec<DetectedService> {
+    let procfile_services = detect_procfile_services(path, &service);
+    if !procfile_services.is_empty() {
+        return procfile_services;
+    }
+
+    let dotnet_services = detect_dotnet_services(path, &service);
+    if !dotnet_services.is_empty() {
+        return dotnet_services;
+    }
+
+    let go_services = detect_go_services(path, &service);
+    if !go_services.is_empty() {
+        return go_services;
+    }
+
+    let cargo_services = detect_cargo_services(path, &service);
+    if !cargo_services.is_empty() {
+        return cargo_services;
+    }
+
+    vec![service]
 }
 
 fn scan_tauri_project(path: &Path) -> Vec<DetectedService> {
@@ -189,7 +441,11 @@ fn scan_tauri_project(path: &Path) -> Vec<DetectedService> {
     services
 }
 
-fn scan_single_service(path: &Path, root_path: &Path) -> Option<DetectedService> {
+fn scan_single_service(
+    path: &Path,
+    root_path: &Path,
+    rules: &DetectionRules,
+) -> Option<DetectedService> {
     if !path.exists() || !path.is_dir() {
         return None;
     }
@@ -216,11 +472,17 @@ fn scan_single_service(path: &Path, root_path: &Path) -> Option<DetectedService>
 
     let package_json = PackageJson::parse(path);
     let cargo_toml = CargoToml::parse(path);
+    service.tasks = detect_tasks(path);
 
     service.package_manager = detect_package_manager(path);
-    service.framework = detect_framework(path, package_json.as_ref(), cargo_toml.as_ref());
+    let (evidence, rule) =
+        detect_framework_explained(path, package_json.as_ref(), cargo_toml.as_ref(), rules);
+    service.framework = evidence.framework.clone();
+    service.detection_evidence = Some(evidence);
     service.category = detect_service_category(path, &service.framework, package_json.as_ref());
     service.port = detect_port(path, &service.framework, package_json.as_ref());
+    service.toolchains = detect_toolchains(path, root_path);
+    service.install_status = detect_install_status(path, &service.package_manager);
 
     let commands = detect_commands(
         path,
@@ -228,12 +490,17 @@ fn scan_single_service(path: &Path, root_path: &Path) -> Option<DetectedService>
         &service.package_manager,
         package_json.as_ref(),
         cargo_toml.as_ref(),
+        &service.tasks,
     );
     service.dev_command = commands.dev;
     service.build_command = commands.build;
     service.start_command = commands.start;
     service.install_command = commands.install;
 
+    if let Some(rule) = rule {
+        apply_rule(&mut service, rule);
+    }
+
     service.update_stack_from_framework();
     service.update_port_from_framework();
     service.update_install_from_package_manager();
@@ -241,40 +508,99 @@ fn scan_single_service(path: &Path, root_path: &Path) -> Option<DetectedService>
     Some(service)
 }
 
-pub fn scan_workspace_deep(workspace_path: &str, max_depth: u8) -> Vec<DetectedProject> {
-    let path = Path::new(workspace_path);
-
-    let entries: Vec<_> = match fs::read_dir(path) {
-        Ok(entries) => entries.filter_map(|e| e.ok()).collect(),
-        Err(_) => return Vec::new(),
-    };
-
-    entries
+/// Scan every configured workspace root in parallel
+pub fn scan_workspace_roots(
+    roots: &[WorkspaceRoot],
+    ignore_patterns: &[String],
+) -> Vec<DetectedProject> {
+    workspace_projects(roots, ignore_patterns)
         .into_par_iter()
-        .filter_map(|entry| {
-            let entry_path = entry.path();
+        .filter_map(|(path, root)| scan_workspace_project(&path, root, ignore_patterns, None))
+        .collect()
+}
 
-            if !entry_path.is_dir() {
-                return None;
-            }
+/// Project folders under the roots with the root each was found under.
+/// Overlapping roots (`~/work` and `~/work/*`) list a folder once.
+pub fn workspace_projects<'a>(
+    roots: &'a [WorkspaceRoot],
+    ignore_patterns: &[String],
+) -> Vec<(PathBuf, &'a WorkspaceRoot)> {
+    let mut seen = HashSet::new();
+    roots
+        .iter()
+        .flat_map(|root| {
+            root.expand()
+                .into_iter()
+                .flat_map(|dir| {
+                    workspace_candidates(&dir, &[ignore_patterns, &root.ignore].concat())
+                })
+                .map(move |path| (path, root))
+        })
+        .filter(|(path, _)| seen.insert(path.clone()))
+        .collect()
+}
 
-            let folder_name = entry_path.file_name()?;
-            let folder_name = folder_name.to_string_lossy();
+/// Folders directly under a workspace directory that look like projects
+fn workspace_candidates(path: &Path, ignore_patterns: &[String]) -> Vec<PathBuf> {
+    let ignore = ScanIgnore::new(path, ignore_patterns);
 
-            if folder_name.starts_with('.')
-                || matches!(
-                    folder_name.as_ref(),
-                    "node_modules" | "target" | "dist" | "build" | "__pycache__" | "vendor"
-                )
-            {
-                return None;
-            }
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
 
-            if is_valid_project(&entry_path) {
-                Some(scan_project_deep(&entry_path, max_depth))
-            } else {
-                None
-            }
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|entry_path| {
+            entry_path.is_dir() && !ignore.is_ignored(entry_path) && is_valid_project(entry_path)
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::detected_service::Framework;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_root_service_beside_nested_workspace() {
+        let fixture = Fixture::new(&[
+            (
+                "package.json",
+                r#"{ "name": "shop", "scripts": { "dev": "next dev" }, "dependencies": { "next": "14.2.0" } }"#,
+            ),
+            ("server/Cargo.toml", "[workspace]\nmembers = [\"api\"]\n"),
+            (
+                "server/api/Cargo.toml",
+                "[package]\nname = \"api\"\nversion = \"0.1.0\"\n",
+            ),
+            ("server/api/src/main.rs", "fn main() {}\n"),
+        ]);
+        let root = fixture.path();
+
+        let project = scan_project_deep(root, 3, &[]);
+        assert!(project.is_monorepo);
+        assert_eq!(project.workspace_systems[0].root, "server");
+
+        let root_service = project
+            .services
+            .iter()
+            .find(|s| s.path == root.to_string_lossy())
+            .expect("root service");
+        assert_eq!(root_service.framework, Framework::NextJs);
+        assert!(project
+            .services
+            .iter()
+            .any(|s| s.path == root.join("server/api").to_string_lossy()));
+
+        // A workspace defined at the root stands in for the root service
+        fixture.write("pnpm-workspace.yaml", "packages:\n  - \"apps/*\"\n");
+        fixture.write("apps/web/package.json", r#"{ "name": "web" }"#);
+        let project = scan_project_deep(root, 3, &[]);
+        assert!(!project
+            .services
+            .iter()
+            .any(|s| s.path == root.to_string_lossy()));
+    }
+}
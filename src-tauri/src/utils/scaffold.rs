@@ -0,0 +1,525 @@
+use crate::models::scaffold::{ScaffoldParams, ScaffoldTemplate};
+use crate::utils::launch_env::{find_program, read_lines, shell_environment};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A template instantiated for a target folder, ready to run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldPlan {
+    pub template_id: String,
+    /// Folder the project ends up in, `<folder>/<name>`
+    pub project_dir: String,
+    /// Folder the steps run in: the project itself for templates that
+    /// create it, its parent otherwise
+    pub working_dir: String,
+    pub create_folder: bool,
+    /// Program and arguments of each step, placeholders replaced
+    pub steps: Vec<Vec<String>>,
+    pub files: BTreeMap<String, String>,
+    pub port: u16,
+}
+
+/// Project names become a folder and a program argument, so only plain
+/// names are accepted: no separators, no leading `-` or `.`
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid project name '{}': use letters, digits, '.', '_' and '-', starting with a letter or digit",
+            name
+        ))
+    }
+}
+
+/// Check the target folder and work out what a template will run there
+pub fn plan_scaffold(
+    template: &ScaffoldTemplate,
+    folder: &Path,
+    params: &ScaffoldParams,
+) -> Result<ScaffoldPlan, String> {
+    validate_name(&params.name)?;
+
+    if !folder.is_absolute() {
+        return Err(format!(
+            "Folder must be an absolute path: {}",
+            folder.display()
+        ));
+    }
+    if !folder.is_dir() {
+        return Err(format!("Folder does not exist: {}", folder.display()));
+    }
+    let project_dir = folder.join(&params.name);
+    if project_dir.exists() {
+        return Err(format!("{} already exists", project_dir.display()));
+    }
+
+    for file in template.files.keys() {
+        if !Path::new(file)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(format!("Template file outside the project: {}", file));
+        }
+    }
+
+    let port = params.port.unwrap_or(template.default_port);
+    let package_manager = params
+        .package_manager
+        .as_ref()
+        .unwrap_or(&template.package_manager);
+    let language = params.language.unwrap_or(template.language);
+    let port_value = port.to_string();
+    let values = [
+        ("{name}", params.name.as_str()),
+        ("{port}", port_value.as_str()),
+        ("{packageManager}", package_manager.cli_name()),
+        ("{language}", language.as_str()),
+    ];
+    let substitute = |arg: &String| {
+        values
+            .iter()
+            .fold(arg.clone(), |arg, (placeholder, value)| {
+                arg.replace(placeholder, value)
+            })
+    };
+
+    let working_dir = if template.create_folder {
+        &project_dir
+    } else {
+        folder
+    };
+
+    Ok(ScaffoldPlan {
+        template_id: template.id.clone(),
+        project_dir: project_dir.to_string_lossy().to_string(),
+        working_dir: working_dir.to_string_lossy().to_string(),
+        create_folder: template.create_folder,
+        steps: template
+            .steps
+            .iter()
+            .map(|step| step.iter().map(substitute).collect())
+            .collect(),
+        files: template
+            .files
+            .iter()
+            .map(|(path, content)| (path.clone(), substitute(content)))
+            .collect(),
+        port,
+    })
+}
+
+/// Command for one step, run directly rather than through a shell. The
+/// program is looked up on the login shell's PATH, where version managers
+/// put their shims.
+pub fn step_command(plan: &ScaffoldPlan, step: &[String]) -> Result<Command, String> {
+    let (program, args) = step
+        .split_first()
+        .ok_or_else(|| "Empty scaffold step".to_string())?;
+
+    let environment = shell_environment();
+    let resolved = if Path::new(program).components().count() > 1 {
+        PathBuf::from(program)
+    } else {
+        environment
+            .get("PATH")
+            .and_then(|path| find_program(path, program))
+            .ok_or_else(|| format!("{} not found on PATH", program))?
+    };
+
+    let mut command = Command::new(resolved);
+    command
+        .args(args)
+        .current_dir(&plan.working_dir)
+        .env_clear()
+        .envs(environment);
+    Ok(command)
+}
+
+/// How a scaffold run ended
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ScaffoldStatus {
+    Succeeded,
+    Failed,
+    Cancelled,
+    TimedOut,
+}
+
+/// Outcome of `run_scaffold`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldRun {
+    pub status: ScaffoldStatus,
+    /// Exit code of the last step run; `None` when it was killed
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// How often a running step is checked for cancellation and timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time a killed step gets to exit on SIGTERM before it is sent SIGKILL
+#[cfg(not(target_os = "windows"))]
+const KILL_GRACE: Duration = Duration::from_secs(3);
+
+/// Run the steps of a plan one after another, then write its files.
+///
+/// Each step runs in its own process group with stdin closed, so a
+/// generator that prompts fails instead of waiting forever, and cancelling
+/// or timing out kills the whole tree it spawned. A step is only done once
+/// its output is closed too, which a background process it started may
+/// hold on to. The project folder is removed again unless every step
+/// succeeds.
+pub fn run_scaffold(
+    plan: &ScaffoldPlan,
+    timeout: Option<Duration>,
+    cancelled: &AtomicBool,
+    on_step: &dyn Fn(usize, &[String]),
+    on_line: &(dyn Fn(String, bool) + Sync),
+) -> ScaffoldRun {
+    let started = Instant::now();
+    let deadline = timeout.map(|timeout| started + timeout);
+
+    let (status, exit_code, error) = run_steps(plan, deadline, cancelled, on_step, on_line);
+    if status != ScaffoldStatus::Succeeded {
+        fs::remove_dir_all(&plan.project_dir).ok();
+    }
+
+    ScaffoldRun {
+        status,
+        exit_code,
+        duration_ms: started.elapsed().as_millis() as u64,
+        error,
+    }
+}
+
+fn run_steps(
+    plan: &ScaffoldPlan,
+    deadline: Option<Instant>,
+    cancelled: &AtomicBool,
+    on_step: &dyn Fn(usize, &[String]),
+    on_line: &(dyn Fn(String, bool) + Sync),
+) -> (ScaffoldStatus, Option<i32>, Option<String>) {
+    let failed = |error: String| (ScaffoldStatus::Failed, None, Some(error));
+
+    if plan.create_folder {
+        if let Err(e) = fs::create_dir(&plan.project_dir) {
+            return failed(e.to_string());
+        }
+    }
+
+    let mut exit_code = None;
+    for (index, step) in plan.steps.iter().enumerate() {
+        on_step(index, step);
+
+        let mut command = match step_command(plan, step) {
+            Ok(command) => command,
+            Err(e) => return failed(e),
+        };
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => return failed(format!("Failed to run {}: {}", step[0], e)),
+        };
+
+        let (sender, lines) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_reader(stdout, false, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_reader(stderr, true, sender);
+        }
+
+        let status = match wait_or_kill(&mut child, &lines, on_line, deadline, cancelled) {
+            Ok(status) => status,
+            Err(stopped) => return (stopped, None, None),
+        };
+        exit_code = status.code();
+        if !status.success() {
+            return (
+                ScaffoldStatus::Failed,
+                exit_code,
+                Some(format!(
+                    "{} failed with exit code {}",
+                    step.join(" "),
+                    exit_code.map_or_else(|| "unknown".to_string(), |c| c.to_string())
+                )),
+            );
+        }
+    }
+
+    match write_files(plan) {
+        Ok(()) => (ScaffoldStatus::Succeeded, exit_code, None),
+        Err(e) => (ScaffoldStatus::Failed, exit_code, Some(e)),
+    }
+}
+
+/// Forward a pipe's lines over a channel. The thread is not joined: it
+/// ends once everything holding the pipe is gone, which a killed run no
+/// longer waits for.
+fn spawn_reader(output: impl Read + Send + 'static, stderr: bool, sender: Sender<(String, bool)>) {
+    thread::spawn(move || {
+        read_lines(output, &mut |line| {
+            sender.send((line, stderr)).ok();
+        })
+    });
+}
+
+/// Wait for a step to exit and its output to close, passing lines on as
+/// they come, and kill its process group once the run is cancelled or past
+/// its deadline
+fn wait_or_kill(
+    child: &mut Child,
+    lines: &Receiver<(String, bool)>,
+    on_line: &(dyn Fn(String, bool) + Sync),
+    deadline: Option<Instant>,
+    cancelled: &AtomicBool,
+) -> Result<ExitStatus, ScaffoldStatus> {
+    let mut exited = None;
+    loop {
+        let closed = match lines.recv_timeout(POLL_INTERVAL) {
+            Ok((line, stderr)) => {
+                on_line(line, stderr);
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+
+        if exited.is_none() {
+            match child.try_wait() {
+                Ok(status) => exited = status,
+                Err(_) => return Err(ScaffoldStatus::Failed),
+            }
+        }
+        match exited {
+            Some(status) if closed => return Ok(status),
+            // Nothing left to receive, so the channel no longer paces the loop
+            None if closed => thread::sleep(POLL_INTERVAL),
+            _ => {}
+        }
+
+        let stopped = if cancelled.load(Ordering::Relaxed) {
+            ScaffoldStatus::Cancelled
+        } else if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            ScaffoldStatus::TimedOut
+        } else {
+            continue;
+        };
+
+        kill_process_group(child);
+        return Err(stopped);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill_process_group(child: &mut Child) {
+    // The step leads its own group, whose id is its pid. The group outlives
+    // the step while processes it started are still in it.
+    let group = child.id() as libc::pid_t;
+    let signal = |signal| unsafe { libc::killpg(group, signal) } == 0;
+
+    if signal(libc::SIGTERM) {
+        let grace = Instant::now() + KILL_GRACE;
+        while Instant::now() < grace {
+            child.try_wait().ok();
+            // Signal 0 only checks whether anything is left in the group
+            if !signal(0) {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        signal(libc::SIGKILL);
+    }
+    child.kill().ok();
+    child.wait().ok();
+}
+
+#[cfg(target_os = "windows")]
+fn kill_process_group(child: &mut Child) {
+    Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &child.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok();
+    child.kill().ok();
+    child.wait().ok();
+}
+
+fn write_files(plan: &ScaffoldPlan) -> Result<(), String> {
+    let project_dir = Path::new(&plan.project_dir);
+    for (file, content) in &plan.files {
+        let path = project_dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, content).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::detected_service::PackageManager;
+    use crate::utils::parsers::ScaffoldTemplates;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_plan_scaffold() {
+        let fixture = Fixture::new(&[]);
+        fixture.mkdir("taken");
+        let folder = fixture.path();
+        let templates = ScaffoldTemplates::builtin();
+        let nestjs = templates.find("nestjs").unwrap();
+
+        let mut params = ScaffoldParams {
+            name: "api".to_string(),
+            package_manager: Some(PackageManager::Pnpm),
+            language: None,
+            port: None,
+        };
+        let plan = plan_scaffold(nestjs, folder, &params).unwrap();
+        assert_eq!(plan.working_dir, folder.to_string_lossy());
+        assert_eq!(plan.port, nestjs.default_port);
+        assert_eq!(
+            plan.steps[0][1..],
+            [
+                "@nestjs/cli@latest",
+                "new",
+                "api",
+                "--strict",
+                "--skip-git",
+                "--package-manager",
+                "pnpm"
+            ]
+        );
+
+        for name in ["taken", "../escape", "-rf", ".hidden", "my app", ""] {
+            params.name = name.to_string();
+            assert!(plan_scaffold(nestjs, folder, &params).is_err(), "{}", name);
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_run_scaffold_timeout() {
+        let fixture = Fixture::new(&[]);
+        let project_dir = fixture.join("app");
+        let mut plan = ScaffoldPlan {
+            template_id: "test".to_string(),
+            project_dir: project_dir.to_string_lossy().to_string(),
+            working_dir: project_dir.to_string_lossy().to_string(),
+            create_folder: true,
+            steps: vec![vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "echo started; read answer; sleep 30".to_string(),
+            ]],
+            files: BTreeMap::new(),
+            port: 3000,
+        };
+
+        let lines = std::sync::Mutex::new(Vec::new());
+        let run = run_scaffold(
+            &plan,
+            Some(Duration::from_millis(500)),
+            &AtomicBool::new(false),
+            &|_, _| {},
+            &|line, _| lines.lock().unwrap().push(line),
+        );
+        // stdin is closed, so `read` returns and the step reaches `sleep`
+        assert_eq!(run.status, ScaffoldStatus::TimedOut);
+        assert!(run.duration_ms < 5000);
+        assert_eq!(*lines.lock().unwrap(), ["started"]);
+        assert!(!project_dir.exists());
+
+        plan.steps = vec![vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            "exit 3".to_string(),
+        ]];
+        let run = run_scaffold(&plan, None, &AtomicBool::new(false), &|_, _| {}, &|_, _| {});
+        assert_eq!(run.status, ScaffoldStatus::Failed);
+        assert_eq!(run.exit_code, Some(3));
+
+        // Invalid UTF-8 doesn't end the output
+        plan.steps = vec![vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            r"printf 'caf\351\nnext\n'".to_string(),
+        ]];
+        let lines = std::sync::Mutex::new(Vec::new());
+        let run = run_scaffold(
+            &plan,
+            None,
+            &AtomicBool::new(false),
+            &|_, _| {},
+            &|line, _| lines.lock().unwrap().push(line),
+        );
+        assert_eq!(run.status, ScaffoldStatus::Succeeded);
+        assert_eq!(*lines.lock().unwrap(), ["caf\u{FFFD}", "next"]);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_cancel_scaffold() {
+        let fixture = Fixture::new(&[]);
+        let project_dir = fixture.join("app");
+        // The step exits at once but leaves a process holding its output
+        let plan = ScaffoldPlan {
+            template_id: "test".to_string(),
+            project_dir: project_dir.to_string_lossy().to_string(),
+            working_dir: project_dir.to_string_lossy().to_string(),
+            create_folder: true,
+            steps: vec![vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "sleep 30 & echo started".to_string(),
+            ]],
+            files: BTreeMap::new(),
+            port: 3000,
+        };
+
+        let cancelled = AtomicBool::new(false);
+        let run = thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(500));
+                cancelled.store(true, Ordering::Relaxed);
+            });
+            run_scaffold(&plan, None, &cancelled, &|_, _| {}, &|_, _| {})
+        });
+        assert_eq!(run.status, ScaffoldStatus::Cancelled);
+        assert!(run.duration_ms < 5000);
+        assert!(!project_dir.exists());
+    }
+}
@@ -0,0 +1,123 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Decides which directories the scanner skips: hidden folders, the global
+/// patterns from settings, and whatever `.gitignore` / `.ignore` files between
+/// the scan root and the directory exclude.
+pub struct ScanIgnore {
+    root: PathBuf,
+    patterns: Vec<glob::Pattern>,
+    /// Parsed ignore files per directory; `None` when it has none
+    ignore_files: Mutex<HashMap<PathBuf, Option<Gitignore>>>,
+}
+
+impl ScanIgnore {
+    /// `patterns` are globs matched against folder names, usually the
+    /// settings' `ignore_patterns`, which callers load once per scan
+    pub fn new(root: &Path, patterns: &[String]) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            patterns: patterns
+                .iter()
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect(),
+            ignore_files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy()) else {
+            return false;
+        };
+
+        if name.starts_with('.') || self.patterns.iter().any(|p| p.matches(&name)) {
+            return true;
+        }
+
+        self.is_git_ignored(path)
+    }
+
+    /// The closest ignore file with an opinion wins, as in git
+    fn is_git_ignored(&self, path: &Path) -> bool {
+        if !path.starts_with(&self.root) {
+            return false;
+        }
+
+        let Ok(mut cache) = self.ignore_files.lock() else {
+            return false;
+        };
+
+        for dir in path.ancestors().skip(1) {
+            let matcher = cache
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| load_ignore_files(dir));
+
+            if let Some(matcher) = matcher {
+                match matcher.matched_path_or_any_parents(path, true) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+
+            if dir == self.root {
+                break;
+            }
+        }
+
+        false
+    }
+}
+
+fn load_ignore_files(dir: &Path) -> Option<Gitignore> {
+    let files: Vec<PathBuf> = [".gitignore", ".ignore"]
+        .iter()
+        .map(|file| dir.join(file))
+        .filter(|file| file.is_file())
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(dir);
+    for file in files {
+        builder.add(file);
+    }
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+
+    #[test]
+    fn test_gitignore_and_patterns() {
+        let fixture = Fixture::new(&[
+            (".gitignore", "data/\ngenerated/\n"),
+            ("apps/.ignore", "!generated/\n"),
+        ]);
+        for dir in [
+            "apps/web",
+            "apps/generated",
+            "apps/keep",
+            "data",
+            "coverage",
+        ] {
+            fixture.mkdir(dir);
+        }
+        let root = fixture.path().to_path_buf();
+
+        let ignore = ScanIgnore::new(&root, &["cover*".to_string()]);
+
+        assert!(ignore.is_ignored(&root.join("data")));
+        assert!(ignore.is_ignored(&root.join("coverage")));
+        assert!(!ignore.is_ignored(&root.join("apps/web")));
+        assert!(!ignore.is_ignored(&root.join("apps/keep")));
+        // The closer `.ignore` re-includes it
+        assert!(!ignore.is_ignored(&root.join("apps/generated")));
+    }
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A throwaway directory tree for tests. It is removed when dropped, also
+/// when the test panics, and its name is unique across test binaries.
+pub struct Fixture {
+    dir: TempDir,
+}
+
+impl Fixture {
+    /// A directory holding the given files; parent directories are created
+    /// as needed
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        let fixture = Self {
+            dir: tempfile::Builder::new()
+                .prefix("warden-")
+                .tempdir()
+                .expect("create fixture directory"),
+        };
+        for (file, content) in files {
+            fixture.write(file, content);
+        }
+        fixture
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.dir.path().join(path)
+    }
+
+    pub fn write(&self, file: &str, content: &str) -> PathBuf {
+        let path = self.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    pub fn mkdir(&self, dir: &str) -> PathBuf {
+        let path = self.join(dir);
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+}
@@ -0,0 +1,192 @@
+use crate::config_store::config::WorkspaceRoot;
+use crate::models::detected_service::DetectedProject;
+use crate::utils::project_scanner::{scan_workspace_project, workspace_projects};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Time one project may take before the scan moves on without it
+pub const PROJECT_SCAN_BUDGET: Duration = Duration::from_secs(30);
+
+/// What a running workspace scan reports
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ScanEvent {
+    /// A project is starting; `projects_scanned` of `projects_total` are done
+    Progress {
+        projects_scanned: usize,
+        projects_total: usize,
+        projects_found: usize,
+        current_path: String,
+    },
+    Project {
+        project: DetectedProject,
+    },
+    /// The project overran its budget and was left out; its scan stopped
+    TimedOut {
+        path: String,
+    },
+    Finished {
+        projects_found: usize,
+        cancelled: bool,
+    },
+}
+
+/// Scan workspace roots, reporting each project as soon as it is ready.
+/// Stops picking up new projects once `cancelled` is set.
+pub fn scan_workspace_streaming(
+    roots: &[WorkspaceRoot],
+    ignore_patterns: &[String],
+    cancelled: &AtomicBool,
+    budget: Duration,
+    emit: &(dyn Fn(ScanEvent) + Sync),
+) {
+    let projects = workspace_projects(roots, ignore_patterns);
+    let scanned = AtomicUsize::new(0);
+    let found = AtomicUsize::new(0);
+
+    projects.par_iter().for_each(|(path, root)| {
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        emit(ScanEvent::Progress {
+            projects_scanned: scanned.load(Ordering::Relaxed),
+            projects_total: projects.len(),
+            projects_found: found.load(Ordering::Relaxed),
+            current_path: path.to_string_lossy().to_string(),
+        });
+
+        let deadline = Instant::now() + budget;
+        match scan_workspace_project(path, root, ignore_patterns, Some(deadline)) {
+            Some(project) => {
+                found.fetch_add(1, Ordering::Relaxed);
+                emit(ScanEvent::Project { project });
+            }
+            None => emit(ScanEvent::TimedOut {
+                path: path.to_string_lossy().to_string(),
+            }),
+        }
+        scanned.fetch_add(1, Ordering::Relaxed);
+    });
+
+    emit(ScanEvent::Finished {
+        projects_found: found.load(Ordering::Relaxed),
+        cancelled: cancelled.load(Ordering::Relaxed),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_fixtures::Fixture;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    fn workspace() -> Fixture {
+        Fixture::new(&[
+            ("shop/package.json", "{}"),
+            ("api/go.mod", "module example.com/api\n\ngo 1.22\n"),
+            ("notes/README.md", "# Notes\n"),
+            ("archive/old/package.json", "{}"),
+        ])
+    }
+
+    fn scan(roots: &[WorkspaceRoot], cancelled: bool, budget: Duration) -> Vec<ScanEvent> {
+        let events = Mutex::new(Vec::new());
+        scan_workspace_streaming(
+            roots,
+            &["archive".to_string()],
+            &AtomicBool::new(cancelled),
+            budget,
+            &|event| events.lock().unwrap().push(event),
+        );
+        events.into_inner().unwrap()
+    }
+
+    fn root(path: &Path) -> WorkspaceRoot {
+        WorkspaceRoot {
+            path: path.to_string_lossy().to_string(),
+            scan_depth: 2,
+            ..WorkspaceRoot::default()
+        }
+    }
+
+    #[test]
+    fn test_streaming_scan() {
+        let fixture = workspace();
+        // The same root twice still scans each project once
+        let roots = [root(fixture.path()), root(fixture.path())];
+        let events = scan(&roots, false, PROJECT_SCAN_BUDGET);
+
+        let mut projects: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                ScanEvent::Project { project } => Some(project.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        projects.sort();
+        assert_eq!(projects, vec!["api", "shop"]);
+
+        let progress = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    ScanEvent::Progress {
+                        projects_total: 2,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(progress, 2);
+        assert!(matches!(
+            events.last(),
+            Some(ScanEvent::Finished {
+                projects_found: 2,
+                cancelled: false
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cancelled_scan() {
+        let fixture = workspace();
+        let events = scan(&[root(fixture.path())], true, PROJECT_SCAN_BUDGET);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            ScanEvent::Finished {
+                projects_found: 0,
+                cancelled: true
+            }
+        ));
+    }
+
+    #[test]
+    fn test_scan_over_budget() {
+        let fixture = workspace();
+        let events = scan(&[root(fixture.path())], false, Duration::ZERO);
+
+        let timed_out = events
+            .iter()
+            .filter(|event| matches!(event, ScanEvent::TimedOut { .. }))
+            .count();
+        assert_eq!(timed_out, 2);
+        assert!(matches!(
+            events.last(),
+            Some(ScanEvent::Finished {
+                projects_found: 0,
+                ..
+            })
+        ));
+    }
+}
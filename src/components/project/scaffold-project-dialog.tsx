@@ -46,6 +46,7 @@ export function ScaffoldProjectDialog({ trigger, onSuccess }: ScaffoldProjectDia
     updateFormField,
     goBack,
     executeScaffold,
+    cancelScaffold,
     reset,
     getFilteredTemplates,
     getCommandPreview,
@@ -117,6 +118,17 @@ export function ScaffoldProjectDialog({ trigger, onSuccess }: ScaffoldProjectDia
         </div>
       </div>
 
+      {state.templateErrors.length > 0 && (
+        <div className="border-destructive/50 space-y-1 rounded-lg border p-3">
+          <p className="text-destructive text-sm font-medium">{t("scaffold.templateErrors")}</p>
+          {state.templateErrors.map((error) => (
+            <p key={error} className="text-muted-foreground font-mono text-xs">
+              {error}
+            </p>
+          ))}
+        </div>
+      )}
+
       <ScrollArea className="h-[400px]">
         <div className="grid gap-3 pr-4">
           {getFilteredTemplates().map((template) => (
@@ -238,6 +250,19 @@ export function ScaffoldProjectDialog({ trigger, onSuccess }: ScaffoldProjectDia
             </div>
           </div>
 
+          <div className="space-y-2">
+            <Label htmlFor="project-timeout">{t("scaffold.timeout")}</Label>
+            <Input
+              id="project-timeout"
+              type="number"
+              min={0}
+              value={formData.timeoutSecs}
+              onChange={(e) => updateFormField("timeoutSecs", parseInt(e.target.value) || 0)}
+              className="w-28"
+            />
+            <p className="text-muted-foreground text-xs">{t("scaffold.timeoutHint")}</p>
+          </div>
+
           <Separator />
 
           <div className="bg-muted rounded-lg p-4">
@@ -249,16 +274,26 @@ export function ScaffoldProjectDialog({ trigger, onSuccess }: ScaffoldProjectDia
               {getCommandPreview()}
             </code>
           </div>
+
+          {state.error && <p className="text-destructive text-sm">{state.error}</p>}
         </div>
       </div>
     )
   }
 
   const renderExecutingStep = () => (
-    <div className="flex flex-col items-center justify-center py-12">
+    <div className="flex flex-col items-center justify-center py-6">
       <Loader2 className="text-primary mb-4 size-12 animate-spin" />
       <h3 className="text-lg font-semibold">{t("scaffold.creatingProject")}</h3>
-      <p className="text-muted-foreground text-sm">{t("scaffold.pleaseWait")}</p>
+      <p className="text-muted-foreground mb-4 text-sm">{t("scaffold.pleaseWait")}</p>
+      <ScrollArea className="h-48 w-full rounded bg-black">
+        <pre className="px-3 py-2 text-xs whitespace-pre-wrap text-green-400">
+          {state.commandOutput.slice(-200).join("\n")}
+        </pre>
+      </ScrollArea>
+      <Button variant="outline" className="mt-4" onClick={cancelScaffold} disabled={!state.jobId}>
+        {t("common.cancel")}
+      </Button>
     </div>
   )
 
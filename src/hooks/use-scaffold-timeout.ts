@@ -0,0 +1,39 @@
+import { useState, useEffect, useCallback } from "react"
+import { useTranslation } from "@/i18n"
+import { settingsApi } from "@/lib/api"
+import { toast } from "@/lib/toast"
+
+/** Default scaffold timeout, 10 minutes as in the backend settings */
+const DEFAULT_SCAFFOLD_TIMEOUT = 600
+
+export function useScaffoldTimeout() {
+  const { t } = useTranslation()
+  const [timeoutSecs, setTimeoutSecs] = useState(DEFAULT_SCAFFOLD_TIMEOUT)
+
+  useEffect(() => {
+    settingsApi
+      .getScaffoldTimeout()
+      .then(setTimeoutSecs)
+      .catch(() => {})
+  }, [])
+
+  const saveTimeout = useCallback(
+    async (seconds: number) => {
+      try {
+        await settingsApi.saveScaffoldTimeout(seconds)
+        setTimeoutSecs(seconds)
+        toast.success(t("settings.scaffoldTimeout.saveSuccess"))
+      } catch {
+        toast.error(t("settings.scaffoldTimeout.saveError"), {
+          description: t("common.tryAgain"),
+        })
+      }
+    },
+    [t]
+  )
+
+  return {
+    timeoutSecs,
+    saveTimeout,
+  }
+}
@@ -1,9 +1,7 @@
-import { useState, useEffect, useCallback } from "react"
+import { useState, useEffect, useCallback, useRef } from "react"
 import { useProjectStore } from "@/stores/project-store"
-import type { ScaffoldTemplate, ScaffoldPackageManager } from "@/types/scaffold"
-import { scaffoldApi } from "@/lib/api"
-import { useUrlSuffix } from "./use-url-suffix"
-import type { Stack, ServiceType } from "@/types/project"
+import type { ScaffoldTemplate, ScaffoldPackageManager, ScaffoldEvent } from "@/types/scaffold"
+import { scaffoldApi, settingsApi } from "@/lib/api"
 
 export type ScaffoldStep = "package-manager" | "template" | "configure" | "executing" | "success"
 
@@ -12,9 +10,13 @@ interface ScaffoldState {
   selectedPackageManager: ScaffoldPackageManager | null
   selectedTemplate: ScaffoldTemplate | null
   templates: ScaffoldTemplate[]
+  /** Problems with the user's template file */
+  templateErrors: string[]
   isLoading: boolean
   error: string | null
   commandOutput: string[]
+  /** Id of the running scaffold job */
+  jobId: string | null
 }
 
 interface ScaffoldFormData {
@@ -22,6 +24,8 @@ interface ScaffoldFormData {
   folder: string
   url: string
   port: number
+  /** Seconds the steps may run; 0 for no limit */
+  timeoutSecs: number
 }
 
 interface UseScaffoldReturn {
@@ -35,6 +39,7 @@ interface UseScaffoldReturn {
   updateFormField: (field: keyof ScaffoldFormData, value: string | number) => void
   goBack: () => void
   executeScaffold: () => Promise<void>
+  cancelScaffold: () => Promise<void>
   reset: () => void
 
   // Helpers
@@ -47,6 +52,7 @@ const initialFormData: ScaffoldFormData = {
   folder: "",
   url: "",
   port: 3000,
+  timeoutSecs: 600,
 }
 
 const initialState: ScaffoldState = {
@@ -54,14 +60,15 @@ const initialState: ScaffoldState = {
   selectedPackageManager: null,
   selectedTemplate: null,
   templates: [],
+  templateErrors: [],
   isLoading: false,
   error: null,
   commandOutput: [],
+  jobId: null,
 }
 
 export function useScaffold(): UseScaffoldReturn {
-  const { addProject } = useProjectStore()
-  const { urlSuffix } = useUrlSuffix()
+  const { loadProjects } = useProjectStore()
 
   const [state, setState] = useState<ScaffoldState>(initialState)
   const [formData, setFormData] = useState<ScaffoldFormData>(initialFormData)
@@ -71,9 +78,8 @@ export function useScaffold(): UseScaffoldReturn {
   useEffect(() => {
     const loadTemplates = async () => {
       try {
-        const response = await fetch("/scaffold-templates.json")
-        const data = await response.json()
-        setState((prev) => ({ ...prev, templates: data.templates }))
+        const { templates, errors } = await scaffoldApi.getTemplates()
+        setState((prev) => ({ ...prev, templates, templateErrors: errors }))
       } catch (error) {
         setState((prev) => ({
           ...prev,
@@ -84,6 +90,14 @@ export function useScaffold(): UseScaffoldReturn {
     loadTemplates()
   }, [])
 
+  // The form starts from the timeout in the settings
+  useEffect(() => {
+    settingsApi
+      .getScaffoldTimeout()
+      .then((timeoutSecs) => setFormData((prev) => ({ ...prev, timeoutSecs })))
+      .catch(() => {})
+  }, [])
+
   const selectPackageManager = useCallback((pm: ScaffoldPackageManager) => {
     setState((prev) => ({
       ...prev,
@@ -140,83 +154,116 @@ export function useScaffold(): UseScaffoldReturn {
     return Object.keys(newErrors).length === 0
   }, [formData])
 
-  const executeScaffold = useCallback(async () => {
-    if (!validateForm() || !state.selectedTemplate) return
+  // Job the scaffold events are for; events of other jobs are ignored
+  const jobIdRef = useRef<string | null>(null)
+  // Events that arrive while `execute_scaffold` has yet to return the job id
+  const pendingEventsRef = useRef<ScaffoldEvent[] | null>(null)
 
-    setState((prev) => ({ ...prev, step: "executing", isLoading: true }))
+  const handleEvent = useCallback(
+    (event: ScaffoldEvent) => {
+      if (!jobIdRef.current && pendingEventsRef.current) {
+        pendingEventsRef.current.push(event)
+        return
+      }
+      if (event.jobId !== jobIdRef.current) return
 
-    try {
-      // Execute scaffold command
-      const command = state.selectedTemplate.command.replace(/\{name\}/g, formData.name)
-
-      await scaffoldApi.executeScaffold(formData.folder, command)
-
-      // Add project to database
-      const projectPath = `${formData.folder}/${formData.name}`
-
-      // Determine service type based on template category
-      const serviceType: ServiceType =
-        state.selectedTemplate.category === "frontend"
-          ? "frontend"
-          : state.selectedTemplate.category === "backend"
-            ? "backend"
-            : "frontend"
-
-      // Map language to stack
-      const stackMap: Record<string, Stack> = {
-        typescript: state.selectedTemplate.tags.includes("nextjs")
-          ? "next"
-          : state.selectedTemplate.tags.includes("react")
-            ? "react"
-            : "node",
-        javascript: "node",
-        python: state.selectedTemplate.tags.includes("django")
-          ? "django"
-          : state.selectedTemplate.tags.includes("flask")
-            ? "flask"
-            : "node",
-        php: "laravel",
-        rust: "rust",
-        go: "go",
-        java: "nestjs",
-        kotlin: "nestjs",
-        ruby: "rails",
-        elixir: "node",
+      switch (event.type) {
+        case "step":
+          setState((prev) => ({
+            ...prev,
+            commandOutput: [...prev.commandOutput, `$ ${event.command.join(" ")}`],
+          }))
+          break
+        case "output":
+          setState((prev) => ({ ...prev, commandOutput: [...prev.commandOutput, event.line] }))
+          break
+        case "finished":
+          jobIdRef.current = null
+          if (event.status === "succeeded") {
+            loadProjects()
+          }
+          setState((prev) => ({
+            ...prev,
+            jobId: null,
+            isLoading: false,
+            step: event.status === "succeeded" ? "success" : "configure",
+            error:
+              event.status === "succeeded" || event.status === "cancelled"
+                ? null
+                : event.status === "timedOut"
+                  ? "Scaffold timed out"
+                  : (event.error ?? "Failed to create project"),
+          }))
+          break
       }
+    },
+    [loadProjects]
+  )
 
-      await addProject({
-        name: formData.name,
-        folder: projectPath,
-        services: [
-          {
-            name: state.selectedTemplate.name,
-            type: serviceType,
-            stack: stackMap[state.selectedTemplate.language] || "other",
-            path: projectPath,
-            url: formData.url,
-            port: formData.port,
-            command: `${state.selectedTemplate.packageManager} run dev`,
-          },
-        ],
-      })
+  useEffect(() => {
+    const unlisten = scaffoldApi.onScaffoldEvent(handleEvent)
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [handleEvent])
 
-      setState((prev) => ({
-        ...prev,
-        step: "success",
-        isLoading: false,
-      }))
+  const executeScaffold = useCallback(async () => {
+    if (!validateForm() || !state.selectedTemplate) return
+
+    setState((prev) => ({
+      ...prev,
+      step: "executing",
+      isLoading: true,
+      error: null,
+      commandOutput: [],
+    }))
+
+    pendingEventsRef.current = []
+    try {
+      // Runs the template in the background; the project is registered with
+      // its detected services once the job reports success
+      const jobId = await scaffoldApi.executeScaffold(
+        state.selectedTemplate.id,
+        formData.folder,
+        {
+          name: formData.name,
+          packageManager: state.selectedPackageManager ?? undefined,
+          port: formData.port,
+        },
+        formData.url,
+        formData.timeoutSecs
+      )
+      jobIdRef.current = jobId
+      setState((prev) => ({ ...prev, jobId }))
+      const pending = pendingEventsRef.current
+      pendingEventsRef.current = null
+      pending?.forEach(handleEvent)
     } catch (error) {
+      pendingEventsRef.current = null
       setState((prev) => ({
         ...prev,
+        step: "configure",
         isLoading: false,
-        error: error instanceof Error ? error.message : "Failed to create project",
+        error:
+          typeof error === "string"
+            ? error
+            : error instanceof Error
+              ? error.message
+              : "Failed to create project",
       }))
     }
-  }, [validateForm, state.selectedTemplate, formData, addProject, urlSuffix])
+  }, [validateForm, state.selectedTemplate, state.selectedPackageManager, formData, handleEvent])
+
+  const cancelScaffold = useCallback(async () => {
+    if (jobIdRef.current) {
+      await scaffoldApi.cancelScaffold(jobIdRef.current)
+    }
+  }, [])
 
   const reset = useCallback(() => {
     setState(initialState)
-    setFormData(initialFormData)
+    // Keep the timeout loaded from the settings
+    setFormData((prev) => ({ ...initialFormData, timeoutSecs: prev.timeoutSecs }))
     setErrors({})
   }, [])
 
@@ -225,10 +272,40 @@ export function useScaffold(): UseScaffoldReturn {
     return state.templates.filter((t) => t.packageManager === state.selectedPackageManager)
   }, [state.templates, state.selectedPackageManager])
 
-  const getCommandPreview = useCallback(() => {
-    if (!state.selectedTemplate) return ""
-    return state.selectedTemplate.command.replace(/\{name\}/g, formData.name || "my-project")
-  }, [state.selectedTemplate, formData.name])
+  // The backend plans the steps, so the preview shows exactly what runs
+  const [commandPreview, setCommandPreview] = useState("")
+  useEffect(() => {
+    const template = state.selectedTemplate
+    if (!template || !formData.folder) {
+      setCommandPreview("")
+      return
+    }
+
+    let stale = false
+    scaffoldApi
+      .previewScaffold(template.id, formData.folder, {
+        name: formData.name || "my-project",
+        packageManager: state.selectedPackageManager ?? undefined,
+        port: formData.port,
+      })
+      .then((plan) => {
+        if (!stale) setCommandPreview(plan.steps.map((step) => step.join(" ")).join(" && "))
+      })
+      .catch((error) => {
+        if (!stale) setCommandPreview(typeof error === "string" ? error : "")
+      })
+    return () => {
+      stale = true
+    }
+  }, [
+    state.selectedTemplate,
+    state.selectedPackageManager,
+    formData.folder,
+    formData.name,
+    formData.port,
+  ])
+
+  const getCommandPreview = useCallback(() => commandPreview, [commandPreview])
 
   return {
     state,
@@ -239,6 +316,7 @@ export function useScaffold(): UseScaffoldReturn {
     updateFormField,
     goBack,
     executeScaffold,
+    cancelScaffold,
     reset,
     getFilteredTemplates,
     getCommandPreview,
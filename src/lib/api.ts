@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core"
+import { listen, type UnlistenFn } from "@tauri-apps/api/event"
 
 export const api = {
   async invoke<T>(command: string, args?: Record<string, unknown>): Promise<T> {
@@ -22,6 +23,15 @@ export const settingsApi = {
   async getIdeCommand(): Promise<string | null> {
     return api.invoke<string | null>("get_ide_command")
   },
+
+  /** Seconds before a scaffold is killed; 0 disables the timeout */
+  async saveScaffoldTimeout(seconds: number): Promise<void> {
+    return api.invoke("save_scaffold_timeout", { seconds })
+  },
+
+  async getScaffoldTimeout(): Promise<number> {
+    return api.invoke<number>("get_scaffold_timeout")
+  },
 }
 
 export const workspaceApi = {
@@ -32,6 +42,22 @@ export const workspaceApi = {
   async getRootPath(): Promise<string | null> {
     return api.invoke<string | null>("get_root_path")
   },
+
+  async saveWorkspaceRoots(roots: WorkspaceRoot[]): Promise<void> {
+    return api.invoke("save_workspace_roots", { roots })
+  },
+
+  async getWorkspaceRoots(): Promise<WorkspaceRoot[]> {
+    return api.invoke<WorkspaceRoot[]>("get_workspace_roots")
+  },
+}
+
+/** Workspace root as stored in settings.json */
+export interface WorkspaceRoot {
+  path: string
+  scan_depth: number
+  ignore: string[]
+  auto_import: boolean
 }
 
 export const gitApi = {
@@ -58,6 +84,15 @@ export const scanDepthApi = {
   },
 }
 
+export const ignorePatternsApi = {
+  async saveIgnorePatterns(patterns: string[]): Promise<void> {
+    return api.invoke("save_ignore_patterns", { patterns })
+  },
+  async getIgnorePatterns(): Promise<string[]> {
+    return api.invoke<string[]>("get_ignore_patterns")
+  },
+}
+
 /** Row returned by `create_project` / `get_project` / `get_projects` */
 export interface ProjectRow {
   id: string
@@ -178,6 +213,14 @@ export const projectApi = {
     return api.invoke<boolean>("project_exists_by_folder", { folder })
   },
 
+  async exportProject(projectId: string, format: "toml" | "json" = "toml"): Promise<string> {
+    return api.invoke<string>("export_project", { projectId, format })
+  },
+
+  async importProject(path: string): Promise<ProjectRow> {
+    return api.invoke<ProjectRow>("import_project", { path })
+  },
+
   async scanProjectServices(
     path: string,
     maxDepth: number = 2
@@ -188,9 +231,10 @@ export const projectApi = {
     })
   },
 
+  /** Scans every configured workspace root when no path is given */
   async scanWorkspaceServices(
-    workspacePath: string,
-    maxDepth: number = 2
+    workspacePath?: string,
+    maxDepth?: number
   ): Promise<import("@/types/project").DetectedProject[]> {
     return api.invoke<import("@/types/project").DetectedProject[]>("scan_workspace_services", {
       workspacePath,
@@ -203,6 +247,12 @@ export const projectApi = {
       path,
     })
   },
+
+  async explainFrameworkDetection(path: string): Promise<import("@/types/project").DetectionEvidence> {
+    return api.invoke<import("@/types/project").DetectionEvidence>("explain_framework_detection", {
+      path,
+    })
+  },
 }
 
 export const ideApi = {
@@ -211,8 +261,136 @@ export const ideApi = {
   },
 }
 
+export const launchApi = {
+  /** Wraps a service command with its version manager and login-shell environment */
+  async getLaunchCommand(
+    servicePath: string,
+    command: string,
+    projectPath?: string
+  ): Promise<import("@/types/project").LaunchCommand> {
+    return api.invoke("get_launch_command", { servicePath, command, projectPath })
+  },
+}
+
 export const scaffoldApi = {
-  async executeScaffold(workingDir: string, command: string): Promise<string> {
-    return api.invoke<string>("execute_scaffold", { workingDir, command })
+  /** Built-in templates plus the user's own from the config dir */
+  async getTemplates(): Promise<import("@/types/scaffold").ScaffoldConfig> {
+    return api.invoke("get_scaffold_templates")
+  },
+  async previewScaffold(
+    templateId: string,
+    folder: string,
+    params: import("@/types/scaffold").ScaffoldParams
+  ): Promise<import("@/types/scaffold").ScaffoldPlan> {
+    return api.invoke("preview_scaffold", { templateId, folder, params })
+  },
+  /**
+   * Starts creating `<folder>/<name>` from the template and returns the job id.
+   * The project is registered once the `finished` event reports success.
+   */
+  async executeScaffold(
+    templateId: string,
+    folder: string,
+    params: import("@/types/scaffold").ScaffoldParams,
+    url?: string,
+    timeoutSecs?: number
+  ): Promise<string> {
+    return api.invoke<string>("execute_scaffold", { templateId, folder, params, url, timeoutSecs })
+  },
+  async cancelScaffold(jobId: string): Promise<boolean> {
+    return api.invoke<boolean>("cancel_scaffold", { jobId })
+  },
+  async onScaffoldEvent(
+    handler: (event: import("@/types/scaffold").ScaffoldEvent) => void
+  ): Promise<UnlistenFn> {
+    return listen<import("@/types/scaffold").ScaffoldEvent>("scaffold", (event) =>
+      handler(event.payload)
+    )
+  },
+  /** `create-vite my-app` -> `pnpm dlx create-vite my-app`, `bunx create-vite my-app`... */
+  async getDlxCommand(
+    packageManager: import("@/types/project").PackageManager,
+    command: string
+  ): Promise<string> {
+    return api.invoke<string>("get_dlx_command", { packageManager, command })
+  },
+}
+
+/** Backup returned by `backup_state` / `get_backups` */
+export interface BackupInfo {
+  name: string
+  path: string
+  hasSettings: boolean
+}
+
+export const backupApi = {
+  async backupState(): Promise<BackupInfo> {
+    return api.invoke<BackupInfo>("backup_state")
+  },
+  async getBackups(): Promise<BackupInfo[]> {
+    return api.invoke<BackupInfo[]>("get_backups")
+  },
+  async restoreState(path: string): Promise<void> {
+    return api.invoke("restore_state", { path })
+  },
+}
+
+export const scanApi = {
+  /** Starts a background scan and returns its job id */
+  async startWorkspaceScan(workspacePath?: string, maxDepth?: number): Promise<string> {
+    return api.invoke<string>("start_workspace_scan", { workspacePath, maxDepth })
+  },
+  async cancelWorkspaceScan(jobId: string): Promise<boolean> {
+    return api.invoke<boolean>("cancel_workspace_scan", { jobId })
+  },
+  async onScanEvent(
+    handler: (event: import("@/types/project").ScanEvent) => void
+  ): Promise<UnlistenFn> {
+    return listen<import("@/types/project").ScanEvent>("workspace-scan", (event) =>
+      handler(event.payload)
+    )
+  },
+}
+
+export const installApi = {
+  async getInstallStatus(
+    serviceId: string
+  ): Promise<import("@/types/project").InstallStatus | null> {
+    return api.invoke("get_install_status", { serviceId })
+  },
+  /** Starts the service's install command; progress arrives as install events */
+  async installDependencies(serviceId: string): Promise<void> {
+    return api.invoke<void>("install_dependencies", { serviceId })
+  },
+  async onInstallEvent(
+    handler: (event: import("@/types/project").InstallEvent) => void
+  ): Promise<UnlistenFn> {
+    return listen<import("@/types/project").InstallEvent>("dependency-install", (event) =>
+      handler(event.payload)
+    )
+  },
+}
+
+export const diskApi = {
+  /** Measures artifact directories of the given projects (all when omitted) and returns the job id */
+  async startDiskUsageScan(projectIds?: string[]): Promise<string> {
+    return api.invoke<string>("start_disk_usage_scan", { projectIds })
+  },
+  async cancelDiskUsageScan(jobId: string): Promise<boolean> {
+    return api.invoke<boolean>("cancel_disk_usage_scan", { jobId })
+  },
+  async onDiskUsageEvent(
+    handler: (event: import("@/types/project").DiskUsageEvent) => void
+  ): Promise<UnlistenFn> {
+    return listen<import("@/types/project").DiskUsageEvent>("disk-usage", (event) =>
+      handler(event.payload)
+    )
+  },
+  async cleanProject(
+    projectId: string,
+    kinds: import("@/types/project").ArtifactKind[],
+    options: { mode?: import("@/types/project").CleanMode; dryRun?: boolean } = {}
+  ): Promise<import("@/types/project").CleanReport> {
+    return api.invoke("clean_project", { projectId, kinds, ...options })
   },
 }
@@ -98,10 +98,12 @@ export const SCAFFOLD_PACKAGE_MANAGERS = [
   "bun",
   "deno",
   "composer",
+  "bundler",
   "pip",
   "poetry",
   "pipenv",
   "cargo",
+  "goMod",
   "gradle",
   "maven",
   "dotnet",
@@ -175,10 +177,12 @@ export const PACKAGE_MANAGER_CONFIGS: Record<
   bun: { name: "Bun", icon: "🥯", color: "#FFD700" },
   deno: { name: "Deno", icon: "🦕", color: "#000000" },
   composer: { name: "Composer", icon: "🎼", color: "#885630" },
+  bundler: { name: "Bundler", icon: "💎", color: "#CC342D" },
   pip: { name: "pip", icon: "🐍", color: "#3776AB" },
   poetry: { name: "Poetry", icon: "📜", color: "#60A5FA" },
   pipenv: { name: "Pipenv", icon: "🐍", color: "#3B82F6" },
   cargo: { name: "Cargo", icon: "🦀", color: "#DEA584" },
+  goMod: { name: "Go", icon: "🐹", color: "#00ADD8" },
   gradle: { name: "Gradle", icon: "🐘", color: "#02303A" },
   maven: { name: "Maven", icon: "🪶", color: "#C71A36" },
   dotnet: { name: ".NET", icon: "🎯", color: "#512BD4" },
@@ -6,12 +6,22 @@ import { useIDE } from "@/hooks/use-ide"
 import { useWorkspace } from "@/hooks/use-workspace"
 import { useUrlSuffix } from "@/hooks/use-url-suffix"
 import { useScanDepth } from "@/hooks/use-scan-depth"
+import { useScaffoldTimeout } from "@/hooks/use-scaffold-timeout"
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card"
 import { Label } from "@/components/ui/label"
 import { Button } from "@/components/ui/button"
 import { Input } from "@/components/ui/input"
 import { IDE_CONFIGS, type IDE, type Theme } from "@/types/settings"
-import { Settings, Monitor, FolderOpen, Check, Globe, Layers, Terminal } from "lucide-react"
+import {
+  Settings,
+  Monitor,
+  FolderOpen,
+  Check,
+  Globe,
+  Layers,
+  Terminal,
+  Timer,
+} from "lucide-react"
 import { cn } from "@/lib/utils"
 
 export function SettingsPage() {
@@ -24,12 +34,18 @@ export function SettingsPage() {
   const { scanDepth, saveDepth } = useScanDepth()
   const [suffixInput, setSuffixInput] = useState(urlSuffix)
   const [depthInput, setDepthInput] = useState(scanDepth.toString())
+  const { timeoutSecs, saveTimeout } = useScaffoldTimeout()
+  const [timeoutInput, setTimeoutInput] = useState(timeoutSecs.toString())
   const [ideCommandInput, setIdeCommandInput] = useState(ideCommand)
 
   useEffect(() => {
     setIdeCommandInput(ideCommand)
   }, [ideCommand])
 
+  useEffect(() => {
+    setTimeoutInput(timeoutSecs.toString())
+  }, [timeoutSecs])
+
   const handleThemeChange = (newTheme: Theme) => {
     setTheme(newTheme)
   }
@@ -60,6 +76,13 @@ export function SettingsPage() {
     }
   }
 
+  const handleSaveTimeout = async () => {
+    const seconds = parseInt(timeoutInput, 10)
+    if (seconds >= 0) {
+      await saveTimeout(seconds)
+    }
+  }
+
   return (
     <div className="flex flex-1 flex-col overflow-auto">
       <div className="mx-auto w-full max-w-3xl space-y-6 p-4 lg:p-8">
@@ -228,6 +251,33 @@ export function SettingsPage() {
             <p className="text-muted-foreground mt-2 text-xs">{t("settings.scanDepth.hint")}</p>
           </CardContent>
         </Card>
+
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Timer className="size-5" />
+              {t("settings.scaffoldTimeout.title")}
+            </CardTitle>
+            <CardDescription>{t("settings.scaffoldTimeout.description")}</CardDescription>
+          </CardHeader>
+          <CardContent>
+            <div className="flex items-center gap-3">
+              <Input
+                type="number"
+                min={0}
+                value={timeoutInput}
+                onChange={(e) => setTimeoutInput(e.target.value)}
+                className="w-28"
+              />
+              <Button variant="outline" onClick={handleSaveTimeout}>
+                {t("settings.url.save")}
+              </Button>
+            </div>
+            <p className="text-muted-foreground mt-2 text-xs">
+              {t("settings.scaffoldTimeout.hint")}
+            </p>
+          </CardContent>
+        </Card>
       </div>
     </div>
   )
@@ -1,20 +1,20 @@
 import { create } from "zustand"
 import {
   projectApi,
+  scanApi,
   type CreateProjectRequest,
   type CreateServiceRequest,
   type ProjectWithServices,
   type ServiceRow,
-  scanDepthApi,
   urlApi,
 } from "@/lib/api"
-import { workspaceApi } from "@/lib/api"
 import { useSettingsStore } from "@/stores/settings-store"
 import type {
   Project,
   ProjectService,
   ProjectStatus,
   DetectedProject as DetectedProjectType,
+  ScanEvent,
 } from "@/types/project"
 import type { ProjectFormValues } from "@/schemas/project"
 import {
@@ -83,13 +83,61 @@ function mapDetectedProjectToProject(detected: DetectedProjectType, urlSuffix: s
   }
 }
 
+/** Registers a detected project unless it is already known; null when skipped */
+async function importDetectedProject(
+  detected: DetectedProjectType,
+  urlSuffix: string
+): Promise<Project | null> {
+  if (!detected.autoImport) {
+    return null
+  }
+
+  const exists = await projectApi.projectExistsByFolder(detected.path)
+  if (exists) {
+    return null
+  }
+
+  const project = mapDetectedProjectToProject(detected, urlSuffix)
+
+  const projectRequest: CreateProjectRequest = {
+    id: project.id,
+    name: project.name,
+    folder: project.folder,
+  }
+
+  const servicesRequest: CreateServiceRequest[] = project.services.map((s) => ({
+    id: s.id,
+    project_id: project.id,
+    name: s.name,
+    service_type: s.type,
+    stack: s.stack,
+    path: s.path,
+    url: s.url,
+    port: s.port,
+    command: s.command,
+  }))
+
+  await projectApi.createProject(projectRequest, servicesRequest)
+  return project
+}
+
+export interface ScanProgress {
+  projectsScanned: number
+  projectsTotal: number
+  projectsFound: number
+  currentPath: string
+}
+
 interface ProjectState {
   projects: Project[]
   selectedProjectId: string | null
   isLoading: boolean
+  scanJobId: string | null
+  scanProgress: ScanProgress | null
 
   loadProjects: () => Promise<void>
   scanWorkspace: () => Promise<void>
+  cancelScan: () => Promise<void>
   addProject: (data: ProjectFormValues) => Promise<void>
   removeProject: (id: string) => Promise<void>
   updateProject: (id: string, data: { name: string; folder: string }) => Promise<void>
@@ -115,6 +163,8 @@ export const useProjectStore = create<ProjectState>()((set, get) => ({
   projects: [],
   selectedProjectId: null,
   isLoading: false,
+  scanJobId: null,
+  scanProgress: null,
 
   loadProjects: async () => {
     set({ isLoading: true })
@@ -128,55 +178,73 @@ export const useProjectStore = create<ProjectState>()((set, get) => ({
   },
 
   scanWorkspace: async () => {
-    try {
-      const rootPath = await workspaceApi.getRootPath()
-      if (!rootPath) {
-        return
-      }
+    if (get().scanJobId) {
+      return
+    }
 
-      const scanDepth = await scanDepthApi.getScanDepth()
+    try {
       const urlSuffix =
         useSettingsStore.getState().urlSuffix || (await urlApi.getSuffix()) || "test"
 
-      const detectedProjects = await projectApi.scanWorkspaceServices(rootPath, scanDepth)
+      // Projects are registered one at a time, in the order they stream in
+      let imports = Promise.resolve()
+      let jobId: string | null = null
+      const early: ScanEvent[] = []
+      let finish: () => void = () => {}
+      const finished = new Promise<void>((resolve) => {
+        finish = resolve
+      })
 
-      for (const detected of detectedProjects) {
-        const exists = await projectApi.projectExistsByFolder(detected.path)
-        if (exists) {
-          continue
+      const handle = (event: ScanEvent) => {
+        switch (event.type) {
+          case "progress":
+            set({ scanProgress: event })
+            break
+          case "project":
+            imports = imports.then(async () => {
+              try {
+                const project = await importDetectedProject(event.project, urlSuffix)
+                if (project) {
+                  set((currentState) => ({
+                    projects: [...currentState.projects, project],
+                  }))
+                }
+              } catch {}
+            })
+            break
+          case "finished":
+            imports.then(finish)
+            break
         }
+      }
 
-        try {
-          const project = mapDetectedProjectToProject(detected, urlSuffix || "test")
-
-          const projectRequest: CreateProjectRequest = {
-            id: project.id,
-            name: project.name,
-            folder: project.folder,
-          }
-
-          const servicesRequest: CreateServiceRequest[] = project.services.map((s) => ({
-            id: s.id,
-            project_id: project.id,
-            name: s.name,
-            service_type: s.type,
-            stack: s.stack,
-            path: s.path,
-            url: s.url,
-            port: s.port,
-            command: s.command,
-          }))
-
-          await projectApi.createProject(projectRequest, servicesRequest)
-
-          set((currentState) => ({
-            projects: [...currentState.projects, project],
-          }))
-        } catch {}
+      const unlisten = await scanApi.onScanEvent((event) => {
+        if (jobId === null) {
+          early.push(event)
+        } else if (event.jobId === jobId) {
+          handle(event)
+        }
+      })
+
+      try {
+        jobId = await scanApi.startWorkspaceScan()
+        set({ scanJobId: jobId, scanProgress: null })
+        early.filter((event) => event.jobId === jobId).forEach(handle)
+        await finished
+      } finally {
+        unlisten()
+        set({ scanJobId: null, scanProgress: null })
       }
     } catch {}
   },
 
+  cancelScan: async () => {
+    const jobId = get().scanJobId
+    if (jobId) {
+      await scanApi.cancelWorkspaceScan(jobId)
+    }
+  },
+
   addProject: async (data) => {
     const projectId = generateId()
 
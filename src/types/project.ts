@@ -103,6 +103,8 @@ export type Framework =
   | "kotlin"
   | "cSharp"
   | "elixir"
+  // User-defined detection rule
+  | "custom"
   | "unknown"
 
 export type PackageManager =
@@ -118,6 +120,8 @@ export type PackageManager =
   | "pipenv"
   | "conda"
   | "uv"
+  | "pdm"
+  | "hatch"
   | "composer"
   | "bundler"
   | "goMod"
@@ -149,18 +153,47 @@ export type MonorepoTool =
   | "lerna"
   | "rush"
   | "cargoWorkspace"
-  | "none"
+  | "maven"
+  | "gradle"
+  | "goWorkspace"
+
+export interface WorkspaceSystem {
+  tool: MonorepoTool
+  /** Relative to the repository; "." for the root */
+  root: string
+}
 
 // ============================================================================
 // Detected project/service shapes (returned from Rust scanner)
 // ============================================================================
 
+export type TaskSource =
+  | "makefile"
+  | "justfile"
+  | "taskfile"
+  | "pyProject"
+  | "pipfile"
+  | "launchSettings"
+  | "cargo"
+  | "nx"
+  | "turborepo"
+  | "deno"
+
+export interface DetectedTask {
+  name: string
+  description?: string
+  source: TaskSource
+  command: string
+  steps: string[]
+}
+
 export interface DetectedService {
   name: string
   path: string
   relativePath: string
   category: ServiceCategory
   framework: Framework
+  frameworkLabel?: string
   stack: string
   packageManager: PackageManager
   port?: number
@@ -170,21 +203,122 @@ export interface DetectedService {
   installCommand?: string
   isDockerService: boolean
   dockerServiceName?: string
+  tasks: DetectedTask[]
+  detectionEvidence?: DetectionEvidence
+  envFiles: string[]
+  dependsOn: string[]
+  healthCheck?: HealthCheck
+  toolchains: ToolchainRequirement[]
+  installStatus?: InstallStatus
+}
+
+export interface HealthCheck {
+  url?: string
+  command?: string
+  interval?: number
+}
+
+export type Toolchain = "node" | "python" | "rust" | "go"
+
+export interface ToolchainRequirement {
+  toolchain: Toolchain
+  required: string
+  source: string
+  installed?: string
+  mismatch: boolean
+}
+
+export type InstallStatus = "installed" | "stale" | "missing"
+
+export type VersionManager = "mise" | "asdf" | "nvm" | "pyenv"
+
+export interface LaunchCommand {
+  command: string
+  versionManager?: VersionManager
+  /** Directory put first on PATH; the rest of the environment stays in the backend */
+  pathPrefix?: string
+}
+
+export interface ManifestError {
+  line: number
+  message: string
+}
+
+export interface FrameworkCandidate {
+  framework: Framework
+  rule?: string
+  score: number
+  evidence: string[]
+}
+
+export interface DetectionEvidence {
+  framework: Framework
+  confidence: number
+  candidates: FrameworkCandidate[]
 }
 
 export interface DetectedProject {
   name: string
   path: string
   isMonorepo: boolean
-  monorepoTool: MonorepoTool
+  workspaceSystems: WorkspaceSystem[]
   isTauri: boolean
   hasDocker: boolean
   hasDockerCompose: boolean
   rootPackageManager: PackageManager
   services: DetectedService[]
   workspaces: string[]
+  manifest?: string
+  manifestErrors: ManifestError[]
+  /** Detection rule files that failed to load and the rules dropped from them */
+  ruleErrors: string[]
+  workspaceRoot?: string
+  autoImport: boolean
 }
 
+/** Payload of the `workspace-scan` event emitted by a running scan job */
+export type ScanEvent = { jobId: string } & (
+  | {
+      type: "progress"
+      projectsScanned: number
+      projectsTotal: number
+      projectsFound: number
+      currentPath: string
+    }
+  | { type: "project"; project: DetectedProject }
+  | { type: "timedOut"; path: string }
+  | { type: "finished"; projectsFound: number; cancelled: boolean }
+)
+
+export type InstallEvent = { serviceId: string } & (
+  | { type: "started"; command: string }
+  | { type: "output"; line: string; stderr: boolean }
+  | { type: "finished"; success: boolean; exitCode?: number; status?: InstallStatus }
+)
+
+export type ArtifactKind = "nodeModules" | "target" | "next" | "dist" | "pycache" | "gradle"
+
+export interface Artifact {
+  kind: ArtifactKind
+  path: string
+  size: number
+  files: number
+}
+
+export type CleanMode = "trash" | "delete"
+
+export interface CleanReport {
+  dryRun: boolean
+  removed: Artifact[]
+  freed: number
+  errors: { path: string; message: string }[]
+}
+
+export type DiskUsageEvent = { jobId: string } & (
+  | { type: "project"; projectId: string; totalSize: number; artifacts: Artifact[] }
+  | { type: "finished"; cancelled: boolean }
+)
+
 // ============================================================================
 // Core domain models
 // ============================================================================
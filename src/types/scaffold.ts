@@ -8,6 +8,7 @@ export type { ScaffoldPackageManager, ScaffoldLanguage } from "@/lib/constants"
 export { PACKAGE_MANAGER_CONFIGS, LANGUAGE_CONFIGS } from "@/lib/constants"
 import type { ScaffoldPackageManager, ScaffoldLanguage } from "@/lib/constants"
 import type { ProjectCategory } from "@/lib/constants"
+import type { PackageManager } from "@/types/project"
 
 export interface ScaffoldTemplate {
   id: string
@@ -16,14 +17,43 @@ export interface ScaffoldTemplate {
   language: ScaffoldLanguage
   packageManager: ScaffoldPackageManager
   category: ProjectCategory
-  command: string
+  /** Program and arguments of each step, run without a shell */
+  steps: string[][]
+  /** Steps run inside a freshly created project folder */
+  createFolder: boolean
+  /** Files written into the project after the steps, by relative path */
+  files: Record<string, string>
   defaultPort: number
   icon?: string
   tags: string[]
+  /** "user" for templates from scaffold-templates.* in the config dir */
+  source: "builtin" | "user"
 }
 
+/** Values substituted for `{name}`, `{packageManager}`, `{language}` and `{port}` */
+export interface ScaffoldParams {
+  name: string
+  packageManager?: PackageManager
+  language?: ScaffoldLanguage
+  port?: number
+}
+
+/** A template instantiated for a target folder, as returned by `preview_scaffold` */
+export interface ScaffoldPlan {
+  templateId: string
+  projectDir: string
+  workingDir: string
+  createFolder: boolean
+  steps: string[][]
+  files: Record<string, string>
+  port: number
+}
+
+/** Templates as returned by `get_scaffold_templates` */
 export interface ScaffoldConfig {
   templates: ScaffoldTemplate[]
+  /** Why the user's template file or some of its templates were left out */
+  errors: string[]
 }
 
 export interface ScaffoldProjectData {
@@ -33,3 +63,19 @@ export interface ScaffoldProjectData {
   url: string
   port: number
 }
+
+export type ScaffoldStatus = "succeeded" | "failed" | "cancelled" | "timedOut"
+
+/** Payload of the `scaffold` event */
+export type ScaffoldEvent = { jobId: string } & (
+  | { type: "step"; index: number; command: string[] }
+  | { type: "output"; line: string; stderr: boolean }
+  | {
+      type: "finished"
+      status: ScaffoldStatus
+      exitCode?: number
+      durationMs: number
+      error?: string
+      project?: import("@/lib/api").ProjectRow
+    }
+)